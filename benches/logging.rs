@@ -0,0 +1,68 @@
+//! Logging throughput benchmarks
+//!
+//! Run with `cargo bench --bench logging`. Every logger writes to a file in
+//! a temporary directory.
+//!
+//! - enabled: an INFO entry written to a file, text vs JSON pattern
+//! - filtered: a DEBUG call on a logger at INFO, formatted eagerly
+//!   (`log_debug(&format!(..))`) vs the lazy `log_debug!` macro, which only
+//!   checks the level
+//! - timestamp: formatting every timestamp vs `TimestampCache`
+
+use std::hint::black_box;
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use tempfile::{tempdir, TempDir};
+
+use FreedomLogger::core::time;
+use FreedomLogger::format::{format_timestamp, TimestampCache};
+use FreedomLogger::{log_debug, log_init_with_level, LogLevel, Logger, LoggerConfig, Pattern};
+
+/// Logger at INFO writing to `bench.log`/`bench.json` in a new temp dir
+fn file_logger(pattern: Pattern) -> (Logger, TempDir) {
+    let temp_dir = tempdir().unwrap();
+    let mut config = LoggerConfig::basic(pattern, temp_dir.path().to_path_buf(), "bench".to_string());
+    config.log_level = Some(LogLevel::Info);
+    // Keep rotation out of the numbers
+    config.max_file_size = u64::MAX;
+    (Logger::new(config), temp_dir)
+}
+
+fn enabled(c: &mut Criterion) {
+    let mut group = c.benchmark_group("enabled");
+    for (name, pattern) in [("text", Pattern::Basic), ("json", Pattern::Json)] {
+        let (logger, _temp_dir) = file_logger(pattern);
+        group.bench_function(name, |b| b.iter(|| logger.info(black_box("User logged in successfully"))));
+    }
+    group.finish();
+}
+
+fn filtered(c: &mut Criterion) {
+    let temp_dir = tempdir().unwrap();
+    log_init_with_level(Pattern::Basic, temp_dir.path(), "global", LogLevel::Info);
+    let user = "alice";
+    let elapsed = Duration::from_millis(42);
+
+    let mut group = c.benchmark_group("filtered_debug");
+    group.bench_function("eager_format", |b| {
+        b.iter(|| log_debug(&format!("Request from {} took {:?}", black_box(user), black_box(elapsed))))
+    });
+    group.bench_function("lazy_macro", |b| {
+        b.iter(|| FreedomLogger::log_debug!("Request from {} took {:?}", black_box(user), black_box(elapsed)))
+    });
+    group.finish();
+}
+
+fn timestamp(c: &mut Criterion) {
+    let cache = TimestampCache::new();
+    let mut group = c.benchmark_group("timestamp");
+    group.bench_function("uncached", |b| b.iter(|| format_timestamp(black_box(time::now()), false)));
+    group.bench_function("cached", |b| {
+        b.iter(|| cache.get(black_box(time::now()), |now| format_timestamp(now, false)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, enabled, filtered, timestamp);
+criterion_main!(benches);
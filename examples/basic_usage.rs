@@ -1,4 +1,4 @@
-use FreedomLogger::{log_init_with_level, log_info, log_warning, log_error, Pattern, LogLevel, Logger};
+use FreedomLogger::{log_info, log_warning, log_error, Pattern, LogLevel};
 
 fn main() {
     // Detailed pattern met file:line info
@@ -14,4 +14,4 @@ fn main() {
     log_error("Error with location info");
 
     println!("Check ./logs/detailed_test.log");
-}
\ No newline at end of file
+}
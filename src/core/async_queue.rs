@@ -0,0 +1,311 @@
+//! Background writer for FreedomLogger's async mode
+//!
+//! With `LoggerConfig::async_queue_capacity` set, log calls only capture the
+//! entry and push it onto a bounded queue; a background thread formats and
+//! writes it. Logging never blocks on disk I/O this way.
+//!
+//! When the queue is full (the writer can't keep up) the entry is dropped
+//! instead of blocking the caller. Dropped entries are counted, and the
+//! writer reports them in the log itself as a WARNING line
+//! ("dropped N messages due to backpressure") at most once per interval,
+//! and always before a flush completes.
+//!
+//! Once stopped (shutdown or drop) the queue accepts nothing more: a late
+//! entry is discarded and reported as `LoggerError::ChannelSendFailed`.
+
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex, RwLock};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use crate::core::time::Instant;
+use crate::core::config::LogLevel;
+use crate::core::logger::{Entry, LoggerCore, current_thread_id, current_thread_name};
+use crate::error::{LoggerError, LoggerResult};
+
+/// Minimum time between two backpressure warnings
+const DROP_REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Work for the background writer
+enum Message {
+    /// Write one entry
+    Entry(Entry<'static>),
+    /// Flush the writers, then acknowledge on the given channel
+    Flush(SyncSender<()>),
+}
+
+/// Bounded queue to the background writer thread
+pub(crate) struct AsyncQueue {
+    /// Sending side of the queue (None once stopped)
+    ///
+    /// Log calls only take the read lock, so they never wait for each other;
+    /// the write lock is taken once, by `stop`.
+    sender: RwLock<Option<SyncSender<Message>>>,
+    /// Background writer thread (None once stopped)
+    worker: Mutex<Option<JoinHandle<()>>>,
+    /// Entries dropped on a full queue since creation
+    dropped: AtomicU64,
+    /// Dropped entries not yet reported in the log
+    unreported: Arc<AtomicU64>,
+}
+
+impl AsyncQueue {
+    /// Start the background writer
+    ///
+    /// # Arguments
+    /// * `core` - Shared writing state of the logger
+    /// * `capacity` - Maximum number of queued entries (at least 1)
+    pub(crate) fn start(core: Arc<LoggerCore>, capacity: usize) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(capacity.max(1));
+        let unreported = Arc::new(AtomicU64::new(0));
+
+        let worker_unreported = Arc::clone(&unreported);
+        let worker = thread::Builder::new()
+            .name("freedom-logger".to_string())
+            .spawn(move || run_worker(&core, receiver, &worker_unreported))
+            .expect("failed to spawn the async logger thread");
+
+        Self {
+            sender: RwLock::new(Some(sender)),
+            worker: Mutex::new(Some(worker)),
+            dropped: AtomicU64::new(0),
+            unreported,
+        }
+    }
+
+    /// Queue an entry, dropping it if the queue is full
+    ///
+    /// # Returns
+    /// ChannelSendFailed if the writer has stopped (the entry is discarded)
+    pub(crate) fn push(&self, entry: Entry<'static>) -> LoggerResult<()> {
+        let sender = self.sender.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let Some(sender) = sender.as_ref() else {
+            return Err(LoggerError::ChannelSendFailed {
+                reason: "async writer already stopped".to_string(),
+            });
+        };
+
+        match sender.try_send(Message::Entry(entry)) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                self.unreported.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(TrySendError::Disconnected(_)) => Err(LoggerError::ChannelSendFailed {
+                reason: "async writer thread exited".to_string(),
+            }),
+        }
+    }
+
+    /// Wait until every queued entry is written, then flush the writers
+    pub(crate) fn flush(&self) {
+        // Send on a clone, outside the lock: a blocking send on a full queue
+        // must not hold up log calls (or stop) while the writer drains
+        let sender = self.sender.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+        let Some(sender) = sender else {
+            return;
+        };
+
+        let (ack_sender, ack_receiver) = mpsc::sync_channel(1);
+        // A blocking send: the flush request must not be dropped
+        if sender.send(Message::Flush(ack_sender)).is_ok() {
+            let _ = ack_receiver.recv();
+        }
+    }
+
+    /// Close the queue and wait for the writer to finish the remaining entries
+    ///
+    /// Safe to call more than once.
+    pub(crate) fn stop(&self) {
+        self.sender.write().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+        let worker = self.worker.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+        if let Some(worker) = worker {
+            let _ = worker.join();
+        }
+    }
+
+    /// Number of entries dropped because the queue was full
+    pub(crate) fn dropped_count(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+/// Background writer loop: runs until the queue is closed, then flushes
+fn run_worker(core: &LoggerCore, receiver: Receiver<Message>, unreported: &AtomicU64) {
+    let mut last_report: Option<Instant> = None;
+
+    for message in receiver {
+        match message {
+            Message::Entry(entry) => {
+                core.write(&entry);
+                let report_due = last_report.is_none_or(|reported| reported.elapsed() >= DROP_REPORT_INTERVAL);
+                if report_due && report_dropped(core, unreported) {
+                    last_report = Some(Instant::now());
+                }
+            }
+            Message::Flush(ack) => {
+                report_dropped(core, unreported);
+                core.flush();
+                let _ = ack.send(());
+            }
+        }
+    }
+
+    report_dropped(core, unreported);
+    core.flush();
+}
+
+/// Write a WARNING line for entries dropped since the last report
+///
+/// # Returns
+/// True if a warning was written
+fn report_dropped(core: &LoggerCore, unreported: &AtomicU64) -> bool {
+    let count = unreported.swap(0, Ordering::Relaxed);
+    if count == 0 {
+        return false;
+    }
+
+    core.write(&Entry {
+        level: LogLevel::Warning,
+        message: Cow::Owned(format!("FreedomLogger dropped {} messages due to backpressure", count)),
+        detail: None,
+        module: None,
+        file: Cow::Borrowed(file!()),
+        line: line!(),
+        thread_name: current_thread_name(),
+        thread_id: current_thread_id(),
+        now: core.now(),
+        context: Vec::new(),
+    });
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::config::{LoggerConfig, Pattern};
+    use crate::core::logger::Logger;
+    use crate::core::writers::LogWriter;
+    use crate::error::LoggerResult;
+    use crate::format::LogInfo;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    /// Consumer that takes a while per entry
+    struct SlowWriter;
+
+    impl LogWriter for SlowWriter {
+        fn write_entry(&self, _info: &LogInfo, _formatted: &str, _path: &Path) -> LoggerResult<()> {
+            thread::sleep(Duration::from_millis(20));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_async_mode_writes_all_entries() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.async_queue_capacity = Some(1000);
+        let logger = Logger::new(config);
+
+        for index in 0..50 {
+            logger.info(&format!("Async entry {}", index));
+        }
+        logger.flush();
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert_eq!(content.lines().count(), 50);
+        assert!(content.ends_with("INFO: Async entry 49\n"));
+        assert_eq!(logger.dropped_count(), 0);
+    }
+
+    #[test]
+    fn test_full_queue_drops_and_reports() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.custom_writer = Some(Arc::new(SlowWriter));
+        config.async_queue_capacity = Some(1);
+        let logger = Logger::new(config);
+
+        for index in 0..20 {
+            logger.info(&format!("Burst entry {}", index));
+        }
+        let dropped = logger.dropped_count();
+        logger.flush();
+
+        assert!(dropped > 0);
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        let written = content.lines().filter(|line| line.contains("Burst entry")).count() as u64;
+        assert_eq!(written + dropped, 20);
+        assert!(content.contains("WARNING: FreedomLogger dropped"));
+        assert!(content.contains("messages due to backpressure"));
+    }
+
+    /// Consumer that waits until the test releases the gate
+    struct GatedWriter {
+        gate: Arc<Mutex<()>>,
+    }
+
+    impl LogWriter for GatedWriter {
+        fn write_entry(&self, _info: &LogInfo, _formatted: &str, _path: &Path) -> LoggerResult<()> {
+            drop(self.gate.lock().unwrap());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_flush_on_full_queue_does_not_block_logging() {
+        let temp_dir = tempdir().unwrap();
+        let gate = Arc::new(Mutex::new(()));
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.custom_writer = Some(Arc::new(GatedWriter { gate: Arc::clone(&gate) }));
+        config.async_queue_capacity = Some(1);
+        let logger = Arc::new(Logger::new(config));
+
+        let closed = gate.lock().unwrap();
+        // The writer blocks on the first entry, the second fills the queue
+        logger.info("Held by the writer");
+        thread::sleep(Duration::from_millis(50));
+        logger.info("Fills the queue");
+
+        // The flush waits for room in the queue...
+        let flusher = {
+            let logger = Arc::clone(&logger);
+            thread::spawn(move || logger.flush())
+        };
+        thread::sleep(Duration::from_millis(50));
+
+        // ...but log calls still return and drop instead of waiting
+        logger.info("Dropped while flushing");
+        assert!(logger.dropped_count() >= 1);
+
+        drop(closed);
+        flusher.join().unwrap();
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert!(content.contains("Held by the writer"));
+    }
+
+    #[test]
+    fn test_log_after_shutdown_is_reported_not_panicking() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.async_queue_capacity = Some(10);
+        let logger = Logger::new(config);
+
+        logger.info("Before shutdown");
+        logger.shutdown();
+        logger.info("After shutdown");
+        logger.shutdown();
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert!(content.contains("INFO: Before shutdown"));
+        assert!(!content.contains("After shutdown"));
+        let errors = fs::read_to_string(temp_dir.path().join("logger_errors.log")).unwrap();
+        assert!(errors.contains("Failed to queue log entry: async writer already stopped"));
+    }
+}
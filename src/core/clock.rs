@@ -0,0 +1,37 @@
+//! Timestamp source for FreedomLogger
+//!
+//! The logger asks its `Clock` for the current time of every entry instead of
+//! reading the system time directly, so tests can pin timestamps and assert
+//! on exact log lines.
+//!
+//! Any `Fn() -> Timestamp` closure is a clock (see `core::time` for building
+//! a fixed instant):
+//! `Logger::with_clock(config, Arc::new(|| fixed_instant))`
+
+use crate::core::time::{self, Timestamp};
+
+/// Source of the current time for log timestamps
+pub trait Clock: Send + Sync {
+    /// Get the current time
+    fn now(&self) -> Timestamp;
+}
+
+/// System time - the default clock
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Timestamp {
+        time::now()
+    }
+}
+
+/// Closures returning the time (e.g. a frozen clock in tests)
+impl<F> Clock for F
+where
+    F: Fn() -> Timestamp + Send + Sync,
+{
+    fn now(&self) -> Timestamp {
+        self()
+    }
+}
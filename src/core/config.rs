@@ -1,4 +1,4 @@
-/// File: src/core/config.rs
+//! File: src/core/config.rs
 
 /*
 Configuration types and structures for FreedomLogger
@@ -8,15 +8,31 @@ what pattern to use, witch levels to log, file paths, etc.
 The configuration is set once during the initialization and remains constant.
  */
 
-use std::path::PathBuf;
+use std::fmt;
+use std::str::FromStr;
+use crate::core::time::{self, Date};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use crate::core::writers::LogWriter;
+use crate::error::{LoggerError, LoggerResult};
+use crate::format::LogInfo;
 
 /*
 Log levels in order from most critical to the least critical
-Used for filtering - if logger is configured with INFO level.
+Used for filtering - if logger is configured with INFO level, ERROR,
+WARNING and INFO messages are written, DEBUG and TRACE are dropped.
+The numeric values grow with verbosity (Error=1 < Trace=5), so "more
+severe" means "smaller": use is_at_least / includes rather than comparing
+levels directly.
+Off sorts below Error: configured as the level, nothing is logged.
  */
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum LogLevel {
+    Off = 0,
     Error = 1,
     Warning = 2,
     Info = 3,
@@ -24,10 +40,14 @@ pub enum LogLevel {
     Trace = 5,
 }
 
+/// ANSI escape code resetting the terminal color, ends `LogLevel::ansi_color` text
+pub const ANSI_RESET: &str = "\x1b[0m";
+
 impl LogLevel {
     /// Convert log level to string for output formatting
     pub fn as_str(&self) -> &'static str {
         match self {
+            LogLevel::Off => "OFF",
             LogLevel::Error => "ERROR",
             LogLevel::Warning => "WARNING",
             LogLevel::Info => "INFO",
@@ -36,6 +56,76 @@ impl LogLevel {
         }
     }
 
+    /// Numeric severity on the OpenTelemetry scale, for backends that expect numbers
+    ///
+    /// TRACE=1, DEBUG=5, INFO=9, WARNING=13, ERROR=17 (Off has no severity: 0)
+    pub fn severity_number(&self) -> u8 {
+        match self {
+            LogLevel::Off => 0,
+            LogLevel::Error => 17,
+            LogLevel::Warning => 13,
+            LogLevel::Info => 9,
+            LogLevel::Debug => 5,
+            LogLevel::Trace => 1,
+        }
+    }
+
+    /// Syslog severity of this level (journald PRIORITY, GELF level)
+    ///
+    /// ERROR=3, WARNING=4, INFO=6, DEBUG/TRACE=7 (Off is treated as ERROR)
+    pub fn syslog_severity(&self) -> u8 {
+        match self {
+            LogLevel::Off | LogLevel::Error => 3,
+            LogLevel::Warning => 4,
+            LogLevel::Info => 6,
+            LogLevel::Debug | LogLevel::Trace => 7,
+        }
+    }
+
+    /// ANSI escape code coloring this level on a terminal
+    ///
+    /// ERROR red, WARNING yellow, INFO green, DEBUG cyan, TRACE gray.
+    /// Off gets the reset code; end colored text with `ANSI_RESET`.
+    pub fn ansi_color(&self) -> &'static str {
+        match self {
+            LogLevel::Off => ANSI_RESET,
+            LogLevel::Error => "\x1b[31m",
+            LogLevel::Warning => "\x1b[33m",
+            LogLevel::Info => "\x1b[32m",
+            LogLevel::Debug => "\x1b[36m",
+            LogLevel::Trace => "\x1b[90m",
+        }
+    }
+
+    /// Hex color of this level for HTML output, matching `ansi_color`
+    ///
+    /// Off is plain black.
+    pub fn html_color(&self) -> &'static str {
+        match self {
+            LogLevel::Off => "#000000",
+            LogLevel::Error => "#d32f2f",
+            LogLevel::Warning => "#f9a825",
+            LogLevel::Info => "#388e3c",
+            LogLevel::Debug => "#0097a7",
+            LogLevel::Trace => "#757575",
+        }
+    }
+
+    /// Check if this level is at least as severe as `other`
+    ///
+    /// `Error.is_at_least(Warning)` is true, `Debug.is_at_least(Info)` is false.
+    /// Off counts as more severe than everything.
+    pub fn is_at_least(&self, other: LogLevel) -> bool {
+        (*self as u8) <= (other as u8)
+    }
+
+    /// Check if a logger configured with this level writes a message at `msg_level`
+    ///
+    /// `Info.includes(Warning)` is true, `Info.includes(Debug)` is false.
+    /// Messages at `Off` are never written, and `Off` includes nothing.
+    pub fn includes(&self, msg_level: LogLevel) -> bool {
+        msg_level != LogLevel::Off && msg_level.is_at_least(*self)
+    }
 
     /// Check if this level should be logged given the configured minimum level
     ///
@@ -44,9 +134,28 @@ impl LogLevel {
     ///
     /// # Returns
     /// True if this message should be logged, false if it should be filtered.
-
     pub fn should_log(&self, configured_level: LogLevel) -> bool {
-        *self <= configured_level
+        configured_level.includes(*self)
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = LoggerError;
+
+    /// Parse a level name, case-insensitive ("off", "error", "warning"/"warn", "info", "debug", "trace")
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" => Ok(LogLevel::Off),
+            "error" => Ok(LogLevel::Error),
+            "warning" | "warn" => Ok(LogLevel::Warning),
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            "trace" => Ok(LogLevel::Trace),
+            _ => Err(LoggerError::InvalidConfig {
+                field: "log_level".to_string(),
+                reason: format!("Unknown log level '{}'", value),
+            }),
+        }
     }
 }
 
@@ -54,65 +163,223 @@ impl LogLevel {
 /// Log formatting patterns from basic to advanced
 /// Patterns determibe how log messages are formatted in the output file
 /// Order goed from simple to complex
-
+///
+/// With the `serde` feature: "basic", "detailed", ... and {"custom": "..."}
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Pattern {
     Basic,
     Detailed,
     Extended,
     Json,
+    /// JSON with OpenTelemetry log data model names (Timestamp, SeverityText,
+    /// SeverityNumber, Body, Attributes)
+    OtelJson,
+    /// GELF 1.1 JSON line for Graylog (version, host, short_message,
+    /// epoch timestamp, syslog level, `_`-prefixed additional fields)
+    Gelf,
+    /// One HTML table row per entry, level-colored, in a table per file
+    Html,
     Custom(String),
 }
 
-impl Pattern {
+impl Default for Pattern {
     /// Get the default pattern (Basic)
     /// Used as fallback when user-provided pattern is invalid
-
-    pub fn default() -> Self {
+    fn default() -> Self {
         Pattern::Basic
     }
+}
 
+impl Pattern {
     /// Validate a custom pattern string
     /// Returns true if the pattern contains requires placeholders
-
     pub fn validate_custom(pattern: &str) -> bool {
         // Custom pattern must at least have {message} placeholder
         // Other placeholders like {timestamp}, {level} are optional
         pattern.contains("{message}")
         }
 
+    /// Check if entries are written as JSON (`Json`, `OtelJson` or `Gelf`)
+    pub fn is_json(&self) -> bool {
+        matches!(self, Pattern::Json | Pattern::OtelJson | Pattern::Gelf)
+    }
+
     pub fn format(&self, info: &crate::format::LogInfo) -> String {
-        use crate::format::{format_basic, format_detailed};
+        use crate::format::{format_basic, format_detailed, format_extended, format_gelf, format_html, format_custom};
 
         match self {
             Pattern::Basic => format_basic(info),
             Pattern::Detailed => format_detailed(info),
-            Pattern::Extended => format_detailed(info),
-            Pattern::Json => format_basic(info),
-            Pattern::Custom(_) => format_basic(info),
+            Pattern::Extended => format_extended(info),
+            Pattern::Gelf => format_gelf(info),
+            Pattern::Html => format_html(info),
+            Pattern::Json | Pattern::OtelJson => format_basic(info),
+            Pattern::Custom(pattern) => format_custom(pattern, info),
         }
     }
 }
 
 
+/// Where formatted log lines are written
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Destination {
+    /// Log file in `file_path` (with rotation) - the default
+    #[default]
+    File,
+    /// In-memory ring buffer keeping the last N lines, read back with `captured_lines()`
+    Memory(usize),
+    /// Standard output
+    Console,
+    /// Standard error (keeps stdout free for a CLI tool's own output)
+    Stderr,
+    /// Windows Event Log under the given event source name (`eventlog` feature)
+    #[cfg(all(windows, feature = "eventlog"))]
+    EventLog(String),
+    /// systemd journal with structured fields (`journald` feature)
+    #[cfg(all(unix, feature = "journald"))]
+    Journald,
+    /// Named pipe at the given path, opened once and never blocking (Unix only)
+    #[cfg(unix)]
+    Fifo(PathBuf),
+    /// Browser console, one method per level (`wasm` feature, wasm32 only)
+    ///
+    /// On wasm32 the File, Console and Stderr destinations are replaced by
+    /// this one, since the browser has no file system or stdout.
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    WebConsole,
+}
+
+/// What triggers log file rotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum RotationStrategy {
+    /// Rotate when the file reaches `max_file_size` bytes - the default
+    #[default]
+    Size,
+    /// Rotate after the given number of lines (`max_file_size` is ignored)
+    Lines(u64),
+    /// Rotate at `max_file_size` bytes or when a new day starts, whichever
+    /// comes first; backups are dated (app.2025-09-06.1.log, ...)
+    #[cfg_attr(feature = "serde", serde(rename = "size_or_daily"))]
+    SizeOrDaily,
+}
+
+/// Line terminator written after every entry in log files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum LineEnding {
+    /// "\n" (Unix) - the default
+    #[default]
+    Lf,
+    /// "\r\n" (Windows tools such as Notepad before 2018)
+    Crlf,
+}
+
+impl LineEnding {
+    /// The terminator as text
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+}
+
+/// How entries are delimited on a stream destination (`Destination::Fifo`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Framing {
+    /// Entry followed by "\n" - the default
+    #[default]
+    Newline,
+    /// 4-byte big-endian length followed by the entry bytes, with no
+    /// terminator, so entries containing newlines stay unambiguous
+    #[cfg_attr(feature = "serde", serde(rename = "length_prefixed"))]
+    LengthPrefixed,
+}
+
+impl Framing {
+    /// Append one framed entry to `out`
+    ///
+    /// A length-prefixed entry longer than `u32::MAX` bytes is cut to that length.
+    pub fn encode(&self, entry: &str, out: &mut Vec<u8>) {
+        match self {
+            Framing::Newline => {
+                out.extend_from_slice(entry.as_bytes());
+                out.push(b'\n');
+            }
+            Framing::LengthPrefixed => {
+                let bytes = &entry.as_bytes()[..entry.len().min(u32::MAX as usize)];
+                out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+                out.extend_from_slice(bytes);
+            }
+        }
+    }
+
+    /// Bytes `encode` adds around an entry
+    pub fn overhead(&self) -> usize {
+        match self {
+            Framing::Newline => 1,
+            Framing::LengthPrefixed => 4,
+        }
+    }
+}
+
+/// Callback receiving every internal logger error (see `LoggerConfig::on_error`)
+pub type ErrorCallback = Arc<dyn Fn(&LoggerError) + Send + Sync>;
+
+/// Application-provided formatter turning an entry into its output line
+/// (see `LoggerConfig::custom_formatter`)
+pub type CustomFormatter = Arc<dyn Fn(&LogInfo) -> String + Send + Sync>;
+
+/// Callback receiving the log file and its new backup after every rotation
+/// (see `LoggerConfig::on_rotate`)
+pub type RotateCallback = Arc<dyn Fn(&Path, &Path) + Send + Sync>;
+
 /// Complete logger configuration
 ///
 /// Contains all settings needed to initialize the logger.
 /// Created during init()
-
-#[derive(Debug, Clone)]
+///
+/// With the `serde` feature the configuration can be (de)serialized; the
+/// code-only fields (`custom_formatter`, `custom_writer`, `on_error`, `on_rotate`, `redactors`,
+/// `suppress_patterns`) are skipped
+/// and come back empty.
+///
+/// Struct literals should end with `..Default::default()` (or start from
+/// `LoggerConfig::basic()` and assign fields), so they keep compiling when
+/// new options are added.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LoggerConfig {
     // Pattern for formatting log messages
     pub pattern: Pattern,
 
+    // Application-provided formatter used instead of the pattern for every
+    // destination (e.g. GELF). The file gets the formatter's lines as text,
+    // also with a JSON or Html pattern (which still picks the extension)
+    // Default: None
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub custom_formatter: Option<CustomFormatter>,
+
     // Directory path where log files will be created
     pub file_path: PathBuf,
 
     // Base filename for log files
+    // A plain name: '/' and '\\' are rejected by validate(), put
+    // subdirectories in file_path instead ("logs/sub" + "app", not "logs" + "sub/app")
     pub file_name: String,
 
     // Minimum log level
-    // None means log everything
+    // None means log everything. Only seeds the logger: Logger::set_level
+    // changes the logger's own level, never this config (or its clones)
     pub log_level: Option<LogLevel>,
 
     // Maximum file size before rotation 9in bytes)
@@ -122,14 +389,393 @@ pub struct LoggerConfig {
     // Maximum number of log files to keep
     // Default: 5
     pub max_backup_files: u32,
+
+    // Rotate by file size, by number of lines, or by size or new day
+    // Default: RotationStrategy::Size
+    pub rotation_strategy: RotationStrategy,
+
+    // Use UTC instead of local time for timestamps (main log and error log)
+    // UTC lines up across machines in different time zones, but is harder
+    // to read next to local wall-clock time during development.
+    // Default: false (local time)
+    pub use_utc: bool,
+
+    // Line terminator of text and JSON log files (also between the lines of
+    // pretty JSON and around json_array brackets)
+    // Default: LineEnding::Lf
+    pub line_ending: LineEnding,
+
+    // File extension for the log file and its rotation backups (without dot)
+    // None uses the pattern default: "json" for Json, "log" for all others
+    // Example: Some("ndjson") -> app.ndjson, app.1.ndjson, ...
+    pub file_extension: Option<String>,
+
+    // Where log lines are written - every entry goes to all listed destinations
+    // Default: [Destination::File]
+    pub destinations: Vec<Destination>,
+
+    // Application-provided writer that receives every entry in addition
+    // to the destinations above (must be Send + Sync, see LogWriter)
+    // Default: None
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub custom_writer: Option<Arc<dyn LogWriter>>,
+
+    // Write internal errors as JSON lines to logger_errors.json
+    // instead of plain text to logger_errors.log
+    // Default: false
+    pub internal_error_json: bool,
+
+    // File name of the internal error log in file_path
+    // Default: None ("logger_errors.log", or "logger_errors.json" with internal_error_json)
+    pub error_log_name: Option<String>,
+
+    // Write internal errors to stderr instead of an error log file
+    // (same text or JSON lines; no file is created)
+    // Default: false
+    pub error_log_to_stderr: bool,
+
+    // Directory of the internal error log, e.g. on a separate diagnostics
+    // volume. Created when missing; if that fails the error log stays in file_path
+    // Default: None (file_path)
+    pub error_log_dir: Option<PathBuf>,
+
+    // Empty the current log file once when the logger is created,
+    // for a fresh file per run (writes still append afterwards)
+    // Default: false (keep appending to the existing file)
+    pub truncate_on_start: bool,
+
+    // Write indented multi-line JSON (separated by blank lines) for human
+    // inspection instead of compact one-object-per-line JSONL
+    // Default: false
+    pub json_pretty: bool,
+
+    // Write one top-level JSON array instead of JSONL (JSON pattern only).
+    // The array is closed by Logger::flush()/shutdown(); a hard crash leaves
+    // it unterminated - append a `]` line to repair the file
+    // Default: false
+    pub json_array: bool,
+
+    // Keys emitted in JSON output, in this order: built-in fields (timestamp,
+    // level, message, module, file, line, thread, thread_id, pid, hostname, seq)
+    // and context keys. Fields not listed are left out (not for Pattern::OtelJson)
+    // Default: None (all fields)
+    pub json_fields: Option<Vec<String>>,
+
+    // Add "severity_number" (OpenTelemetry scale, see LogLevel::severity_number)
+    // after "level" in JSON output (Pattern::OtelJson always has SeverityNumber)
+    // Default: false
+    pub json_severity_number: bool,
+
+    // Unix permissions for newly created log files, e.g. 0o600 to keep
+    // sensitive logs private (still reduced by the process umask).
+    // Rotation backups are renamed files, so they keep the same mode.
+    // Ignored on non-Unix platforms
+    // Default: None (system default, usually 0o644)
+    pub file_mode: Option<u32>,
+
+    // Write to one file per calendar day, named by date (app-2025-09-06.log).
+    // The date follows `use_utc`; size-based rotation still applies within a day
+    // Default: false (always write to app.log)
+    pub daily_files: bool,
+
+    // Also write entries at this level or more severe to a separate file
+    // next to the main log (app.error.log, app-2025-09-06.error.log with
+    // daily_files), e.g. Some(LogLevel::Error) for an errors-only file to
+    // alert on, Some(LogLevel::Warning) to include warnings. The file is
+    // written even without a File destination and rotates on its own
+    // Default: None (no separate file)
+    pub separate_error_file: Option<LogLevel>,
+
+    // Hard ceiling in bytes for the current log file plus all its backups.
+    // After each rotation the oldest backups are deleted until the set fits
+    // Default: None (only max_file_size and max_backup_files apply)
+    pub max_total_size: Option<u64>,
+
+    // Switch to the system temp directory (std::env::temp_dir()) when the
+    // log directory is not writable at init, instead of losing every line.
+    // The switch is recorded once in logger_errors.log in the temp directory
+    // Default: false
+    pub fallback_to_temp: bool,
+
+    // After this many consecutive failed writes to the log file, write the
+    // entries to stderr instead (with a one-time warning) and retry the file
+    // every 30 seconds (DEFAULT_FALLBACK_RETRY_INTERVAL). Failures are still
+    // reported to on_error and the error log
+    // Default: None (no console fallback)
+    pub console_fallback_after: Option<u32>,
+
+    // Retries of a write that failed with an I/O error, waiting 1 ms, 2 ms,
+    // 4 ms, ... (at most 100 ms) in between. ERROR entries get twice as many.
+    // The logging thread waits meanwhile; at most MAX_OPEN_RETRIES (10)
+    // Default: 0 (no retries)
+    pub max_open_retries: u32,
+
+    // Collect file output in memory and write it in batches instead of
+    // opening the file for every entry. Buffered entries are lost on a crash
+    // Default: false
+    pub buffered: bool,
+
+    // Size in bytes at which buffered output is written to the file (per
+    // file writer). Larger buffers mean fewer writes but more entries lost
+    // on a crash; see Logger::buffer_used()
+    // Default: 8 KiB (DEFAULT_BUFFER_CAPACITY)
+    pub buffer_capacity: usize,
+
+    // With buffering, entries at or above this severity are written to the
+    // file immediately (together with everything buffered before them)
+    // Default: Some(LogLevel::Error)
+    pub flush_on_level: Option<LogLevel>,
+
+    // With buffering, flush from a background thread at this interval, so
+    // entries stay in memory for at most about this long. The thread is
+    // joined by Logger::shutdown() and on drop
+    // Default: None (flush on a full buffer, flush_on_level and flush() only)
+    pub flush_interval: Option<Duration>,
+
+    // Called with every internal error before it is written to the error log,
+    // e.g. to feed metrics or alerts. A panicking callback is caught and ignored
+    // Default: None
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub on_error: Option<ErrorCallback>,
+
+    // Called after every completed rotation with the log file path and the
+    // backup it was moved to (app.1.log, or app.2025-09-06.1.log with
+    // SizeOrDaily), e.g. to upload the backup. Not called without backups
+    // (max_backup_files = 0). Runs while the logger writes, so it must not
+    // log through the same logger. A panicking callback is caught and ignored
+    // Default: None
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub on_rotate: Option<RotateCallback>,
+
+    // Maximum message length in bytes; longer messages are cut at a UTF-8
+    // character boundary and get a "…[truncated N bytes]" marker
+    // Default: None (no limit)
+    pub max_message_len: Option<usize>,
+
+    // Escape newlines and other control characters in messages of text
+    // patterns ("\n" shown literally), so a message can't fake extra log
+    // lines or send terminal escape codes. JSON output escapes them anyway
+    // Default: true
+    pub sanitize_text: bool,
+
+    // Color Console and Stderr lines by level with ANSI escape codes
+    // (LogLevel::ansi_color). Leave off when the output isn't a terminal
+    // Default: false
+    pub console_color: bool,
+
+    // Write an entry with the same level and message as the previous one only
+    // once; the repeats are summarized as "... (repeated N times)" when a
+    // different entry arrives or on flush
+    // Default: false
+    pub dedup_consecutive: bool,
+
+    // Start each new text, JSON or HTML log file (also the fresh file after
+    // rotation or truncate_on_start) with the UTF-8 BOM (EF BB BF), for
+    // Windows tools that don't detect UTF-8 otherwise. Not with compress_live
+    // Default: false
+    pub write_utf8_bom: bool,
+
+    // Record every rotation in "<file_name>.index.json" next to the log:
+    // backup file name, first and last entry time and size, so tools can
+    // find the backup covering a timestamp (see rotation::index)
+    // Default: false
+    pub rotation_index: bool,
+
+    // Add the process ID to every entry ("pid" in JSON, {pid} in custom patterns)
+    // Default: false
+    pub include_pid: bool,
+
+    // Add the host name, read once at init ("hostname" in JSON, {hostname}
+    // in custom patterns)
+    // Default: false
+    pub include_hostname: bool,
+
+    // Number every entry, starting at 0, in the order entries are written
+    // ("seq" in JSON, {seq} in custom patterns). A gap means lost lines
+    // Default: false
+    pub include_sequence: bool,
+
+    // Fields added to every entry, e.g. [("service", "api"), ("env", "prod")]:
+    // extra keys in JSON, key=value after text patterns, {service} in custom
+    // patterns. A context field with the same key overrides the static one
+    // Default: empty
+    pub static_fields: Vec<(String, String)>,
+
+    // Write on a background thread: log calls queue the entry (up to this
+    // many) and return immediately. On a full queue entries are dropped and
+    // counted (Logger::dropped_count); flush() waits for the queue to drain
+    // Default: None (write synchronously on the calling thread)
+    pub async_queue_capacity: Option<usize>,
+
+    // Debug aid: panic with the LoggerError on the first failure to create or
+    // write the log file (permission denied, disk full, ...) instead of
+    // silently routing it to the error log. Don't enable in production
+    // Default: false
+    pub strict: bool,
+
+    // Bytes kept for a Destination::Fifo while no reader is connected or the
+    // pipe is full; they are sent with the next entry or flush
+    // Default: None (drop entries the FIFO can't take right away)
+    pub fifo_buffer_size: Option<usize>,
+
+    // Delimiting of entries on a Destination::Fifo. LengthPrefixed suits
+    // readers decoding a binary stream (entries may contain newlines)
+    // Default: Framing::Newline
+    pub fifo_framing: Framing,
+
+    // Report LoggerError::LowDiskSpace (once, until space recovers) when the
+    // log directory has less free space than this before a file write.
+    // Checked with statvfs on Unix; unknown (never low) elsewhere
+    // Default: None (no check)
+    pub min_free_bytes: Option<u64>,
+
+    // While free space is below min_free_bytes, skip entries below ERROR
+    // Default: false
+    pub pause_on_low_disk: bool,
+
+    // Bytes per line in log_hex! dumps
+    // Default: 16
+    pub hexdump_width: usize,
+
+    // Append the caller's stack to ERROR entries, one "at function (file:line)"
+    // line per frame (see format::backtrace). Captured only for ERROR, so
+    // other levels cost nothing; frames need debug symbols to have names
+    // Default: false
+    pub backtrace_on_error: bool,
+
+    // Patterns masked in every message before formatting (text and JSON),
+    // as (regex, replacement) pairs applied in order. See format::redact
+    // for built-in patterns (emails, card numbers, bearer tokens)
+    // Default: empty (no redaction)
+    #[cfg(feature = "redaction")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub redactors: Vec<crate::format::Redactor>,
+
+    // Drop messages matching any of these patterns (e.g. health-check spam),
+    // checked after the level filter. Dropped messages are counted in
+    // LogCounts::suppressed
+    // Default: empty (nothing suppressed)
+    #[cfg(feature = "regex")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub suppress_patterns: Vec<regex::Regex>,
+
+    // Gzip the log file while writing it (app.log.gz, backups app.log.1.gz).
+    // The gzip stream is finished on flush, before rotation and on drop;
+    // entries not yet flushed are lost on a crash. max_file_size applies to
+    // the compressed file. Not supported with json_array
+    // Default: false
+    #[cfg(feature = "compression")]
+    pub compress_live: bool,
+}
+
+/// Read-only view of how a running logger is configured
+///
+/// Returned by `Logger::current_config()` / `current_config()` for health
+/// endpoints and diagnostics. It is a copy: changing it has no effect on the
+/// logger, and `log_level` is the effective level (including `set_level`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoggerConfigSnapshot {
+    /// Directory of the log files (the temp directory after `fallback_to_temp`)
+    pub file_path: PathBuf,
+    /// Base filename of the log files
+    pub file_name: String,
+    /// Full path of the current log file (without the date of `daily_files`)
+    pub log_file_path: PathBuf,
+    /// Effective minimum level, None when every level is logged
+    pub log_level: Option<LogLevel>,
+    /// Formatting pattern
+    pub pattern: Pattern,
+    /// Size in bytes at which the log file rotates
+    pub max_file_size: u64,
+    /// Number of rotated backups kept
+    pub max_backup_files: u32,
+    /// Whether the file rotates by size or by line count
+    pub rotation_strategy: RotationStrategy,
+    /// Cap on the combined size of the log file and its backups
+    pub max_total_size: Option<u64>,
+    /// Enabled destinations
+    pub destinations: Vec<Destination>,
 }
 
+impl fmt::Debug for LoggerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("LoggerConfig");
+        debug
+            .field("pattern", &self.pattern)
+            .field("custom_formatter", &self.custom_formatter.as_ref().map(|_| "<formatter>"))
+            .field("file_path", &self.file_path)
+            .field("file_name", &self.file_name)
+            .field("log_level", &self.log_level)
+            .field("max_file_size", &self.max_file_size)
+            .field("max_backup_files", &self.max_backup_files)
+            .field("rotation_strategy", &self.rotation_strategy)
+            .field("use_utc", &self.use_utc)
+            .field("line_ending", &self.line_ending)
+            .field("file_extension", &self.file_extension)
+            .field("destinations", &self.destinations)
+            .field("custom_writer", &self.custom_writer.as_ref().map(|_| "<LogWriter>"))
+            .field("internal_error_json", &self.internal_error_json)
+            .field("error_log_name", &self.error_log_name)
+            .field("error_log_to_stderr", &self.error_log_to_stderr)
+            .field("error_log_dir", &self.error_log_dir)
+            .field("truncate_on_start", &self.truncate_on_start)
+            .field("json_pretty", &self.json_pretty)
+            .field("json_array", &self.json_array)
+            .field("json_fields", &self.json_fields)
+            .field("json_severity_number", &self.json_severity_number)
+            .field("file_mode", &self.file_mode)
+            .field("daily_files", &self.daily_files)
+            .field("separate_error_file", &self.separate_error_file)
+            .field("max_total_size", &self.max_total_size)
+            .field("fallback_to_temp", &self.fallback_to_temp)
+            .field("console_fallback_after", &self.console_fallback_after)
+            .field("max_open_retries", &self.max_open_retries)
+            .field("buffered", &self.buffered)
+            .field("buffer_capacity", &self.buffer_capacity)
+            .field("flush_on_level", &self.flush_on_level)
+            .field("flush_interval", &self.flush_interval)
+            .field("on_error", &self.on_error.as_ref().map(|_| "<callback>"))
+            .field("on_rotate", &self.on_rotate.as_ref().map(|_| "<callback>"))
+            .field("max_message_len", &self.max_message_len)
+            .field("sanitize_text", &self.sanitize_text)
+            .field("console_color", &self.console_color)
+            .field("dedup_consecutive", &self.dedup_consecutive)
+            .field("write_utf8_bom", &self.write_utf8_bom)
+            .field("rotation_index", &self.rotation_index)
+            .field("include_pid", &self.include_pid)
+            .field("include_hostname", &self.include_hostname)
+            .field("include_sequence", &self.include_sequence)
+            .field("static_fields", &self.static_fields)
+            .field("async_queue_capacity", &self.async_queue_capacity)
+            .field("strict", &self.strict)
+            .field("fifo_buffer_size", &self.fifo_buffer_size)
+            .field("fifo_framing", &self.fifo_framing)
+            .field("min_free_bytes", &self.min_free_bytes)
+            .field("pause_on_low_disk", &self.pause_on_low_disk)
+            .field("hexdump_width", &self.hexdump_width)
+            .field("backtrace_on_error", &self.backtrace_on_error);
+        #[cfg(feature = "redaction")]
+        debug.field("redactors", &self.redactors);
+        #[cfg(feature = "regex")]
+        debug.field("suppress_patterns", &self.suppress_patterns);
+        #[cfg(feature = "compression")]
+        debug.field("compress_live", &self.compress_live);
+        debug.finish()
+    }
+}
+
+impl Default for LoggerConfig {
+    /// Basic pattern, "app" in the current directory, no level filtering,
+    /// 10MB files and 5 backups (the same as `LoggerConfig::basic`)
+    fn default() -> Self {
+        Self::basic(Pattern::default(), PathBuf::from("."), "app".to_string())
+    }
+}
 
 impl LoggerConfig {
     /// Create basic logger configuration (for logger::init)
     /// Uses default rotatiob settings: 10MB files, 5 backups
     /// Logs everything (no level filtering)
-
     pub fn basic(
         pattern: Pattern,
         file_path: PathBuf,
@@ -137,11 +783,64 @@ impl LoggerConfig {
     ) -> Self {
         Self {
             pattern,
+            custom_formatter: None,
             file_path,
             file_name,
             log_level: None,
             max_file_size: 10 * 1024 * 1024,
             max_backup_files: 5,
+            rotation_strategy: RotationStrategy::Size,
+            use_utc: false,
+            line_ending: LineEnding::Lf,
+            file_extension: None,
+            destinations: vec![Destination::File],
+            custom_writer: None,
+            internal_error_json: false,
+            error_log_name: None,
+            error_log_to_stderr: false,
+            error_log_dir: None,
+            truncate_on_start: false,
+            json_pretty: false,
+            json_array: false,
+            json_fields: None,
+            json_severity_number: false,
+            file_mode: None,
+            daily_files: false,
+            separate_error_file: None,
+            max_total_size: None,
+            fallback_to_temp: false,
+            console_fallback_after: None,
+            max_open_retries: 0,
+            buffered: false,
+            buffer_capacity: crate::core::writers::DEFAULT_BUFFER_CAPACITY,
+            flush_on_level: Some(LogLevel::Error),
+            flush_interval: None,
+            on_error: None,
+            on_rotate: None,
+            max_message_len: None,
+            sanitize_text: true,
+            console_color: false,
+            dedup_consecutive: false,
+            write_utf8_bom: false,
+            rotation_index: false,
+            include_pid: false,
+            include_hostname: false,
+            include_sequence: false,
+            static_fields: Vec::new(),
+            async_queue_capacity: None,
+            strict: false,
+            fifo_buffer_size: None,
+            fifo_framing: Framing::Newline,
+            min_free_bytes: None,
+            pause_on_low_disk: false,
+            hexdump_width: crate::format::DEFAULT_HEXDUMP_WIDTH,
+            backtrace_on_error: false,
+            #[cfg(feature = "redaction")]
+            redactors: Vec::new(),
+            #[cfg(feature = "regex")]
+            suppress_patterns: Vec::new(),
+            #[cfg(feature = "compression")]
+            compress_live: false,
         }
     }
 
@@ -155,19 +854,14 @@ impl LoggerConfig {
         log_level: LogLevel,
     ) -> Self {
         Self {
-            pattern,
-            file_path,
-            file_name,
             log_level: Some(log_level),
-            max_file_size: 10 * 1024 * 1024,
-            max_backup_files: 5,
+            ..Self::basic(pattern, file_path, file_name)
         }
     }
 
 
     /// Create full logger configuration (for logger::init_with_ratation)
     /// All parameters customizable.
-
     pub fn with_rotation(
         pattern: Pattern,
         file_path: PathBuf,
@@ -177,29 +871,553 @@ impl LoggerConfig {
         max_backup_files: u32,
     ) -> Self {
         Self {
-            pattern,
-            file_path,
-            file_name,
             log_level: Some(log_level),
             max_file_size,
             max_backup_files,
+            ..Self::basic(pattern, file_path, file_name)
         }
     }
 
 
-    /// Get a full path to the current log file
-    /// Example: /logs/app.log
+    /// Create configuration that only writes to stdout (for CLI tools)
+    ///
+    /// No log file and no directory: internal errors are not written to
+    /// logger_errors.log, only passed to `on_error` if set (or written to
+    /// stderr with `error_log_to_stderr`).
+    pub fn stdout(pattern: Pattern, log_level: LogLevel) -> Self {
+        Self::console_only(pattern, log_level, Destination::Console)
+    }
+
+    /// Create configuration that only writes to stderr (for CLI tools)
+    ///
+    /// Same as `stdout`, but leaves stdout to the program's own output.
+    pub fn stderr(pattern: Pattern, log_level: LogLevel) -> Self {
+        Self::console_only(pattern, log_level, Destination::Stderr)
+    }
+
+    fn console_only(pattern: Pattern, log_level: LogLevel, destination: Destination) -> Self {
+        Self {
+            destinations: vec![destination],
+            ..Self::with_level(pattern, PathBuf::new(), "console".to_string(), log_level)
+        }
+    }
 
+
+    /// Get the file extension (without dot) used for log files
+    /// Custom extension if configured, otherwise "json" for the JSON patterns, "html" for Html and "log" for other patterns
+    pub fn get_file_extension(&self) -> &str {
+        match &self.file_extension {
+            Some(extension) => extension.trim_start_matches('.'),
+            None => match self.pattern {
+                Pattern::Json | Pattern::OtelJson | Pattern::Gelf => "json",
+                Pattern::Html => "html",
+                _ => "log",
+            },
+        }
+    }
+
+    /// Get a full path to the current log file
+    /// Example: /logs/app.log (/logs/app.log.gz with `compress_live`)
     pub fn get_log_file_path(&self) -> PathBuf {
-        self.file_path.join(format!("{}.log", self.file_name))
+        self.file_path.join(self.log_file_name(&self.file_name))
     }
 
-    /// Check if a loglevel should be written based on configuration
+    /// Get a full path to the rotation index (for `rotation_index`)
+    /// Example: /logs/app.index.json
+    pub fn get_rotation_index_path(&self) -> PathBuf {
+        self.file_path.join(format!("{}.index.json", self.file_name))
+    }
+
+    /// Get a full path to the log file of a given day (for `daily_files`)
+    /// Example: /logs/app-2025-09-06.log
+    pub fn get_daily_log_file_path(&self, date: Date) -> PathBuf {
+        self.file_path.join(self.log_file_name(&format!("{}-{}", self.file_name, time::format_date(date))))
+    }
 
+    /// File name for a base name: extension plus ".gz" for `compress_live`
+    fn log_file_name(&self, base_name: &str) -> String {
+        let name = format!("{}.{}", base_name, self.get_file_extension());
+        #[cfg(feature = "compression")]
+        if self.compress_live {
+            return format!("{}.gz", name);
+        }
+        name
+    }
+
+    /// Take a read-only snapshot of this configuration
+    ///
+    /// # Arguments
+    /// * `log_level` - Effective level of the logger using it (may differ from `self.log_level`)
+    pub fn snapshot(&self, log_level: Option<LogLevel>) -> LoggerConfigSnapshot {
+        LoggerConfigSnapshot {
+            file_path: self.file_path.clone(),
+            file_name: self.file_name.clone(),
+            log_file_path: self.get_log_file_path(),
+            log_level,
+            pattern: self.pattern.clone(),
+            max_file_size: self.max_file_size,
+            max_backup_files: self.max_backup_files,
+            rotation_strategy: self.rotation_strategy,
+            max_total_size: self.max_total_size,
+            destinations: self.destinations.clone(),
+        }
+    }
+
+    /// Get the full path of the internal error log file
+    /// `error_log_name` in `error_log_dir` (or `file_path`)
+    pub fn error_log_path(&self) -> PathBuf {
+        self.error_log_dir
+            .as_ref()
+            .unwrap_or(&self.file_path)
+            .join(self.error_log_file_name())
+    }
+
+    /// Get the file name of the internal error log
+    /// `error_log_name`, by default logger_errors.log (.json with `internal_error_json`)
+    pub fn error_log_file_name(&self) -> &str {
+        let default_name = if self.internal_error_json {
+            crate::error::DEFAULT_JSON_ERROR_LOG_NAME
+        } else {
+            crate::error::DEFAULT_ERROR_LOG_NAME
+        };
+        self.error_log_name.as_deref().unwrap_or(default_name)
+    }
+
+    /// Check if one of the destinations writes to the log file
+    pub fn has_file_destination(&self) -> bool {
+        self.destinations.contains(&Destination::File)
+    }
+
+    /// Check if an entry at this level forces buffered output to the file
+    pub fn should_flush_level(&self, level: LogLevel) -> bool {
+        match self.flush_on_level {
+            Some(flush_level) => self.buffered && level.should_log(flush_level),
+            None => false,
+        }
+    }
+
+    /// Check if a loglevel should be written based on configuration
+    ///
+    /// Without a configured level (None) every level except Off is written.
     pub fn should_log_level(&self, level: LogLevel) -> bool {
         match self.log_level {
-            Some(configured_level) => level.should_log(configured_level),
-            None => true,
+            Some(configured_level) => configured_level.includes(level),
+            None => LogLevel::Trace.includes(level),
+        }
+    }
+
+    /// Validate the configuration before it is used
+    ///
+    /// Checks:
+    /// - `file_name` is not empty and has no path separator ('/' or '\\'),
+    ///   which would put the log file and its rotation backups in different
+    ///   directories
+    /// - `max_file_size` is not zero (would rotate on every write)
+    /// - `RotationStrategy::Lines` has a non-zero line count
+    /// - `Pattern::Custom` contains the required `{message}` placeholder
+    ///
+    /// # Returns
+    /// Ok(()) if valid, LoggerError::InvalidConfig describing the first problem otherwise
+    pub fn validate(&self) -> LoggerResult<()> {
+        if self.file_name.trim().is_empty() {
+            return Err(LoggerError::InvalidConfig {
+                field: "file_name".to_string(),
+                reason: "File name must not be empty".to_string(),
+            });
+        }
+
+        if self.file_name.contains(['/', '\\']) {
+            return Err(LoggerError::InvalidConfig {
+                field: "file_name".to_string(),
+                reason: format!(
+                    "File name '{}' contains a path separator - put directories in file_path",
+                    self.file_name
+                ),
+            });
+        }
+
+        if self.max_file_size == 0 {
+            return Err(LoggerError::InvalidConfig {
+                field: "max_file_size".to_string(),
+                reason: "Maximum file size must be greater than 0".to_string(),
+            });
+        }
+
+        if self.rotation_strategy == RotationStrategy::Lines(0) {
+            return Err(LoggerError::InvalidConfig {
+                field: "rotation_strategy".to_string(),
+                reason: "Maximum line count must be greater than 0".to_string(),
+            });
+        }
+
+        if self.max_open_retries > crate::core::writers::MAX_OPEN_RETRIES {
+            return Err(LoggerError::InvalidConfig {
+                field: "max_open_retries".to_string(),
+                reason: format!(
+                    "max_open_retries is {}, at most {} retries are allowed",
+                    self.max_open_retries,
+                    crate::core::writers::MAX_OPEN_RETRIES
+                ),
+            });
+        }
+
+        if self.separate_error_file == Some(LogLevel::Off) {
+            return Err(LoggerError::InvalidConfig {
+                field: "separate_error_file".to_string(),
+                reason: "separate_error_file needs a level that is written, not Off".to_string(),
+            });
+        }
+
+        if self.flush_interval == Some(Duration::ZERO) {
+            return Err(LoggerError::InvalidConfig {
+                field: "flush_interval".to_string(),
+                reason: "flush_interval must be longer than zero".to_string(),
+            });
+        }
+
+        if self.console_fallback_after == Some(0) {
+            return Err(LoggerError::InvalidConfig {
+                field: "console_fallback_after".to_string(),
+                reason: "console_fallback_after must be at least 1".to_string(),
+            });
+        }
+
+        #[cfg(feature = "compression")]
+        if self.compress_live && self.json_array {
+            return Err(LoggerError::InvalidConfig {
+                field: "compress_live".to_string(),
+                reason: "compress_live cannot be combined with json_array".to_string(),
+            });
+        }
+
+        #[cfg(feature = "compression")]
+        if self.compress_live && self.pattern == Pattern::Html {
+            return Err(LoggerError::InvalidConfig {
+                field: "compress_live".to_string(),
+                reason: "compress_live cannot be combined with the Html pattern".to_string(),
+            });
+        }
+
+        if let Pattern::Custom(pattern) = &self.pattern {
+            if !Pattern::validate_custom(pattern) {
+                return Err(LoggerError::InvalidConfig {
+                    field: "pattern".to_string(),
+                    reason: format!("Custom pattern '{}' is missing the {{message}} placeholder", pattern),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_config() -> LoggerConfig {
+        LoggerConfig::basic(Pattern::Basic, PathBuf::from("logs"), "app".to_string())
+    }
+
+    fn invalid_field(config: &LoggerConfig) -> String {
+        match config.validate() {
+            Err(LoggerError::InvalidConfig { field, .. }) => field,
+            other => panic!("expected InvalidConfig, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_log_file_path_extensions() {
+        let mut config = valid_config();
+        assert_eq!(config.get_log_file_path(), PathBuf::from("logs/app.log"));
+
+        config.pattern = Pattern::Json;
+        assert_eq!(config.get_log_file_path(), PathBuf::from("logs/app.json"));
+
+        config.file_extension = Some("ndjson".to_string());
+        assert_eq!(config.get_log_file_path(), PathBuf::from("logs/app.ndjson"));
+
+        config.file_extension = Some(".txt".to_string());
+        assert_eq!(config.get_log_file_path(), PathBuf::from("logs/app.txt"));
+
+        let date = Date::from_ymd_opt(2025, 9, 6).unwrap();
+        assert_eq!(config.get_daily_log_file_path(date), PathBuf::from("logs/app-2025-09-06.txt"));
+    }
+
+    #[test]
+    fn test_valid_config_passes() {
+        assert!(valid_config().validate().is_ok());
+
+        let mut config = valid_config();
+        config.pattern = Pattern::Custom("{timestamp} {message}".to_string());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_empty_file_name_rejected() {
+        let mut config = valid_config();
+        config.file_name = String::new();
+        assert_eq!(invalid_field(&config), "file_name");
+    }
+
+    #[test]
+    fn test_file_name_with_separator_rejected() {
+        for file_name in ["sub/dir/app", "sub\\app", "/app"] {
+            let mut config = valid_config();
+            config.file_name = file_name.to_string();
+            assert_eq!(invalid_field(&config), "file_name");
         }
+
+        // The same location expressed with the directory in file_path
+        let mut config = valid_config();
+        config.file_path = PathBuf::from("logs").join("sub").join("dir");
+        assert!(config.validate().is_ok());
+        assert_eq!(config.get_log_file_path(), config.file_path.join("app.log"));
+    }
+
+    #[test]
+    fn test_zero_max_file_size_rejected() {
+        let mut config = valid_config();
+        config.max_file_size = 0;
+        assert_eq!(invalid_field(&config), "max_file_size");
+
+        let mut config = valid_config();
+        config.rotation_strategy = RotationStrategy::Lines(0);
+        assert_eq!(invalid_field(&config), "rotation_strategy");
+    }
+
+    #[test]
+    fn test_custom_pattern_without_message_rejected() {
+        let mut config = valid_config();
+        config.pattern = Pattern::Custom("{timestamp} {level}".to_string());
+        assert_eq!(invalid_field(&config), "pattern");
+    }
+
+    #[test]
+    fn test_off_level_filters_everything() {
+        assert!(LogLevel::Off < LogLevel::Error);
+        assert_eq!(LogLevel::Off.as_str(), "OFF");
+
+        for level in [LogLevel::Off, LogLevel::Error, LogLevel::Warning, LogLevel::Info, LogLevel::Debug, LogLevel::Trace] {
+            assert!(!level.should_log(LogLevel::Off));
+        }
+        assert!(!LogLevel::Off.should_log(LogLevel::Trace));
+    }
+
+    #[test]
+    fn test_log_level_from_str() {
+        assert_eq!("off".parse::<LogLevel>(), Ok(LogLevel::Off));
+        assert_eq!("WARN".parse::<LogLevel>(), Ok(LogLevel::Warning));
+        assert_eq!(" Debug ".parse::<LogLevel>(), Ok(LogLevel::Debug));
+        assert!(matches!("verbose".parse::<LogLevel>(), Err(LoggerError::InvalidConfig { .. })));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip_level_and_pattern() {
+        assert_eq!(serde_json::to_string(&LogLevel::Warning).unwrap(), "\"warning\"");
+        assert_eq!(serde_json::from_str::<LogLevel>("\"trace\"").unwrap(), LogLevel::Trace);
+
+        for pattern in [
+            Pattern::Basic,
+            Pattern::Detailed,
+            Pattern::Extended,
+            Pattern::Json,
+            Pattern::Custom("{level}: {message}".to_string()),
+        ] {
+            let json = serde_json::to_string(&pattern).unwrap();
+            assert_eq!(serde_json::from_str::<Pattern>(&json).unwrap(), pattern);
+        }
+        assert_eq!(
+            serde_json::to_string(&Pattern::Custom("{message}".to_string())).unwrap(),
+            "{\"custom\":\"{message}\"}"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip_config() {
+        let mut config = LoggerConfig::with_level(
+            Pattern::Custom("{level} {message}".to_string()),
+            PathBuf::from("logs"),
+            "app".to_string(),
+            LogLevel::Info,
+        );
+        config.destinations = vec![Destination::File, Destination::Memory(10)];
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(json.contains("\"file_path\":\"logs\""));
+        assert!(json.contains("\"log_level\":\"info\""));
+
+        let restored: LoggerConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.pattern, config.pattern);
+        assert_eq!(restored.destinations, config.destinations);
+        assert_eq!(serde_json::to_string(&restored).unwrap(), json);
+    }
+
+    #[test]
+    fn test_severity_numbers_follow_opentelemetry() {
+        assert_eq!(LogLevel::Trace.severity_number(), 1);
+        assert_eq!(LogLevel::Debug.severity_number(), 5);
+        assert_eq!(LogLevel::Info.severity_number(), 9);
+        assert_eq!(LogLevel::Warning.severity_number(), 13);
+        assert_eq!(LogLevel::Error.severity_number(), 17);
+    }
+
+    #[test]
+    fn test_default_config_matches_documented_defaults() {
+        let config = LoggerConfig::default();
+        assert_eq!(config.pattern, Pattern::Basic);
+        assert_eq!(config.file_path, PathBuf::from("."));
+        assert_eq!(config.file_name, "app");
+        assert_eq!(config.log_level, None);
+        assert_eq!(config.max_file_size, 10 * 1024 * 1024);
+        assert_eq!(config.max_backup_files, 5);
+        assert_eq!(config.destinations, vec![Destination::File]);
+        assert_eq!(config.get_log_file_path(), PathBuf::from("./app.log"));
+        assert!(config.validate().is_ok());
+
+        // The struct literal form shown in the CHANGELOG
+        let config = LoggerConfig { file_name: "service".to_string(), ..Default::default() };
+        assert_eq!(config.get_log_file_path(), PathBuf::from("./service.log"));
+    }
+
+    #[test]
+    fn test_level_colors_are_distinct() {
+        let levels = [LogLevel::Off, LogLevel::Error, LogLevel::Warning, LogLevel::Info, LogLevel::Debug, LogLevel::Trace];
+        let ansi: Vec<&str> = levels.iter().map(LogLevel::ansi_color).collect();
+        assert_eq!(ansi, ["\x1b[0m", "\x1b[31m", "\x1b[33m", "\x1b[32m", "\x1b[36m", "\x1b[90m"]);
+        let html: Vec<&str> = levels.iter().map(LogLevel::html_color).collect();
+        assert_eq!(html, ["#000000", "#d32f2f", "#f9a825", "#388e3c", "#0097a7", "#757575"]);
+
+        for (index, level) in levels.iter().enumerate() {
+            for other in &levels[index + 1..] {
+                assert_ne!(level.ansi_color(), other.ansi_color());
+                assert_ne!(level.html_color(), other.html_color());
+            }
+        }
+    }
+
+    #[test]
+    fn test_level_includes_every_pair() {
+        use LogLevel::*;
+        let levels = [Off, Error, Warning, Info, Debug, Trace];
+        // Rows: configured level; columns: message level in `levels` order
+        let expected = [
+            [false, false, false, false, false, false], // Off
+            [false, true, false, false, false, false],  // Error
+            [false, true, true, false, false, false],   // Warning
+            [false, true, true, true, false, false],    // Info
+            [false, true, true, true, true, false],     // Debug
+            [false, true, true, true, true, true],      // Trace
+        ];
+
+        for (row, configured) in levels.iter().enumerate() {
+            for (column, message) in levels.iter().enumerate() {
+                let included = expected[row][column];
+                assert_eq!(configured.includes(*message), included, "{:?} includes {:?}", configured, message);
+                assert_eq!(message.should_log(*configured), included, "{:?} should_log {:?}", message, configured);
+                assert_eq!(message.is_at_least(*configured), column <= row, "{:?} is_at_least {:?}", message, configured);
+            }
+        }
+    }
+
+    #[test]
+    fn test_should_log_level_with_and_without_configured_level() {
+        use LogLevel::*;
+        let messages = [Error, Warning, Info, Debug, Trace];
+        let mut config = valid_config();
+
+        config.log_level = None;
+        assert!(messages.iter().all(|&message| config.should_log_level(message)));
+        assert!(!config.should_log_level(Off));
+
+        for (index, configured) in messages.iter().enumerate() {
+            config.log_level = Some(*configured);
+            for (message_index, message) in messages.iter().enumerate() {
+                assert_eq!(config.should_log_level(*message), message_index <= index, "{:?} at {:?}", message, configured);
+            }
+        }
+
+        config.log_level = Some(Off);
+        assert!(!messages.iter().any(|&message| config.should_log_level(message)));
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_compress_live_path_and_json_array_rejected() {
+        let mut config = valid_config();
+        config.compress_live = true;
+        assert_eq!(config.get_log_file_path(), PathBuf::from("logs/app.log.gz"));
+        assert!(config.validate().is_ok());
+
+        config.json_array = true;
+        assert_eq!(invalid_field(&config), "compress_live");
+
+        config.json_array = false;
+        config.pattern = Pattern::Html;
+        assert_eq!(invalid_field(&config), "compress_live");
+    }
+
+    #[test]
+    fn test_zero_console_fallback_rejected() {
+        let mut config = valid_config();
+        config.console_fallback_after = Some(0);
+        assert_eq!(invalid_field(&config), "console_fallback_after");
+
+        config.console_fallback_after = Some(1);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_too_many_open_retries_rejected() {
+        let mut config = valid_config();
+        config.max_open_retries = crate::core::writers::MAX_OPEN_RETRIES;
+        assert!(config.validate().is_ok());
+
+        config.max_open_retries += 1;
+        assert_eq!(invalid_field(&config), "max_open_retries");
+    }
+
+    #[test]
+    fn test_zero_flush_interval_rejected() {
+        let mut config = valid_config();
+        config.flush_interval = Some(Duration::ZERO);
+        assert_eq!(invalid_field(&config), "flush_interval");
+
+        config.flush_interval = Some(Duration::from_millis(500));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_length_prefixed_frames_decode_at_exact_boundaries() {
+        let mut stream = Vec::new();
+        Framing::LengthPrefixed.encode("{\"message\":\"first\"}", &mut stream);
+        Framing::LengthPrefixed.encode("two\nlines", &mut stream);
+        assert_eq!(stream.len(), 4 + 19 + 4 + 9);
+        assert_eq!(&stream[..4], &[0, 0, 0, 19]);
+
+        let mut frames = Vec::new();
+        let mut rest = stream.as_slice();
+        while !rest.is_empty() {
+            let length = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+            frames.push(std::str::from_utf8(&rest[4..4 + length]).unwrap());
+            rest = &rest[4 + length..];
+        }
+        assert_eq!(frames, ["{\"message\":\"first\"}", "two\nlines"]);
+
+        let mut newline = Vec::new();
+        Framing::Newline.encode("entry", &mut newline);
+        assert_eq!(newline, b"entry\n");
+    }
+
+    #[test]
+    fn test_separate_error_file_off_rejected() {
+        let mut config = valid_config();
+        config.separate_error_file = Some(LogLevel::Off);
+        assert_eq!(invalid_field(&config), "separate_error_file");
+
+        config.separate_error_file = Some(LogLevel::Warning);
+        assert!(config.validate().is_ok());
     }
 }
@@ -0,0 +1,167 @@
+//! Config file loading for FreedomLogger (`config-file` feature)
+//!
+//! Lets operations teams configure the logger with a `logger.toml` next to
+//! the binary instead of positional init arguments:
+//!
+//! ```toml
+//! pattern = "detailed"            # basic, detailed, extended, json, otel_json, gelf, html or a custom pattern
+//! level = "info"                  # optional, logs everything when left out
+//! path = "logs"
+//! file_name = "app"
+//! max_file_size = 10485760        # optional, default 10MB
+//! max_backup_files = 5            # optional, default 5
+//! destinations = ["file", "console", "memory:100"]   # optional, default ["file"]
+//! ```
+//!
+//! Options not covered here keep their `LoggerConfig::basic` defaults.
+
+use std::path::PathBuf;
+use serde::Deserialize;
+use crate::core::config::{Destination, LogLevel, LoggerConfig, Pattern};
+use crate::error::{LoggerError, LoggerResult};
+
+/// Layout of the TOML file (unknown keys are rejected to catch typos)
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileConfig {
+    pattern: String,
+    level: Option<String>,
+    path: PathBuf,
+    file_name: String,
+    max_file_size: Option<u64>,
+    max_backup_files: Option<u32>,
+    destinations: Option<Vec<String>>,
+}
+
+impl LoggerConfig {
+    /// Parse a logger configuration from TOML text
+    ///
+    /// # Arguments
+    /// * `text` - Contents of a logger config file (see the module docs)
+    ///
+    /// # Returns
+    /// The validated configuration, or LoggerError::InvalidConfig for
+    /// malformed TOML, unknown values or a configuration that fails `validate()`
+    pub fn from_toml_str(text: &str) -> LoggerResult<LoggerConfig> {
+        let file: FileConfig = toml::from_str(text).map_err(|error| LoggerError::InvalidConfig {
+            field: "config_file".to_string(),
+            reason: error.message().to_string(),
+        })?;
+
+        let mut config = LoggerConfig::basic(parse_pattern(&file.pattern), file.path, file.file_name);
+        if let Some(level) = file.level {
+            config.log_level = Some(level.parse::<LogLevel>()?);
+        }
+        if let Some(max_file_size) = file.max_file_size {
+            config.max_file_size = max_file_size;
+        }
+        if let Some(max_backup_files) = file.max_backup_files {
+            config.max_backup_files = max_backup_files;
+        }
+        if let Some(destinations) = file.destinations {
+            config.destinations = destinations
+                .iter()
+                .map(|destination| parse_destination(destination))
+                .collect::<LoggerResult<_>>()?;
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// Map a pattern name (case-insensitive) to a Pattern; anything else is a custom pattern
+fn parse_pattern(value: &str) -> Pattern {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "basic" => Pattern::Basic,
+        "detailed" => Pattern::Detailed,
+        "extended" => Pattern::Extended,
+        "json" => Pattern::Json,
+        "otel_json" => Pattern::OtelJson,
+        "gelf" => Pattern::Gelf,
+        "html" => Pattern::Html,
+        _ => Pattern::Custom(value.to_string()),
+    }
+}
+
+/// Parse a destination: "file", "console", "stderr" or "memory:<capacity>"
+fn parse_destination(value: &str) -> LoggerResult<Destination> {
+    let invalid = || LoggerError::InvalidConfig {
+        field: "destinations".to_string(),
+        reason: format!("Unknown destination '{}'", value),
+    };
+
+    match value.trim().to_ascii_lowercase().as_str() {
+        "file" => Ok(Destination::File),
+        "console" => Ok(Destination::Console),
+        "stderr" => Ok(Destination::Stderr),
+        other => {
+            let capacity = other.strip_prefix("memory:").ok_or_else(invalid)?;
+            capacity.trim().parse().map(Destination::Memory).map_err(|_| invalid())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid_config() {
+        let config = LoggerConfig::from_toml_str(
+            r#"
+            pattern = "Detailed"
+            level = "warn"
+            path = "logs"
+            file_name = "service"
+            max_file_size = 2048
+            max_backup_files = 3
+            destinations = ["file", "console", "stderr", "memory:50"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.pattern, Pattern::Detailed);
+        assert_eq!(config.log_level, Some(LogLevel::Warning));
+        assert_eq!(config.get_log_file_path(), PathBuf::from("logs").join("service.log"));
+        assert_eq!(config.max_file_size, 2048);
+        assert_eq!(config.max_backup_files, 3);
+        assert_eq!(
+            config.destinations,
+            vec![Destination::File, Destination::Console, Destination::Stderr, Destination::Memory(50)]
+        );
+    }
+
+    #[test]
+    fn test_custom_pattern_and_defaults() {
+        let config = LoggerConfig::from_toml_str(
+            "pattern = \"{level} {message}\"\npath = \"logs\"\nfile_name = \"app\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.pattern, Pattern::Custom("{level} {message}".to_string()));
+        assert_eq!(config.log_level, None);
+        assert_eq!(config.max_backup_files, 5);
+        assert_eq!(config.destinations, vec![Destination::File]);
+    }
+
+    #[test]
+    fn test_malformed_config_is_invalid() {
+        let invalid_field = |text: &str| match LoggerConfig::from_toml_str(text) {
+            Err(LoggerError::InvalidConfig { field, .. }) => field,
+            other => panic!("expected InvalidConfig, got {:?}", other),
+        };
+
+        assert_eq!(invalid_field("pattern = \"basic\"\npath = \"logs\"\n"), "config_file");
+        assert_eq!(invalid_field("pattern = basic"), "config_file");
+        assert_eq!(
+            invalid_field("pattern = \"basic\"\npath = \"logs\"\nfile_name = \"app\"\nlevel = \"loud\"\n"),
+            "log_level"
+        );
+        assert_eq!(
+            invalid_field("pattern = \"basic\"\npath = \"logs\"\nfile_name = \"app\"\ndestinations = [\"disk\"]\n"),
+            "destinations"
+        );
+        assert_eq!(invalid_field("pattern = \"{level}\"\npath = \"logs\"\nfile_name = \"app\"\n"), "pattern");
+    }
+}
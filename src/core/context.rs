@@ -0,0 +1,234 @@
+//! Thread-local logging context for FreedomLogger (MDC)
+//!
+//! Lets request handlers attach contextual fields (request_id, user, ...) once
+//! and have them included on every log line written from the same thread,
+//! similar to SLF4J's Mapped Diagnostic Context.
+//!
+//! Context is stored per thread and never leaks to other threads:
+//! a field pushed on one thread is invisible to logs written from another.
+//!
+//! Scoped usage (recommended) - the field is removed again when the guard drops:
+//! `let _guard = scope("request_id", "42"); log_info!("handled");`
+//!
+//! Output:
+//! - Text patterns append the fields after the message: `... INFO: msg request_id=42`
+//! - JSON adds each field as an extra key: `{..., "request_id":"42"}`
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+thread_local! {
+    /// Context fields for the current thread, in insertion order
+    static CONTEXT: RefCell<Vec<(String, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Add or update a context field for the current thread
+///
+/// If the key already exists its value is replaced in place,
+/// keeping the original position in the output.
+///
+/// # Arguments
+/// * `key` - Field name (e.g. "request_id")
+/// * `value` - Field value
+pub fn push_context(key: &str, value: &str) {
+    CONTEXT.with(|context| {
+        let mut context = context.borrow_mut();
+        match context.iter_mut().find(|(existing, _)| existing == key) {
+            Some(entry) => entry.1 = value.to_string(),
+            None => context.push((key.to_string(), value.to_string())),
+        }
+    });
+}
+
+/// Remove a context field from the current thread
+///
+/// # Arguments
+/// * `key` - Field name to remove
+///
+/// # Returns
+/// The removed value, or None if the key was not set
+pub fn pop_context(key: &str) -> Option<String> {
+    CONTEXT.with(|context| {
+        let mut context = context.borrow_mut();
+        let position = context.iter().position(|(existing, _)| existing == key)?;
+        Some(context.remove(position).1)
+    })
+}
+
+/// Remove all context fields from the current thread
+pub fn clear_context() {
+    CONTEXT.with(|context| context.borrow_mut().clear());
+}
+
+/// RAII guard for a scoped context field
+///
+/// Returned by `scope()` and `scope_many()`. When dropped, each key is
+/// restored to the value it had before the guard was created, or removed
+/// if it was not set. This makes nested scopes that shadow a key safe.
+///
+/// The guard is tied to the thread that created it and cannot be sent
+/// to another thread.
+#[must_use = "the context field is removed as soon as the guard is dropped"]
+pub struct ContextGuard {
+    /// Keys set by this guard with their previous values, in insertion order
+    previous: Vec<(String, Option<String>)>,
+    /// Context is thread-local, so the guard must be dropped on the same thread
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        // Restore in reverse order so repeated keys end at their oldest value
+        for (key, previous) in self.previous.drain(..).rev() {
+            match previous {
+                Some(value) => push_context(&key, &value),
+                None => {
+                    pop_context(&key);
+                }
+            }
+        }
+    }
+}
+
+/// Set a context field for the lifetime of the returned guard
+///
+/// # Arguments
+/// * `key` - Field name (e.g. "request_id")
+/// * `value` - Field value
+///
+/// # Returns
+/// Guard that restores the previous context on drop
+pub fn scope(key: &str, value: &str) -> ContextGuard {
+    scope_many(&[(key, value)])
+}
+
+/// Set several context fields for the lifetime of the returned guard
+///
+/// # Arguments
+/// * `fields` - Key/value pairs to set
+///
+/// # Returns
+/// Guard that restores the previous context on drop
+pub fn scope_many(fields: &[(&str, &str)]) -> ContextGuard {
+    let previous = fields
+        .iter()
+        .map(|(key, value)| {
+            let old_value = get_context(key);
+            push_context(key, value);
+            (key.to_string(), old_value)
+        })
+        .collect();
+
+    ContextGuard {
+        previous,
+        _not_send: PhantomData,
+    }
+}
+
+/// Get the current value of a context field
+///
+/// # Returns
+/// The value, or None if the key is not set on this thread
+pub fn get_context(key: &str) -> Option<String> {
+    CONTEXT.with(|context| {
+        context
+            .borrow()
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, value)| value.clone())
+    })
+}
+
+/// Get a copy of the current thread's context fields
+///
+/// Used by the logger to attach context to each entry.
+pub fn current_context() -> Vec<(String, String)> {
+    CONTEXT.with(|context| context.borrow().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_push_and_pop_context() {
+        clear_context();
+        push_context("request_id", "42");
+        push_context("user", "alice");
+
+        assert_eq!(
+            current_context(),
+            vec![
+                ("request_id".to_string(), "42".to_string()),
+                ("user".to_string(), "alice".to_string()),
+            ]
+        );
+
+        assert_eq!(pop_context("request_id"), Some("42".to_string()));
+        assert_eq!(pop_context("request_id"), None);
+        assert_eq!(current_context().len(), 1);
+
+        clear_context();
+        assert!(current_context().is_empty());
+    }
+
+    #[test]
+    fn test_push_existing_key_replaces_value() {
+        clear_context();
+        push_context("user", "alice");
+        push_context("user", "bob");
+
+        assert_eq!(current_context(), vec![("user".to_string(), "bob".to_string())]);
+        clear_context();
+    }
+
+    #[test]
+    fn test_context_does_not_leak_across_threads() {
+        clear_context();
+        push_context("request_id", "main-thread");
+
+        let other = thread::spawn(current_context).join().unwrap();
+
+        assert!(other.is_empty());
+        assert_eq!(current_context().len(), 1);
+        clear_context();
+    }
+
+    #[test]
+    fn test_scope_removes_field_on_drop() {
+        clear_context();
+        {
+            let _guard = scope("request_id", "42");
+            assert_eq!(get_context("request_id"), Some("42".to_string()));
+        }
+        assert_eq!(get_context("request_id"), None);
+    }
+
+    #[test]
+    fn test_nested_scope_restores_shadowed_value() {
+        clear_context();
+        let outer = scope("user", "alice");
+        {
+            let _inner = scope("user", "bob");
+            assert_eq!(get_context("user"), Some("bob".to_string()));
+        }
+        assert_eq!(get_context("user"), Some("alice".to_string()));
+
+        drop(outer);
+        assert_eq!(get_context("user"), None);
+    }
+
+    #[test]
+    fn test_scope_many_sets_and_cleans_all_fields() {
+        clear_context();
+        push_context("env", "prod");
+        {
+            let _guard = scope_many(&[("env", "test"), ("request_id", "7")]);
+            assert_eq!(get_context("env"), Some("test".to_string()));
+            assert_eq!(get_context("request_id"), Some("7".to_string()));
+        }
+        assert_eq!(current_context(), vec![("env".to_string(), "prod".to_string())]);
+        clear_context();
+    }
+}
@@ -0,0 +1,118 @@
+//! Low disk space detection for FreedomLogger
+//!
+//! With `LoggerConfig::min_free_bytes` every file write first asks the file
+//! system how much space is left in the log directory. Below the threshold
+//! the logger reports `LoggerError::LowDiskSpace` once (again only after the
+//! space recovered and dropped again) and, with `pause_on_low_disk`, skips
+//! entries below ERROR until space is back.
+//!
+//! Best effort: the free space is read with `statvfs` on Unix; on other
+//! platforms (or when the query fails) the space is unknown and never low.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Query returning the bytes available to the process at a path
+pub(crate) type FreeSpaceQuery = fn(&Path) -> Option<u64>;
+
+/// Outcome of a free space check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SpaceCheck {
+    /// Enough space, or unknown
+    Ok,
+    /// Below the threshold; `first` is true for the first check of a low period
+    Low { available: u64, first: bool },
+}
+
+/// Threshold check shared by all writes of a logger
+#[derive(Debug)]
+pub(crate) struct DiskSpaceMonitor {
+    /// Minimum free bytes before a warning
+    min_free_bytes: u64,
+    /// Free space source (`available_space`, or a fake in tests)
+    query: FreeSpaceQuery,
+    /// Set while the space is below the threshold
+    low: AtomicBool,
+}
+
+impl DiskSpaceMonitor {
+    /// Monitor the space against `min_free_bytes` with the platform query
+    pub(crate) fn new(min_free_bytes: u64) -> Self {
+        Self::with_query(min_free_bytes, available_space)
+    }
+
+    /// Monitor with a custom free space query
+    pub(crate) fn with_query(min_free_bytes: u64, query: FreeSpaceQuery) -> Self {
+        Self { min_free_bytes, query, low: AtomicBool::new(false) }
+    }
+
+    /// Minimum free bytes before a warning
+    pub(crate) fn min_free_bytes(&self) -> u64 {
+        self.min_free_bytes
+    }
+
+    /// Compare the free space at `directory` with the threshold
+    pub(crate) fn check(&self, directory: &Path) -> SpaceCheck {
+        match (self.query)(directory) {
+            Some(available) if available < self.min_free_bytes => SpaceCheck::Low {
+                available,
+                first: !self.low.swap(true, Ordering::Relaxed),
+            },
+            _ => {
+                self.low.store(false, Ordering::Relaxed);
+                SpaceCheck::Ok
+            }
+        }
+    }
+}
+
+/// Bytes available to unprivileged users on the file system of `path`
+#[cfg(unix)]
+#[allow(unsafe_code)]
+pub(crate) fn available_space(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stats = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: c_path is a valid NUL-terminated string and stats is only read
+    // after statvfs reported success (which means it filled the struct)
+    let stats = unsafe {
+        if libc::statvfs(c_path.as_ptr(), stats.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stats.assume_init()
+    };
+    #[allow(clippy::unnecessary_cast)] // the field types differ between platforms
+    Some(stats.f_bavail as u64 * stats.f_frsize as u64)
+}
+
+/// Free space is unknown on this platform
+#[cfg(not(unix))]
+pub(crate) fn available_space(_path: &Path) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_low_space_is_first_reported_once_per_low_period() {
+        let monitor = DiskSpaceMonitor::with_query(100, |path| {
+            Some(if path == Path::new("full") { 10 } else { 1_000 })
+        });
+
+        assert_eq!(monitor.check(Path::new("full")), SpaceCheck::Low { available: 10, first: true });
+        assert_eq!(monitor.check(Path::new("full")), SpaceCheck::Low { available: 10, first: false });
+        assert_eq!(monitor.check(Path::new("free")), SpaceCheck::Ok);
+        assert_eq!(monitor.check(Path::new("full")), SpaceCheck::Low { available: 10, first: true });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_available_space_of_existing_directory() {
+        assert!(available_space(&std::env::temp_dir()).is_some());
+        assert_eq!(available_space(Path::new("/no/such/directory")), None);
+    }
+}
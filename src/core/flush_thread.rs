@@ -0,0 +1,53 @@
+//! Periodic flushing of buffered output for FreedomLogger
+//!
+//! With `LoggerConfig::buffered` and `LoggerConfig::flush_interval` a
+//! background thread flushes the writers once per interval, so an entry
+//! stays in memory for at most about one interval even when little is
+//! logged and the buffer never fills.
+//!
+//! The thread is stopped and joined by `Logger::shutdown()` and on drop.
+
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use crate::core::logger::LoggerCore;
+
+/// Background thread calling `LoggerCore::flush` once per interval
+pub(crate) struct FlushThread {
+    /// Dropping the sender wakes the thread and ends it (None once stopped)
+    stop_sender: Mutex<Option<Sender<()>>>,
+    /// The flush thread (None once stopped)
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl FlushThread {
+    /// Start flushing `core` every `interval`
+    pub(crate) fn start(core: Arc<LoggerCore>, interval: Duration) -> Self {
+        let (stop_sender, stop_receiver) = mpsc::channel::<()>();
+        let worker = thread::Builder::new()
+            .name("freedom-logger-flush".to_string())
+            .spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = stop_receiver.recv_timeout(interval) {
+                    core.flush();
+                }
+            })
+            .expect("failed to spawn the logger flush thread");
+
+        Self {
+            stop_sender: Mutex::new(Some(stop_sender)),
+            worker: Mutex::new(Some(worker)),
+        }
+    }
+
+    /// Stop the thread and wait for it to finish
+    ///
+    /// Safe to call more than once.
+    pub(crate) fn stop(&self) {
+        self.stop_sender.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+        let worker = self.worker.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+        if let Some(worker) = worker {
+            let _ = worker.join();
+        }
+    }
+}
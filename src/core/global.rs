@@ -0,0 +1,140 @@
+//! Slot holding the global logger
+//!
+//! Backs the `log_init*` and `reconfigure` functions: the logger lives behind
+//! an `RwLock<Option<Arc<Logger>>>`. Log calls take a cheap read lock and
+//! clone the `Arc`, so a logger swapped out by `reconfigure` stays alive
+//! until calls that already hold it have finished.
+//!
+//! - `init`: installs a logger only if none is set (repeated init is ignored)
+//! - `replace`: swaps in a new logger and flushes the old one
+
+use std::sync::{Arc, RwLock};
+use crate::core::logger::Logger;
+
+/// Storage for a logger that can be installed once and replaced explicitly
+pub(crate) struct LoggerSlot {
+    logger: RwLock<Option<Arc<Logger>>>,
+}
+
+impl LoggerSlot {
+    /// Create an empty slot (usable in a `static`)
+    pub(crate) const fn new() -> Self {
+        Self {
+            logger: RwLock::new(None),
+        }
+    }
+
+    /// Install a logger unless one is already set
+    ///
+    /// The logger is only created when the slot is empty (or another thread
+    /// is installing one at the same time; the loser's logger is dropped).
+    ///
+    /// # Returns
+    /// True if the logger was installed
+    pub(crate) fn init(&self, create: impl FnOnce() -> Logger) -> bool {
+        self.init_shared(|| Arc::new(create()))
+    }
+
+    /// Install a shared logger unless one is already set
+    ///
+    /// # Returns
+    /// True if the logger was installed
+    pub(crate) fn install(&self, logger: Arc<Logger>) -> bool {
+        self.init_shared(|| logger)
+    }
+
+    /// Install the logger from `create` if the slot is empty
+    ///
+    /// `create` runs without the lock held: building a logger does file I/O
+    /// and may report errors, and a log call or the panic hook on that path
+    /// would otherwise deadlock on the slot.
+    fn init_shared(&self, create: impl FnOnce() -> Arc<Logger>) -> bool {
+        if self.with(|_| ()).is_some() {
+            return false;
+        }
+        let logger = create();
+
+        let mut slot = self.logger.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if slot.is_some() {
+            return false;
+        }
+        *slot = Some(logger);
+        true
+    }
+
+    /// Install a new logger, replacing the current one
+    ///
+    /// The old logger is flushed once it is out of the slot; calls still
+    /// holding it finish writing to it, and it is dropped with its last user.
+    pub(crate) fn replace(&self, logger: Logger) {
+        let old = self
+            .logger
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .replace(Arc::new(logger));
+        if let Some(old) = old {
+            old.flush();
+        }
+    }
+
+    /// Get the current logger, if one is installed
+    pub(crate) fn get(&self) -> Option<Arc<Logger>> {
+        self.logger.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+
+    /// Run `f` on the current logger without taking a reference to it
+    ///
+    /// Cheaper than `get` for short checks (no reference count update), so
+    /// the level check of the macros uses this. `replace` waits until `f`
+    /// returns.
+    ///
+    /// # Returns
+    /// The result of `f`, or None if no logger is installed
+    #[inline]
+    pub(crate) fn with<R>(&self, f: impl FnOnce(&Logger) -> R) -> Option<R> {
+        self.logger.read().unwrap_or_else(|poisoned| poisoned.into_inner()).as_deref().map(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{LogLevel, LoggerConfig, Pattern};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_init_once_then_replace_switches_file() {
+        let temp_dir = tempdir().unwrap();
+        let config = |name: &str| LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), name.to_string());
+        let slot = LoggerSlot::new();
+        assert!(slot.get().is_none());
+
+        assert!(slot.init(|| Logger::new(config("a"))));
+        assert!(!slot.init(|| Logger::new(config("ignored"))));
+        slot.get().unwrap().info("Line for A");
+
+        slot.replace(Logger::new(config("b")));
+        slot.get().unwrap().info("Line for B");
+        assert_eq!(slot.with(|logger| logger.is_enabled(LogLevel::Info)), Some(true));
+
+        let file_a = fs::read_to_string(temp_dir.path().join("a.log")).unwrap();
+        let file_b = fs::read_to_string(temp_dir.path().join("b.log")).unwrap();
+        assert!(file_a.contains("INFO: Line for A") && !file_a.contains("Line for B"));
+        assert!(file_b.contains("INFO: Line for B") && !file_b.contains("Line for A"));
+        assert!(!temp_dir.path().join("ignored.log").exists());
+    }
+
+    #[test]
+    fn test_init_creates_logger_without_holding_the_lock() {
+        let temp_dir = tempdir().unwrap();
+        let slot = LoggerSlot::new();
+
+        // Would deadlock if init held the write lock while creating
+        assert!(slot.init(|| {
+            assert!(slot.get().is_none());
+            Logger::new(LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string()))
+        }));
+        assert!(slot.get().is_some());
+    }
+}
@@ -1,38 +1,177 @@
-/// Main logger implementation for FreedomLogger
-///
-/// This is the core orchestrator that brings together all components:
-/// - Configuration management
-/// - Message formatting using patterns
-/// - Writing to files using appropriate writers
-/// - Log rotation when files get too large
-/// - Error handling and fallback mechanisms
-///
-/// The Logger maintains internal state and provides the main logging methods
-/// that users call: info(), debug(), error(), warning(), trace().
+//! Main logger implementation for FreedomLogger
+//!
+//! This is the core orchestrator that brings together all components:
+//! - Configuration management
+//! - Message formatting using patterns
+//! - Writing to every configured destination (file, console, memory)
+//! - Log rotation when files get too large
+//! - Error handling and fallback mechanisms
+//!
+//! The Logger maintains internal state and provides the main logging methods
+//! that users call: info(), debug(), error(), warning(), trace().
+//!
+//! The Logger captures each entry on the calling thread and hands it to the
+//! shared `LoggerCore`, which formats and writes it - directly, or on a
+//! background thread in async mode (`LoggerConfig::async_queue_capacity`).
 
-use std::sync::Mutex;
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::collections::HashMap;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::fs::{self, OpenOptions};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU64, Ordering};
 use std::thread;
-use crate::core::config::{LoggerConfig, LogLevel, Pattern};
-use crate::core::writers::{TextWriter, JsonWriter};
-use crate::format::LogInfo;
-use crate::rotation::{SizeBasedRotation, RotationResult};
-use crate::error::{write_error_to_log, LoggerError};
+use crate::core::time::{self, Date, Timestamp};
+use crate::core::async_queue::AsyncQueue;
+use crate::core::flush_thread::FlushThread;
+use crate::core::clock::{Clock, SystemClock};
+use crate::core::config::{LoggerConfig, LoggerConfigSnapshot, LogLevel, Pattern, Destination, RotationStrategy};
+use crate::core::context::current_context;
+use crate::core::disk_space::{DiskSpaceMonitor, SpaceCheck};
+use crate::core::metrics::{LevelCounters, LogCounts};
+use crate::core::writers::{LogWriter, TextWriter, JsonWriter, HtmlWriter, ConsoleWriter, MemoryWriter, FallbackWriter, RetryWriter};
+use crate::format::{LogInfo, TimestampCache, compact_backtrace, format_gelf_timestamp, format_hexdump, sanitize_text, format_timestamp, format_rfc3339, truncate_message};
+use crate::rotation::{CombinedRotation, LineBasedRotation, SizeBasedRotation, RotationIndex, RotationResult};
+use crate::error::{write_repeated_error, ErrorRateLimiter, LoggerError};
+
+/// Stored in `Logger::level` when every level is logged (`log_level: None`)
+const LEVEL_ALL: u8 = u8::MAX;
+
+thread_local! {
+    /// Set while this thread is inside a logger's write lock
+    static WRITING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Marks the current thread as writing until dropped (also on unwind)
+struct WritingGuard(bool);
+
+impl WritingGuard {
+    fn enter() -> Self {
+        Self(WRITING.with(|writing| writing.replace(true)))
+    }
+}
+
+impl Drop for WritingGuard {
+    fn drop(&mut self) {
+        WRITING.with(|writing| writing.set(self.0));
+    }
+}
+
+/// Check if the current thread is writing a log entry
+///
+/// A panic hook must not log then: the write lock is held by this thread.
+pub(crate) fn is_writing() -> bool {
+    WRITING.with(Cell::get)
+}
 
 /// Main logger struct that handles all logging operations
 ///
-/// Contains configuration, writers, and rotation management.
-/// Thread-safe through internal mutex for concurrent access.
+/// Filters by level and captures each entry on the calling thread; the
+/// shared core does the formatting, rotation and writing.
+/// Thread-safe: every field is Send + Sync, writes are serialized by a mutex in the core.
 pub struct Logger {
+    /// Writers, rotation and configuration, shared with the async worker
+    core: Arc<LoggerCore>,
+    /// Active minimum level, seeded from the config and changed by `set_level`
+    level: AtomicU8,
+    /// Queue to the background writer thread (async mode only)
+    async_queue: Option<AsyncQueue>,
+    /// Thread flushing buffered output periodically (`flush_interval` only)
+    flush_thread: Option<FlushThread>,
+    /// Emitted entries per level and filtered messages
+    counters: LevelCounters,
+}
+
+/// Everything that writes entries: used directly by the Logger, or by the
+/// background thread in async mode
+pub(crate) struct LoggerCore {
     /// Logger configuration (pattern, paths, levels, etc.)
     config: LoggerConfig,
-    /// Text file writer for plain text logs
-    text_writer: TextWriter,
-    /// JSON writer for structured logs
+    /// One writer per configured destination - each entry goes to all of them
+    writers: Vec<Box<dyn LogWriter>>,
+    /// JSON formatter for non-file destinations with the JSON pattern
     json_writer: JsonWriter,
+    /// Ring buffer of the memory destination, if configured
+    memory_writer: Option<Arc<MemoryWriter>>,
     /// Log rotation manager
     rotation: SizeBasedRotation,
+    /// Line counting rotation, used instead with `RotationStrategy::Lines`
+    line_rotation: Option<LineBasedRotation>,
+    /// Size-or-new-day rotation, used instead with `RotationStrategy::SizeOrDaily`
+    combined_rotation: Option<CombinedRotation>,
+    /// Manifest of backups and their time spans (with `rotation_index`)
+    rotation_index: Option<RotationIndex>,
+    /// Estimated size of each log file, so rotation needs no `fs::metadata` per call
+    file_sizes: Mutex<HashMap<PathBuf, u64>>,
+    /// Number of file destinations (all of them write to the same path)
+    file_writer_count: u64,
+    /// Free space check before file writes (with `min_free_bytes`)
+    disk_space: Option<DiskSpaceMonitor>,
+    /// Source of entry timestamps
+    clock: Arc<dyn Clock>,
+    /// Process ID added to entries (with `include_pid`)
+    pid: Option<u32>,
+    /// Host name added to entries, read once at creation (with `include_hostname`)
+    hostname: Option<String>,
+    /// Next entry sequence number (with `include_sequence`)
+    sequence: Option<AtomicU64>,
+    /// Core of the separate error file and the least severe level it gets
+    /// (with `separate_error_file`)
+    error_file: Option<(LogLevel, Box<LoggerCore>)>,
+    /// Formatted timestamp of the current second, shared by its entries
+    timestamp_cache: TimestampCache,
+    /// Day and path of the current file with `daily_files`, cached per day
+    daily_path: Mutex<Option<(Date, PathBuf)>>,
     /// Mutex for thread-safe logging operations
     write_mutex: Mutex<()>,
+    /// Set once a poisoned mutex has been reported (reported only once)
+    poison_reported: AtomicBool,
+    /// Collapses identical consecutive errors before they reach the error log
+    error_limiter: ErrorRateLimiter,
+    /// Last written entry and its suppressed repeats (with `dedup_consecutive`)
+    last_entry: Mutex<Option<(Entry<'static>, u64)>>,
+}
+
+/// One log entry as captured on the calling thread
+///
+/// Borrows the caller's strings when written directly; `into_owned()`
+/// makes it `'static` so it can be queued for the async worker.
+#[derive(Clone)]
+pub(crate) struct Entry<'a> {
+    pub(crate) level: LogLevel,
+    pub(crate) message: Cow<'a, str>,
+    /// Lines the logger adds below the message itself (hexdump rows,
+    /// backtrace frames); not sanitized, so they stay on separate lines
+    pub(crate) detail: Option<Cow<'a, str>>,
+    pub(crate) module: Option<Cow<'a, str>>,
+    pub(crate) file: Cow<'a, str>,
+    pub(crate) line: u32,
+    pub(crate) thread_name: String,
+    pub(crate) thread_id: u64,
+    pub(crate) now: Timestamp,
+    pub(crate) context: Vec<(String, String)>,
+}
+
+impl Entry<'_> {
+    /// Copy the borrowed parts, for handing the entry to another thread
+    pub(crate) fn into_owned(self) -> Entry<'static> {
+        Entry {
+            level: self.level,
+            message: Cow::Owned(self.message.into_owned()),
+            detail: self.detail.map(|detail| Cow::Owned(detail.into_owned())),
+            module: self.module.map(|module| Cow::Owned(module.into_owned())),
+            file: Cow::Owned(self.file.into_owned()),
+            line: self.line,
+            thread_name: self.thread_name,
+            thread_id: self.thread_id,
+            now: self.now,
+            context: self.context,
+        }
+    }
 }
 
 impl Logger {
@@ -44,168 +183,2782 @@ impl Logger {
     /// # Returns
     /// New Logger instance ready for logging operations
     pub fn new(config: LoggerConfig) -> Self {
-        let rotation = SizeBasedRotation::new(
-            config.max_file_size,
-            config.max_backup_files,
-        );
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    /// Create a new logger that takes entry timestamps from the given clock
+    ///
+    /// Mainly for tests: a fixed clock makes timestamps predictable.
+    ///
+    /// # Arguments
+    /// * `config` - Complete logger configuration
+    /// * `clock` - Timestamp source (any `Fn() -> Timestamp` works)
+    pub fn with_clock(config: LoggerConfig, clock: Arc<dyn Clock>) -> Self {
+        let level = AtomicU8::new(config.log_level.map_or(LEVEL_ALL, |level| level as u8));
+        let async_queue_capacity = config.async_queue_capacity;
+        let core = Arc::new(LoggerCore::new(config, clock));
+
+        let async_queue = async_queue_capacity.map(|capacity| AsyncQueue::start(Arc::clone(&core), capacity));
+        let flush_thread = core.config.flush_interval
+            .filter(|_| core.config.buffered)
+            .map(|interval| FlushThread::start(Arc::clone(&core), interval));
 
         Self {
-            config,
-            text_writer: TextWriter::new(),
-            json_writer: JsonWriter::new(),
-            rotation,
-            write_mutex: Mutex::new(()),
+            core,
+            level,
+            async_queue,
+            flush_thread,
+            counters: LevelCounters::default(),
+        }
+    }
+
+    /// Point the configuration at the temp directory if the log file can't be written
+    ///
+    /// # Returns
+    /// The error describing the fallback (to be logged once), or None if the
+    /// configured directory is writable
+    fn fall_back_to_temp(config: &mut LoggerConfig) -> Option<LoggerError> {
+        let log_file_path = config.get_log_file_path();
+        if TextWriter::new().test_write_permissions(&log_file_path).is_ok() {
+            return None;
         }
+
+        let temp_dir = std::env::temp_dir();
+        let error = LoggerError::FileCreationFailed {
+            path: log_file_path.display().to_string(),
+            reason: format!("Log directory is not writable - falling back to {}", temp_dir.display()),
+        };
+        config.file_path = temp_dir;
+        Some(error)
     }
 
     /// Log an ERROR level message
     ///
     /// # Arguments
     /// * `message` - The message to log
+    #[track_caller]
     pub fn error(&self, message: &str) {
-        self.log(LogLevel::Error, message, file!(), line!());
+        self.log_caller(LogLevel::Error, message);
     }
 
     /// Log a WARNING level message
     ///
     /// # Arguments
     /// * `message` - The message to log
+    #[track_caller]
     pub fn warning(&self, message: &str) {
-        self.log(LogLevel::Warning, message, file!(), line!());
+        self.log_caller(LogLevel::Warning, message);
     }
 
     /// Log an INFO level message
     ///
     /// # Arguments
     /// * `message` - The message to log
+    #[track_caller]
     pub fn info(&self, message: &str) {
-        self.log(LogLevel::Info, message, file!(), line!());
+        self.log_caller(LogLevel::Info, message);
     }
 
     /// Log a DEBUG level message
     ///
     /// # Arguments
     /// * `message` - The message to log
+    #[track_caller]
     pub fn debug(&self, message: &str) {
-        self.log(LogLevel::Debug, message, file!(), line!());
+        self.log_caller(LogLevel::Debug, message);
     }
 
     /// Log a TRACE level message
     ///
     /// # Arguments
     /// * `message` - The message to log
+    #[track_caller]
     pub fn trace(&self, message: &str) {
-        self.log(LogLevel::Trace, message, file!(), line!());
+        self.log_caller(LogLevel::Trace, message);
+    }
+
+    /// Log a message attributed to a source location without a module path
+    ///
+    /// Used for panics, where only the file and line are known.
+    pub(crate) fn log_located(&self, level: LogLevel, message: &str, file: &str, line: u32) {
+        self.log(level, message, None, file, line);
+    }
+
+    /// Log a message at the given level
+    ///
+    /// # Arguments
+    /// * `level` - Level of the message
+    /// * `message` - The message to log
+    #[track_caller]
+    pub fn log_at(&self, level: LogLevel, message: &str) {
+        self.log_caller(level, message);
+    }
+
+    /// Log a message attributed to the caller's location
+    ///
+    /// The public methods are `#[track_caller]`, so this is the line that
+    /// called `info()`, `log_info()`, ... (not a line in this file).
+    #[track_caller]
+    fn log_caller(&self, level: LogLevel, message: &str) {
+        let location = std::panic::Location::caller();
+        self.log(level, message, None, location.file(), location.line());
+    }
+
+    /// Get the configured minimum level
+    ///
+    /// # Returns
+    /// The most verbose level that is written, or None when every level is logged
+    pub fn current_level(&self) -> Option<LogLevel> {
+        match self.level.load(Ordering::Relaxed) {
+            0 => Some(LogLevel::Off),
+            1 => Some(LogLevel::Error),
+            2 => Some(LogLevel::Warning),
+            3 => Some(LogLevel::Info),
+            4 => Some(LogLevel::Debug),
+            5 => Some(LogLevel::Trace),
+            _ => None,
+        }
+    }
+
+    /// Get a read-only snapshot of the active configuration
+    ///
+    /// Paths, pattern, rotation limits and destinations as the logger uses
+    /// them, with the level currently in effect (see `set_level`).
+    pub fn current_config(&self) -> LoggerConfigSnapshot {
+        self.core.config.snapshot(self.current_level())
+    }
+
+    /// Change the minimum level at runtime
+    ///
+    /// Affects this logger only: other loggers created from the same (or a
+    /// cloned) config keep their own level.
+    ///
+    /// # Arguments
+    /// * `level` - New minimum level (`LogLevel::Off` mutes the logger)
+    pub fn set_level(&self, level: LogLevel) {
+        self.level.store(level as u8, Ordering::Relaxed);
+    }
+
+    /// Check if a message at this level would be written
+    #[inline]
+    pub fn is_enabled(&self, level: LogLevel) -> bool {
+        match self.current_level() {
+            Some(configured_level) => level.should_log(configured_level),
+            None => level != LogLevel::Off,
+        }
+    }
+
+    /// Check the level like `is_enabled`, counting a rejection as filtered
+    #[inline]
+    pub(crate) fn filter(&self, level: LogLevel) -> bool {
+        let enabled = self.is_enabled(level);
+        if !enabled {
+            self.counters.record_filtered();
+        }
+        enabled
+    }
+
+    /// Check a message against `LoggerConfig::suppress_patterns`
+    #[cfg(feature = "regex")]
+    fn is_suppressed(&self, message: &str) -> bool {
+        let patterns = &self.core.config.suppress_patterns;
+        !patterns.is_empty() && patterns.iter().any(|pattern| pattern.is_match(message))
+    }
+
+    /// Log a message with the call site captured by the logging macros
+    ///
+    /// # Arguments
+    /// * `level` - Level of the message
+    /// * `message` - The message to log
+    /// * `module` - Module path of the call site (`module_path!()`)
+    /// * `file` - Source file of the call site (`file!()`)
+    /// * `line` - Line of the call site (`line!()`)
+    pub fn log_from(&self, level: LogLevel, message: &str, module: &str, file: &str, line: u32) {
+        self.log(level, message, Some(module), file, line);
+    }
+
+    /// Log a byte buffer as a hexdump (offset, hex and ASCII columns)
+    ///
+    /// The message is a "hexdump (N bytes)" header followed by one line per
+    /// `LoggerConfig::hexdump_width` bytes. Nothing is formatted when the
+    /// level is filtered out.
+    ///
+    /// # Arguments
+    /// * `level` - Level of the message
+    /// * `bytes` - The buffer to dump
+    #[track_caller]
+    pub fn log_hex(&self, level: LogLevel, bytes: &[u8]) {
+        let location = std::panic::Location::caller();
+        self.log_hex_at(level, bytes, None, location.file(), location.line());
+    }
+
+    /// Log a hexdump with the call site captured by `log_hex!`
+    pub fn log_hex_from(&self, level: LogLevel, bytes: &[u8], module: &str, file: &str, line: u32) {
+        self.log_hex_at(level, bytes, Some(module), file, line);
+    }
+
+    /// Format the hexdump only when the level is enabled, then log it
+    fn log_hex_at(&self, level: LogLevel, bytes: &[u8], module: Option<&str>, file: &str, line: u32) {
+        if !self.filter(level) {
+            return;
+        }
+        let message = format!("hexdump ({} bytes)", bytes.len());
+        let dump = format_hexdump(bytes, self.core.config.hexdump_width);
+        self.log_with_detail(level, &message, Some(dump), module, file, line);
+    }
+
+    /// Format a message like it would be written, without writing it
+    ///
+    /// Applies the configured pattern (a JSON line for `Pattern::Json`) to an
+    /// entry with the current timestamp, thread and context and the caller's
+    /// location. Nothing is written or counted, and the level is not checked.
+    ///
+    /// # Returns
+    /// The formatted entry, without a line ending
+    #[track_caller]
+    pub fn format_entry(&self, level: LogLevel, message: &str) -> String {
+        let location = std::panic::Location::caller();
+        let entry = self.capture(level, Cow::Borrowed(message), None, None, location.file(), location.line());
+        self.core.format_entry(&entry)
+    }
+
+    /// Capture an entry with the calling thread's name, ID and context
+    fn capture<'a>(&self, level: LogLevel, message: Cow<'a, str>, detail: Option<String>, module: Option<&'a str>, file: &'a str, line: u32) -> Entry<'a> {
+        Entry {
+            level,
+            message,
+            detail: detail.map(Cow::Owned),
+            module: module.map(Cow::Borrowed),
+            file: Cow::Borrowed(file),
+            line,
+            thread_name: current_thread_name(),
+            thread_id: current_thread_id(),
+            now: self.core.clock.now(),
+            context: current_context(),
+        }
     }
 
     /// Internal logging method that handles all log levels
     ///
-    /// This method orchestrates the entire logging process:
-    /// 1. Check if level should be logged (filtering)
-    /// 2. Create LogInfo with current timestamp and location
-    /// 3. Check and perform log rotation if needed
-    /// 4. Format message using configured pattern
-    /// 5. Write to appropriate file format
-    /// 6. Handle any errors silently
-    fn log(&self, level: LogLevel, message: &str, file: &str, line: u32) {
+    /// 1. Check if level should be logged (filtering), and count the entry
+    /// 2. Capture timestamp, thread and context on the calling thread
+    /// 3. Write the entry now, or queue it for the background writer in async mode
+    fn log(&self, level: LogLevel, message: &str, module: Option<&str>, file: &str, line: u32) {
+        self.log_with_detail(level, message, None, module, file, line);
+    }
+
+    /// Log a message with lines of the logger's own below it (see `Entry::detail`)
+    fn log_with_detail(&self, level: LogLevel, message: &str, detail: Option<String>, module: Option<&str>, file: &str, line: u32) {
         // Step 1: Check if this log level should be written
-        if !self.config.should_log_level(level) {
+        if !self.filter(level) {
             return; // Silently ignore - no error
         }
+        #[cfg(feature = "regex")]
+        if self.is_suppressed(message) {
+            self.counters.record_suppressed();
+            return;
+        }
+        self.counters.record(level);
 
-        // Step 2: Thread-safe logging operation
-        let _lock = match self.write_mutex.lock() {
-            Ok(lock) => lock,
-            Err(_) => {
-                // Mutex poisoned - attempt to continue without lock
-                // In production, we prefer to log something rather than nothing
-                self.handle_error(LoggerError::RotationFailed {
-                    current_file: "mutex".to_string(),
-                    backup_file: "poisoned".to_string(),
-                    reason: "Mutex poisoned during logging".to_string(),
-                });
-                return;
+        // Step 2: Capture everything that belongs to the calling thread
+        // (including its stack, so it is taken here and not on the async writer)
+        let detail = if level == LogLevel::Error && self.core.config.backtrace_on_error {
+            let frames = compact_backtrace(&Backtrace::force_capture());
+            Some(match detail {
+                Some(detail) => format!("{}\nbacktrace:\n{}", detail, frames),
+                None => format!("backtrace:\n{}", frames),
+            })
+        } else {
+            detail
+        };
+        let entry = self.capture(level, Cow::Borrowed(message), detail, module, file, line);
+
+        // Step 3: Write or queue
+        match &self.async_queue {
+            Some(queue) => {
+                // Best effort: the worker that normally writes is gone
+                if let Err(error) = queue.push(entry.into_owned()) {
+                    self.core.handle_error(error);
+                }
             }
+            None => self.core.write(&entry),
+        }
+    }
+
+    /// Finish pending output of every writer (e.g. close a JSON array)
+    ///
+    /// In async mode this first waits until the queued entries are written.
+    /// Safe to call repeatedly; logging can continue afterwards.
+    pub fn flush(&self) {
+        match &self.async_queue {
+            Some(queue) => queue.flush(),
+            None => self.core.flush(),
+        }
+    }
+
+    /// Flush all writers before the application exits
+    ///
+    /// In async mode this writes the queued entries and stops the background
+    /// thread; entries logged afterwards are discarded (reported as
+    /// ChannelSendFailed in the error log), never a panic. Otherwise it is the
+    /// same as `flush()`: messages logged after shutdown are still written,
+    /// but need another flush to be completed. The periodic flush thread
+    /// (`flush_interval`) is stopped.
+    pub fn shutdown(&self) {
+        if let Some(flush_thread) = &self.flush_thread {
+            flush_thread.stop();
+        }
+        match &self.async_queue {
+            Some(queue) => queue.stop(),
+            None => self.core.flush(),
+        }
+    }
+
+    /// Number of entries dropped because the async queue was full
+    ///
+    /// # Returns
+    /// Total since creation (always 0 outside async mode)
+    pub fn dropped_count(&self) -> u64 {
+        self.async_queue.as_ref().map_or(0, AsyncQueue::dropped_count)
+    }
+
+    /// Number of entries emitted per level, and of filtered, suppressed and dropped messages
+    ///
+    /// Counted without locks on the logging path. An entry counts as
+    /// emitted once it passes the level filter (written, or queued in async
+    /// mode); entries a full async queue dropped are also in `dropped`.
+    ///
+    /// # Returns
+    /// Totals since creation
+    pub fn log_counts(&self) -> LogCounts {
+        self.counters.snapshot(self.dropped_count())
+    }
+
+    /// Bytes of file output currently held in memory (`LoggerConfig::buffered`)
+    ///
+    /// Sums the buffers of all writers; 0 without buffering and after a
+    /// flush. Entries waiting in the async queue are not included.
+    pub fn buffer_used(&self) -> usize {
+        self.core.buffer_used()
+    }
+
+    /// Path of the log file an entry logged now is written to
+    ///
+    /// Includes the extension (`.log`, `.json`, `file_extension`, `.gz` with
+    /// `compress_live`) and, with `daily_files`, today's date. Also reflects
+    /// the temp-directory fallback taken when `file_path` was unwritable.
+    pub fn current_log_path(&self) -> PathBuf {
+        self.core.current_log_path()
+    }
+
+    /// Get a copy of the lines captured by the memory destination
+    ///
+    /// # Returns
+    /// Captured lines, oldest first (empty without a memory destination)
+    pub fn captured_lines(&self) -> Vec<String> {
+        self.core.memory_writer
+            .as_ref()
+            .map(|writer| writer.lines())
+            .unwrap_or_default()
+    }
+}
+
+impl LoggerCore {
+    /// Build the writers, rotation and instance data for `config`
+    ///
+    /// Falls back to the temp directory and truncates the log file when
+    /// configured, reporting problems to the error log.
+    fn new(mut config: LoggerConfig, clock: Arc<dyn Clock>) -> Self {
+        #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+        use_web_console(&mut config);
+
+        let fallback_error = if config.fallback_to_temp && config.has_file_destination() {
+            Logger::fall_back_to_temp(&mut config)
+        } else {
+            None
+        };
+
+        let rotation = SizeBasedRotation::new(
+            config.max_file_size,
+            config.max_backup_files,
+        ).with_max_total_size(config.max_total_size);
+        let line_rotation = match config.rotation_strategy {
+            RotationStrategy::Lines(max_lines) => Some(
+                LineBasedRotation::new(max_lines, config.max_backup_files)
+                    .with_max_total_size(config.max_total_size),
+            ),
+            _ => None,
+        };
+        let combined_rotation = (config.rotation_strategy == RotationStrategy::SizeOrDaily).then(|| {
+            CombinedRotation::new(config.max_file_size, config.max_backup_files).with_utc(config.use_utc)
+        });
+
+        let rotation_index = (config.rotation_index && config.has_file_destination()).then(|| {
+            RotationIndex::new(config.get_rotation_index_path()).with_utc(config.use_utc)
+        });
+
+        let buffer_capacity = config.buffered.then_some(config.buffer_capacity);
+        let mut writers: Vec<Box<dyn LogWriter>> = Vec::new();
+        let mut memory_writer = None;
+
+        for destination in &config.destinations {
+            let writer: Box<dyn LogWriter> = match destination {
+                Destination::File => file_writer(&config, buffer_capacity),
+                Destination::Memory(capacity) => {
+                    let writer = Arc::new(MemoryWriter::new(*capacity));
+                    memory_writer.get_or_insert_with(|| Arc::clone(&writer));
+                    Box::new(writer)
+                }
+                Destination::Console => Box::new(ConsoleWriter::new().with_color(config.console_color)),
+                Destination::Stderr => Box::new(ConsoleWriter::stderr().with_color(config.console_color)),
+                #[cfg(all(windows, feature = "eventlog"))]
+                Destination::EventLog(source) => Box::new(crate::core::writers::WindowsEventWriter::new(source)),
+                #[cfg(all(unix, feature = "journald"))]
+                Destination::Journald => Box::new(crate::core::writers::JournaldWriter::new()),
+                #[cfg(unix)]
+                Destination::Fifo(path) => Box::new(
+                    crate::core::writers::FifoWriter::new(path)
+                        .with_buffer_limit(config.fifo_buffer_size)
+                        .with_framing(config.fifo_framing),
+                ),
+                #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+                Destination::WebConsole => Box::new(crate::core::writers::WebConsoleWriter::new()),
+            };
+
+            // Retries first, so the console fallback only sees writes that failed for good
+            let writer = with_retries(&config, writer);
+            writers.push(match (destination, config.console_fallback_after) {
+                (Destination::File, Some(max_failures)) => Box::new(FallbackWriter::new(writer, max_failures)),
+                _ => writer,
+            });
+        }
+
+        if let Some(custom_writer) = &config.custom_writer {
+            writers.push(with_retries(&config, Box::new(Arc::clone(custom_writer))));
+        }
+
+        let json_writer = JsonWriter::new()
+            .with_otel(config.pattern == Pattern::OtelJson)
+            .with_pretty(config.json_pretty)
+            .with_fields(config.json_fields.clone())
+            .with_severity_number(config.json_severity_number);
+
+        let pid = config.include_pid.then(std::process::id);
+        // GELF requires the host in every message
+        let hostname = (config.include_hostname || config.pattern == Pattern::Gelf).then(read_hostname);
+        let sequence = config.include_sequence.then(|| AtomicU64::new(0));
+        let file_writer_count = config.destinations.iter()
+            .filter(|destination| matches!(destination, Destination::File))
+            .count() as u64;
+
+        let disk_space = config.min_free_bytes.map(DiskSpaceMonitor::new);
+        let error_file = config.separate_error_file.map(|level| {
+            (level, Box::new(LoggerCore::new(error_file_config(&config), Arc::clone(&clock))))
+        });
+
+        let core = LoggerCore {
+            config,
+            pid,
+            hostname,
+            writers,
+            json_writer,
+            memory_writer,
+            rotation,
+            line_rotation,
+            combined_rotation,
+            rotation_index,
+            file_sizes: Mutex::new(HashMap::new()),
+            file_writer_count,
+            disk_space,
+            clock,
+            sequence,
+            error_file,
+            timestamp_cache: TimestampCache::new(),
+            daily_path: Mutex::new(None),
+            write_mutex: Mutex::new(()),
+            poison_reported: AtomicBool::new(false),
+            error_limiter: ErrorRateLimiter::default(),
+            last_entry: Mutex::new(None),
         };
 
-        // Step 3: Create log info with all available data
-        let timestamp = self.get_current_timestamp();
-        let thread_name = self.get_current_thread_name();
+        if let Some(error) = fallback_error {
+            core.handle_error(error);
+        }
+
+        if core.config.truncate_on_start && core.config.has_file_destination() {
+            core.truncate_log_file();
+        }
+
+        core
+    }
+
+    /// Empty the current log file (used once at creation for `truncate_on_start`)
+    ///
+    /// A missing file is not an error - it will be created by the first write.
+    fn truncate_log_file(&self) {
+        let log_file_path = self.log_file_path_at(self.clock.now());
+        if !log_file_path.exists() {
+            return;
+        }
 
-        let log_info = LogInfo::new(message, level, &timestamp)
-            .with_location(file, line)
-            .with_thread(&thread_name);
+        if OpenOptions::new().write(true).truncate(true).open(&log_file_path).is_err() {
+            self.handle_error(LoggerError::FileCreationFailed {
+                path: log_file_path.display().to_string(),
+                reason: "Failed to truncate log file on start".to_string(),
+            });
+        }
+    }
 
-        // Step 4: Get appropriate file path based on pattern
-        let log_file_path = match self.config.pattern {
-            Pattern::Json => {
-                // JSON pattern uses .json extension
-                self.config.file_path.join(format!("{}.json", self.config.file_name))
+    /// Write one captured entry
+    ///
+    /// This method orchestrates the entire writing process:
+    /// 1. Lock (recovering a poisoned mutex)
+    /// 2. Create LogInfo with timestamp and location
+    /// 3. Check and perform log rotation if needed
+    /// 4. Format message using configured pattern
+    /// 5. Write to every destination
+    /// 6. Handle any errors silently
+    pub(crate) fn write(&self, entry: &Entry) {
+        // Step 1: Thread-safe logging operation
+        // A poisoned mutex is recovered: it only guards `()`, so there is no
+        // invariant that could have been broken by the panicking thread
+        let _lock = self.write_mutex.lock().unwrap_or_else(|poisoned| {
+            self.write_mutex.clear_poison();
+            if !self.poison_reported.swap(true, Ordering::Relaxed) {
+                self.handle_error(LoggerError::LockPoisoned {
+                    reason: "write mutex poisoned during logging - recovered, logging continues".to_string(),
+                });
             }
-            _ => {
-                // All other patterns use .log extension
-                self.config.get_log_file_path()
+            poisoned.into_inner()
+        });
+        let _writing = WritingGuard::enter();
+
+        // A repeat of the previous entry is only counted
+        if self.config.dedup_consecutive && self.count_repeat(entry) {
+            return;
+        }
+
+        self.write_numbered(entry);
+    }
+
+    /// Number an entry and write it (the write lock must be held)
+    fn write_numbered(&self, entry: &Entry) {
+        // Taken under the write lock, so the numbers follow the file order
+        let seq = self.sequence.as_ref().map(|sequence| sequence.fetch_add(1, Ordering::Relaxed));
+
+        // Step 2: Create log info with all available data
+        self.with_log_info(entry, seq, |log_info| self.write_info(entry, log_info));
+    }
+
+    /// Track consecutive identical entries (with `dedup_consecutive`)
+    ///
+    /// An entry with the same level and message as the previous one is
+    /// counted instead of written. A different entry first gets the repeat
+    /// summary of the previous one written, then becomes the new previous.
+    ///
+    /// # Returns
+    /// True when the entry is a repeat and must not be written
+    fn count_repeat(&self, entry: &Entry) -> bool {
+        let mut last_entry = self.last_entry.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((last, repeats)) = last_entry.as_mut() {
+            if last.level == entry.level && last.message == entry.message && last.detail == entry.detail {
+                last.now = entry.now;
+                *repeats += 1;
+                return true;
+            }
+        }
+
+        let previous = last_entry.replace((entry.clone().into_owned(), 0));
+        drop(last_entry);
+        if let Some((last, repeats)) = previous {
+            self.write_repeat_summary(last, repeats);
+        }
+        false
+    }
+
+    /// Write the pending repeat summary, if any (the write lock must be held)
+    ///
+    /// The previous entry stays, so repeats after a flush are counted again.
+    fn flush_repeats(&self) {
+        let pending = self
+            .last_entry
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .as_mut()
+            .map(|(last, repeats)| (last.clone(), std::mem::take(repeats)));
+        if let Some((last, repeats)) = pending {
+            self.write_repeat_summary(last, repeats);
+        }
+    }
+
+    /// Write "... (repeated N times)" for an entry, at its level and call site
+    fn write_repeat_summary(&self, mut last: Entry<'static>, repeats: u64) {
+        if repeats == 0 {
+            return;
+        }
+        let times = if repeats == 1 { "time" } else { "times" };
+        last.message = Cow::Owned(format!("... (repeated {} {})", repeats, times));
+        last.detail = None;
+        self.write_numbered(&last);
+    }
+
+    /// Format one captured entry with the configured pattern, without writing it
+    ///
+    /// Shows the sequence number the next written entry gets (without taking it).
+    pub(crate) fn format_entry(&self, entry: &Entry) -> String {
+        let seq = self.sequence.as_ref().map(|sequence| sequence.load(Ordering::Relaxed));
+        self.with_log_info(entry, seq, |log_info| self.format_line(log_info))
+    }
+
+    /// Build the `LogInfo` of an entry and pass it to `f`
+    ///
+    /// Applies redaction, sanitizing and truncation to the message and adds
+    /// the fields, location and instance data. The entry's detail lines are
+    /// appended after sanitizing, so they keep their line breaks.
+    fn with_log_info<R>(&self, entry: &Entry, seq: Option<u64>, f: impl FnOnce(&LogInfo) -> R) -> R {
+        let timestamp = self.format_entry_timestamp(entry.now);
+
+        // Redact before truncating, so a secret cut in half is still masked
+        #[cfg(feature = "redaction")]
+        let redacted = crate::format::redact(&entry.message, &self.config.redactors);
+        #[cfg(feature = "redaction")]
+        let message: &str = &redacted;
+        #[cfg(not(feature = "redaction"))]
+        let message: &str = &entry.message;
+
+        // Text patterns escape newlines and control codes (JSON escapes them itself)
+        let sanitized = if self.config.sanitize_text && !self.config.pattern.is_json() {
+            sanitize_text(message)
+        } else {
+            Cow::Borrowed(message)
+        };
+        let sanitized = match &entry.detail {
+            Some(detail) => {
+                #[cfg(feature = "redaction")]
+                let detail = crate::format::redact(detail, &self.config.redactors);
+                Cow::Owned(format!("{}\n{}", sanitized, detail))
             }
+            None => sanitized,
         };
 
-        // Step 5: Check and perform rotation if needed
-        match self.rotation.check_and_rotate(&log_file_path) {
-            RotationResult::Failed(error) => {
-                self.handle_error(error);
-                // Continue with logging even if rotation failed
+        let message = match self.config.max_message_len {
+            Some(max_len) => truncate_message(&sanitized, max_len),
+            None => Cow::Borrowed(sanitized.as_ref()),
+        };
+
+        let fields = self.entry_fields(&entry.context);
+
+        let mut log_info = LogInfo::new(&message, entry.level, &timestamp)
+            .with_location(&entry.file, entry.line)
+            .with_thread(&entry.thread_name)
+            .with_thread_id(entry.thread_id)
+            .with_fields(&fields);
+        if let Some(module) = &entry.module {
+            log_info = log_info.with_module(module);
+        }
+        if let Some(pid) = self.pid {
+            log_info = log_info.with_pid(pid);
+        }
+        if let Some(hostname) = &self.hostname {
+            log_info = log_info.with_hostname(hostname);
+        }
+        if let Some(seq) = seq {
+            log_info = log_info.with_seq(seq);
+        }
+
+        f(&log_info)
+    }
+
+    /// Rotate if needed and write a prepared entry to every destination
+    fn write_info(&self, entry: &Entry, log_info: &LogInfo) {
+        // Step 3: Get file path (.json for JSON, .log otherwise, unless a custom extension is set)
+        // With daily files the path follows the date of this entry
+        let log_file_path = self.log_file_path_at(entry.now);
+
+        // Step 4: Check free space, then perform rotation if needed (file destination only)
+        // One decision per entry for the path, shared by every file destination
+        if self.config.has_file_destination() {
+            if !self.has_disk_space_for(entry.level, &log_file_path) {
+                return;
             }
-            _ => {
-                // Rotation completed or not needed - continue normally
+            self.rotate_if_needed(&log_file_path, self.entry_date(entry.now));
+            if let Some(rotation_index) = &self.rotation_index {
+                rotation_index.record_entry(entry.now);
             }
         }
 
-        // Step 6: Write the log entry
-        self.write_log_entry(&log_info, &log_file_path);
-    }
+        // Step 5: Format and write the log entry
+        // A panic in a pattern, formatter or custom writer must not unwind
+        // through the held lock (poisoning it) or into the caller
+        let write_result = panic::catch_unwind(AssertUnwindSafe(|| {
+            self.write_log_entry(log_info, &log_file_path);
 
-    /// Write a log entry using the appropriate writer and format
-    fn write_log_entry(&self, log_info: &LogInfo, file_path: &std::path::Path) {
-        match self.config.pattern {
-            Pattern::Json => {
-                // Use JSON writer for JSON pattern
-                if let Err(error) = self.json_writer.write_log_entry(log_info, file_path) {
-                    self.handle_error(error);
-                }
+            // Severe entries must reach the file even when output is buffered
+            if self.config.should_flush_level(entry.level) {
+                self.flush_writers(&log_file_path);
             }
-            _ => {
-                // Use text writer for all other patterns
-                let formatted_message = self.config.pattern.format(log_info);
-                if let Err(error) = self.text_writer.write_message(&formatted_message, file_path) {
-                    self.handle_error(error);
-                }
+        }));
+
+        if let Err(payload) = write_result {
+            // A strict-mode panic is meant for the caller, not the error log
+            if self.config.strict {
+                panic::resume_unwind(payload);
+            }
+            self.handle_error(LoggerError::SerializationFailed {
+                reason: panic_message(payload.as_ref()),
+            });
+        }
+
+        if let Some((level, error_file)) = &self.error_file {
+            if entry.level.should_log(*level) {
+                error_file.write_info(entry, log_info);
             }
         }
     }
 
-    /// Get current timestamp as string
-    fn get_current_timestamp(&self) -> String {
-        use chrono::{Local, DateTime};
-        let now: DateTime<Local> = Local::now();
-        now.format("%Y-%m-%d %H:%M:%S").to_string()
+    /// Static fields followed by the entry's context fields
+    ///
+    /// A context field replaces the static field with the same key.
+    /// Borrows the context when there are no static fields.
+    fn entry_fields<'a>(&self, context: &'a [(String, String)]) -> Cow<'a, [(String, String)]> {
+        if self.config.static_fields.is_empty() {
+            return Cow::Borrowed(context);
+        }
+
+        let mut fields: Vec<(String, String)> = self.config.static_fields
+            .iter()
+            .filter(|(key, _)| !context.iter().any(|(context_key, _)| context_key == key))
+            .cloned()
+            .collect();
+        fields.extend_from_slice(context);
+        Cow::Owned(fields)
     }
 
-    /// Get current thread name or ID
-    fn get_current_thread_name(&self) -> String {
-        thread::current()
-            .name()
-            .unwrap_or("unnamed")
-            .to_string()
+    /// Check the free space of the log directory (with `min_free_bytes`)
+    ///
+    /// Reports the first check of a low period as LowDiskSpace.
+    ///
+    /// # Returns
+    /// false if the entry is skipped (`pause_on_low_disk` and below ERROR)
+    fn has_disk_space_for(&self, level: LogLevel, log_file_path: &Path) -> bool {
+        let Some(monitor) = &self.disk_space else {
+            return true;
+        };
+
+        let directory = log_file_path.parent().unwrap_or(Path::new("."));
+        match monitor.check(directory) {
+            SpaceCheck::Ok => true,
+            SpaceCheck::Low { available, first } => {
+                if first {
+                    self.handle_error(LoggerError::LowDiskSpace {
+                        path: directory.display().to_string(),
+                        available_bytes: available,
+                        min_free_bytes: monitor.min_free_bytes(),
+                    });
+                }
+                !self.config.pause_on_low_disk || level == LogLevel::Error
+            }
+        }
     }
 
-    /// Handle logger internal errors by writing to error log
+    /// Write a log entry to every configured destination
     ///
-    /// This method never panics or returns errors - it's the final fallback
-    fn handle_error(&self, error: LoggerError) {
-        // Write to error log in same directory as main log
-        write_error_to_log(&error, &self.config.file_path);
+    /// Each writer fails independently - an error is routed to the error log
+    /// and the remaining writers still receive the entry.
+    fn write_log_entry(&self, log_info: &LogInfo, file_path: &Path) {
+        let formatted = self.format_line(log_info);
+        self.record_written(file_path, &formatted);
+
+        for writer in &self.writers {
+            if let Err(error) = writer.write_entry(log_info, &formatted, file_path) {
+                self.handle_error(error);
+            }
+        }
     }
-}
 
-/// Thread-safe implementation - Logger can be shared between threads
-unsafe impl Send for Logger {}
-unsafe impl Sync for Logger {}
\ No newline at end of file
+    /// Rotate the file at `path` if it reached the size limit (or, with
+    /// `RotationStrategy::SizeOrDaily`, if `today` is a new day)
+    ///
+    /// The decision uses the cached size estimate; the file is only checked
+    /// on disk the first time a path is seen and once the estimate reaches
+    /// the limit. Writers finish the old file first so a rotated JSON array
+    /// is closed, and all file destinations move to the new file together.
+    fn rotate_if_needed(&self, path: &Path, today: Date) {
+        if let Some(line_rotation) = &self.line_rotation {
+            if line_rotation.needs_rotation(path) {
+                self.flush_writers(path);
+                let backup = line_rotation.first_backup_path(path);
+                match line_rotation.rotate(path) {
+                    RotationResult::Failed(error) => self.handle_error(error),
+                    _ => self.notify_rotated(path, backup.as_deref()),
+                }
+            }
+            return;
+        }
+
+        let mut file_sizes = self.file_sizes.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let file_size = |path: &Path| fs::metadata(path).map_or(0, |metadata| metadata.len());
+
+        let estimate = *file_sizes.entry(path.to_path_buf()).or_insert_with(|| file_size(path));
+        let new_day = self.combined_rotation.as_ref().is_some_and(|combined| combined.time_due(path, today));
+
+        let mut actual = estimate;
+        if !new_day {
+            if estimate < self.config.max_file_size {
+                return;
+            }
+
+            // The estimate may be off (buffered output, files changed elsewhere)
+            actual = file_size(path);
+            if actual < self.config.max_file_size {
+                file_sizes.insert(path.to_path_buf(), actual);
+                return;
+            }
+        }
+
+        self.flush_writers(path);
+        let (backup, result) = match &self.combined_rotation {
+            Some(combined) => (Some(combined.next_backup_path(path, today)), combined.rotate(path, today)),
+            None => (self.rotation.first_backup_path(path), self.rotation.rotate(path)),
+        };
+        match result {
+            RotationResult::Failed(error) => {
+                file_sizes.insert(path.to_path_buf(), actual);
+                drop(file_sizes);
+                self.handle_error(error);
+                // Continue with logging even if rotation failed
+            }
+            _ => {
+                file_sizes.insert(path.to_path_buf(), 0);
+                drop(file_sizes);
+                self.notify_rotated(path, backup.as_deref());
+            }
+        }
+    }
+
+    /// Record a completed rotation in the index and pass it to `on_rotate`
+    /// (a panicking callback is ignored)
+    fn notify_rotated(&self, path: &Path, backup: Option<&Path>) {
+        if let Some(rotation_index) = &self.rotation_index {
+            // Only dated backups (SizeOrDaily) keep their name on later rotations
+            let shifted = self.combined_rotation.is_none();
+            if let Err(error) = rotation_index.record_rotation(backup, shifted, self.clock.now()) {
+                self.handle_error(error);
+            }
+        }
+        if let (Some(on_rotate), Some(backup)) = (&self.config.on_rotate, backup) {
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| on_rotate(path, backup)));
+        }
+    }
+
+    /// Add one formatted entry to the size estimate (or line count) of `path`
+    ///
+    /// Counts the line plus two bytes (newline, JSON array separator) for
+    /// each file destination, so the estimate errs on the large side.
+    fn record_written(&self, path: &Path, formatted: &str) {
+        if self.file_writer_count == 0 {
+            return;
+        }
+        if let Some(line_rotation) = &self.line_rotation {
+            let lines = formatted.matches('\n').count() as u64 + 1;
+            line_rotation.record_lines(lines * self.file_writer_count);
+            return;
+        }
+        let mut file_sizes = self.file_sizes.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(size) = file_sizes.get_mut(path) {
+            *size += (formatted.len() as u64 + 2) * self.file_writer_count;
+        }
+    }
+
+    /// Finish pending output of every writer (e.g. close a JSON array)
+    pub(crate) fn flush(&self) {
+        let _lock = self.write_mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let _writing = WritingGuard::enter();
+        self.flush_repeats();
+        let daily_path = self.daily_path.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+        match daily_path {
+            Some((_, path)) => self.flush_writers(&path),
+            None => self.flush_writers(&self.config.get_log_file_path()),
+        }
+        if let Some((_, error_file)) = &self.error_file {
+            error_file.flush();
+        }
+    }
+
+    /// Path of the file an entry logged now is written to
+    ///
+    /// Like `log_file_path_at`, but leaves the cached daily path alone.
+    fn current_log_path(&self) -> PathBuf {
+        if self.config.daily_files {
+            self.config.get_daily_log_file_path(self.entry_date(self.clock.now()))
+        } else {
+            self.config.get_log_file_path()
+        }
+    }
+
+    /// Bytes held in the buffers of all writers
+    fn buffer_used(&self) -> usize {
+        let error_file = self.error_file.as_ref().map_or(0, |(_, error_file)| error_file.buffer_used());
+        self.writers.iter().map(|writer| writer.buffered_bytes()).sum::<usize>() + error_file
+    }
+
+    /// Flush each writer, routing failures to the error log
+    fn flush_writers(&self, file_path: &Path) {
+        for writer in &self.writers {
+            if let Err(error) = writer.flush(file_path) {
+                self.handle_error(error);
+            }
+        }
+    }
+
+    /// Format a log entry as a single line (JSON object for the JSON pattern)
+    fn format_line(&self, log_info: &LogInfo) -> String {
+        if let Some(formatter) = &self.config.custom_formatter {
+            return formatter(log_info);
+        }
+        match self.config.pattern {
+            Pattern::Json | Pattern::OtelJson => self.json_writer.format_as_json(log_info),
+            _ => self.config.pattern.format(log_info),
+        }
+    }
+
+    /// Date of an entry written at `now` (UTC date with `use_utc`)
+    fn entry_date(&self, now: Timestamp) -> Date {
+        time::date_of(now, self.config.use_utc)
+    }
+
+    /// Get the log file path for an entry written at `now`
+    ///
+    /// Without `daily_files` this is always the configured file. With daily
+    /// files the dated path is cached until the date changes; the previous
+    /// day's file is flushed then (e.g. to close its JSON array).
+    fn log_file_path_at(&self, now: Timestamp) -> PathBuf {
+        if !self.config.daily_files {
+            return self.config.get_log_file_path();
+        }
+
+        let today = self.entry_date(now);
+
+        let mut cached = self.daily_path.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((date, path)) = cached.as_ref() {
+            if *date == today {
+                return path.clone();
+            }
+            self.flush_writers(path);
+        }
+
+        let path = self.config.get_daily_log_file_path(today);
+        *cached = Some((today, path.clone()));
+        path
+    }
+
+    /// Format the timestamp of an entry (local time or UTC per configuration)
+    ///
+    /// JSON output always uses RFC 3339 so log processors can parse it;
+    /// text patterns keep the human-readable format. Formatted once per
+    /// second and reused for the other entries of that second.
+    fn format_entry_timestamp(&self, now: Timestamp) -> String {
+        let use_utc = self.config.use_utc;
+        match self.config.pattern {
+            Pattern::Json | Pattern::OtelJson => self.timestamp_cache.get(now, |now| format_rfc3339(now, use_utc)),
+            // Milliseconds: not cached per second
+            Pattern::Gelf => format_gelf_timestamp(now),
+            _ => self.timestamp_cache.get(now, |now| format_timestamp(now, use_utc)),
+        }
+    }
+
+    /// Get the current time from the configured clock
+    pub(crate) fn now(&self) -> Timestamp {
+        self.clock.now()
+    }
+
+    /// Handle logger internal errors by writing to error log
+    ///
+    /// The `on_error` callback, if configured, is called before the write
+    /// (for every error). Identical consecutive errors are collapsed in the
+    /// error log by the rate limiter.
+    /// This method never panics or returns errors - it's the final fallback.
+    /// Only in strict mode, a failure to create or write the log file panics.
+    pub(crate) fn handle_error(&self, error: LoggerError) {
+        if self.config.strict && error.is_write_failure() && !thread::panicking() {
+            panic!("FreedomLogger strict mode: {}", error);
+        }
+
+        // Notify the application first - a panicking callback is ignored
+        if let Some(on_error) = &self.config.on_error {
+            let _ = panic::catch_unwind(AssertUnwindSafe(|| on_error(&error)));
+        }
+
+        // Write to the error log (by default in the same directory as the main log)
+        for (error, repeated) in self.error_limiter.record(&error) {
+            write_repeated_error(&error, repeated, &self.config);
+        }
+    }
+}
+
+impl Drop for Logger {
+    /// A dropped logger finishes its output (e.g. closes a JSON array)
+    ///
+    /// In async mode the queued entries are written before the worker stops.
+    fn drop(&mut self) {
+        if let Some(flush_thread) = &self.flush_thread {
+            flush_thread.stop();
+        }
+        match &self.async_queue {
+            Some(queue) => queue.stop(),
+            None => self.core.flush(),
+        }
+    }
+}
+
+/// Create the writer of the file destination (gzip, JSON, HTML or text)
+///
+/// Lines of a custom formatter always go to a text file.
+fn file_writer(config: &LoggerConfig, buffer_capacity: Option<usize>) -> Box<dyn LogWriter> {
+    let pattern = match config.custom_formatter {
+        Some(_) => &Pattern::Basic,
+        None => &config.pattern,
+    };
+    match pattern {
+        #[cfg(feature = "compression")]
+        _ if config.compress_live => Box::new(
+            crate::core::writers::GzipWriter::new()
+                .with_line_ending(config.line_ending)
+                .with_file_mode(config.file_mode),
+        ),
+        Pattern::Json | Pattern::OtelJson => Box::new(
+            JsonWriter::new()
+                .with_otel(config.pattern == Pattern::OtelJson)
+                .with_pretty(config.json_pretty)
+                .with_array(config.json_array)
+                .with_fields(config.json_fields.clone())
+                .with_severity_number(config.json_severity_number)
+                .with_line_ending(config.line_ending)
+                .with_file_mode(config.file_mode)
+                .with_buffer(buffer_capacity)
+                .with_utf8_bom(config.write_utf8_bom),
+        ),
+        Pattern::Html => Box::new(
+            HtmlWriter::new()
+                .with_line_ending(config.line_ending)
+                .with_file_mode(config.file_mode)
+                .with_buffer(buffer_capacity)
+                .with_utf8_bom(config.write_utf8_bom),
+        ),
+        _ => Box::new(
+            TextWriter::new()
+                .with_line_ending(config.line_ending)
+                .with_file_mode(config.file_mode)
+                .with_buffer(buffer_capacity)
+                .with_utf8_bom(config.write_utf8_bom),
+        ),
+    }
+}
+
+/// Send the File, Console and Stderr destinations to the browser console
+///
+/// The browser has no file system or stdout; the other destinations are
+/// kept. There is no separate error file either.
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+fn use_web_console(config: &mut LoggerConfig) {
+    let count = config.destinations.len();
+    config.destinations.retain(|destination| {
+        !matches!(destination, Destination::File | Destination::Console | Destination::Stderr)
+    });
+    if config.destinations.len() < count && !config.destinations.contains(&Destination::WebConsole) {
+        config.destinations.push(Destination::WebConsole);
+    }
+    config.separate_error_file = None;
+}
+
+/// Configuration of the separate error file (`separate_error_file`)
+///
+/// The main configuration with file name "<file_name>.error" and only the
+/// File destination; the temp-directory fallback already happened for the main log.
+fn error_file_config(config: &LoggerConfig) -> LoggerConfig {
+    let mut error_config = config.clone();
+    error_config.file_name = format!("{}.error", config.file_name);
+    error_config.destinations = vec![Destination::File];
+    error_config.custom_writer = None;
+    error_config.separate_error_file = None;
+    error_config.fallback_to_temp = false;
+    error_config
+}
+
+/// Wrap a writer in a `RetryWriter` with `max_open_retries`
+fn with_retries(config: &LoggerConfig, writer: Box<dyn LogWriter>) -> Box<dyn LogWriter> {
+    match config.max_open_retries {
+        0 => writer,
+        retries => Box::new(RetryWriter::new(writer, retries)),
+    }
+}
+
+/// Read the host name of this machine
+///
+/// Tries the HOSTNAME / COMPUTERNAME environment variables, then the Linux
+/// and Unix hostname files, so no extra dependency is needed.
+///
+/// # Returns
+/// The host name, or "unknown" if none of the sources is available
+fn read_hostname() -> String {
+    ["HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .filter_map(|variable| std::env::var(variable).ok())
+        .chain(
+            ["/proc/sys/kernel/hostname", "/etc/hostname"]
+                .iter()
+                .filter_map(|path| std::fs::read_to_string(path).ok()),
+        )
+        .map(|hostname| hostname.trim().to_string())
+        .find(|hostname| !hostname.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Get current thread name or ID
+pub(crate) fn current_thread_name() -> String {
+    thread::current()
+        .name()
+        .unwrap_or("unnamed")
+        .to_string()
+}
+
+/// Next ID handed out by `current_thread_id`
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// Numeric ID of this thread, assigned on its first log call
+    static THREAD_ID: u64 = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Get the numeric ID of the current thread
+///
+/// `ThreadId::as_u64` is not stable, so the logger numbers threads itself:
+/// each thread gets the next number (starting at 1) the first time it
+/// asks, and keeps it. Unique for the lifetime of the process; 0 while the
+/// thread's locals are being destroyed.
+pub(crate) fn current_thread_id() -> u64 {
+    THREAD_ID.try_with(|id| *id).unwrap_or(0)
+}
+
+
+/// Extract the message from a caught panic payload
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        format!("panic: {}", message)
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        format!("panic: {}", message)
+    } else {
+        "panic with non-string payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::LineEnding;
+    use crate::core::time::timestamp_from_utc;
+    use tempfile::tempdir;
+    use std::fs;
+
+    #[test]
+    fn test_context_fields_appear_until_cleared() {
+        use crate::core::context::{push_context, clear_context};
+
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        let logger = Logger::new(config);
+
+        push_context("request_id", "req-42");
+        logger.info("With context");
+        clear_context();
+        logger.info("Without context");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert!(lines[0].ends_with("INFO: With context request_id=req-42"));
+        assert!(lines[1].ends_with("INFO: Without context"));
+    }
+
+    #[test]
+    fn test_context_fields_in_json() {
+        use crate::core::context::{push_context, clear_context};
+
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Json, temp_dir.path().to_path_buf(), "app".to_string());
+        let logger = Logger::new(config);
+
+        push_context("user", "alice");
+        logger.info("JSON context");
+        clear_context();
+
+        let content = fs::read_to_string(temp_dir.path().join("app.json")).unwrap();
+        assert!(content.contains("\"user\":\"alice\""));
+    }
+
+    #[test]
+    fn test_custom_extension_for_file_and_backups() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::with_rotation(
+            Pattern::Basic,
+            temp_dir.path().to_path_buf(),
+            "app".to_string(),
+            LogLevel::Trace,
+            10,
+            2,
+        );
+        config.file_extension = Some("txt".to_string());
+        let logger = Logger::new(config);
+
+        logger.info("First line goes over the size limit");
+        logger.info("Second line triggers rotation");
+
+        assert!(temp_dir.path().join("app.txt").exists());
+        assert!(temp_dir.path().join("app.1.txt").exists());
+        assert!(!temp_dir.path().join("app.log").exists());
+        assert!(!temp_dir.path().join("app.1.log").exists());
+    }
+
+    #[test]
+    fn test_memory_destination_captures_lines_without_file() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.destinations = vec![Destination::Memory(3)];
+        let logger = Logger::new(config);
+
+        for i in 1..=5 {
+            logger.info(&format!("Memory line {}", i));
+        }
+
+        let lines = logger.captured_lines();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("INFO: Memory line 3"));
+        assert!(lines[2].ends_with("INFO: Memory line 5"));
+        assert!(!temp_dir.path().join("app.log").exists());
+    }
+
+    #[test]
+    fn test_entry_fans_out_to_file_and_memory() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.destinations = vec![Destination::File, Destination::Memory(10)];
+        let logger = Logger::new(config);
+
+        logger.warning("Fan-out message");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert!(content.contains("WARNING: Fan-out message"));
+
+        let captured = logger.captured_lines();
+        assert_eq!(captured.len(), 1);
+        assert_eq!(format!("{}\n", captured[0]), content);
+    }
+
+    #[test]
+    fn test_custom_formatter_replaces_pattern() {
+        for (pattern, extension) in [(Pattern::Detailed, "log"), (Pattern::Json, "json")] {
+            let temp_dir = tempdir().unwrap();
+            let mut config = LoggerConfig::basic(pattern, temp_dir.path().to_path_buf(), "app".to_string());
+            config.destinations = vec![Destination::File, Destination::Memory(5)];
+            config.custom_formatter = Some(Arc::new(|info: &LogInfo| {
+                format!("{} {}", info.level.as_str(), info.message).to_uppercase()
+            }));
+            let logger = Logger::new(config);
+
+            logger.warning("Disk almost full");
+            logger.info("retrying in 5s");
+
+            let content = fs::read_to_string(temp_dir.path().join(format!("app.{}", extension))).unwrap();
+            assert_eq!(content, "WARNING DISK ALMOST FULL\nINFO RETRYING IN 5S\n");
+            assert_eq!(logger.captured_lines(), vec!["WARNING DISK ALMOST FULL", "INFO RETRYING IN 5S"]);
+        }
+    }
+
+    #[test]
+    fn test_custom_writer_receives_entries() {
+        use crate::error::LoggerResult;
+
+        /// Records every formatted line it receives
+        struct RecordingWriter {
+            calls: Mutex<Vec<String>>,
+        }
+
+        impl LogWriter for RecordingWriter {
+            fn write_entry(&self, info: &LogInfo, formatted: &str, _path: &Path) -> LoggerResult<()> {
+                self.calls.lock().unwrap().push(format!("{}|{}", info.level.as_str(), formatted));
+                Ok(())
+            }
+        }
+
+        let temp_dir = tempdir().unwrap();
+        let recorder = Arc::new(RecordingWriter { calls: Mutex::new(Vec::new()) });
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.destinations = Vec::new();
+        config.custom_writer = Some(recorder.clone());
+        let logger = Logger::new(config);
+
+        logger.info("First custom");
+        logger.error("Second custom");
+
+        let calls = recorder.calls.lock().unwrap();
+        assert_eq!(calls.len(), 2);
+        assert!(calls[0].starts_with("INFO|") && calls[0].ends_with("INFO: First custom"));
+        assert!(calls[1].starts_with("ERROR|") && calls[1].ends_with("ERROR: Second custom"));
+    }
+
+    #[test]
+    fn test_rotation_failure_written_as_error_json() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::with_rotation(
+            Pattern::Basic,
+            temp_dir.path().to_path_buf(),
+            "app".to_string(),
+            LogLevel::Trace,
+            10,
+            1,
+        );
+        config.internal_error_json = true;
+
+        // A non-empty directory in the backup slot makes rotation fail
+        let blocked_backup = temp_dir.path().join("app.1.log");
+        fs::create_dir(&blocked_backup).unwrap();
+        fs::write(blocked_backup.join("keep"), "x").unwrap();
+        fs::write(temp_dir.path().join("app.log"), "over the size limit").unwrap();
+
+        let logger = Logger::new(config);
+        logger.info("Triggers a failing rotation");
+
+        let errors = fs::read_to_string(temp_dir.path().join("logger_errors.json")).unwrap();
+        let line = errors.lines().next().unwrap();
+        assert!(line.contains("\"error_type\":\"RotationFailed\""));
+        assert!(line.contains("\"backup_file\":"));
+        assert!(line.contains("\"reason\":\"Failed to delete oldest backup\""));
+        assert!(!temp_dir.path().join("logger_errors.log").exists());
+    }
+
+    #[test]
+    fn test_panicking_writer_does_not_break_logging() {
+        use crate::error::LoggerResult;
+
+        /// Panics on messages containing "boom"
+        struct PanickingWriter;
+
+        impl LogWriter for PanickingWriter {
+            fn write_entry(&self, info: &LogInfo, _formatted: &str, _path: &Path) -> LoggerResult<()> {
+                if info.message.contains("boom") {
+                    panic!("writer exploded");
+                }
+                Ok(())
+            }
+        }
+
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.destinations = vec![Destination::Memory(10)];
+        config.custom_writer = Some(Arc::new(PanickingWriter));
+        let logger = Logger::new(config);
+
+        logger.info("boom");
+        logger.info("After the panic");
+
+        let captured = logger.captured_lines();
+        assert_eq!(captured.len(), 2);
+        assert!(captured[1].ends_with("INFO: After the panic"));
+        assert!(!logger.core.write_mutex.is_poisoned());
+
+        let errors = fs::read_to_string(temp_dir.path().join("logger_errors.log")).unwrap();
+        assert!(errors.contains("Failed to format log entry: panic: writer exploded"));
+    }
+
+    #[test]
+    fn test_thread_ids_are_stable_and_distinct() {
+        let main_id = current_thread_id();
+        assert_ne!(main_id, 0);
+        assert_eq!(current_thread_id(), main_id);
+
+        let other_id = thread::spawn(current_thread_id).join().unwrap();
+        assert_ne!(other_id, 0);
+        assert_ne!(other_id, main_id);
+    }
+
+    #[test]
+    fn test_poisoned_mutex_is_recovered() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        let logger = Arc::new(Logger::new(config));
+
+        let poisoner = Arc::clone(&logger);
+        let result = thread::spawn(move || {
+            let _lock = poisoner.core.write_mutex.lock().unwrap();
+            panic!("poison the write mutex");
+        }).join();
+        assert!(result.is_err());
+        assert!(logger.core.write_mutex.is_poisoned());
+
+        logger.info("First after poisoning");
+        logger.info("Second after poisoning");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert!(content.contains("INFO: First after poisoning"));
+        assert!(content.contains("INFO: Second after poisoning"));
+        assert!(!logger.core.write_mutex.is_poisoned());
+
+        let errors = fs::read_to_string(temp_dir.path().join("logger_errors.log")).unwrap();
+        assert_eq!(errors.lines().filter(|line| line.contains("Lock poisoned: write mutex poisoned")).count(), 1);
+        assert!(!errors.contains("Log rotation failed"));
+    }
+
+    #[test]
+    fn test_truncate_on_start_removes_old_content() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("app.log");
+        fs::write(&log_path, "Old line from previous run\n").unwrap();
+
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.truncate_on_start = true;
+        let logger = Logger::new(config);
+
+        logger.info("Fresh run");
+        logger.info("Still appending");
+
+        let content = fs::read_to_string(&log_path).unwrap();
+        assert!(!content.contains("Old line from previous run"));
+        assert_eq!(content.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_default_keeps_existing_content() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("app.log");
+        fs::write(&log_path, "Old line from previous run\n").unwrap();
+
+        let config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        Logger::new(config).info("Next run");
+
+        let content = fs::read_to_string(&log_path).unwrap();
+        assert!(content.starts_with("Old line from previous run\n"));
+        assert_eq!(content.lines().count(), 2);
+    }
+
+    /// Extract a string field from a single-line JSON object
+    fn json_string_field<'a>(line: &'a str, field: &str) -> &'a str {
+        let key = format!("\"{}\":\"", field);
+        let start = line.find(&key).expect("field missing") + key.len();
+        let end = start + line[start..].find('"').unwrap();
+        &line[start..end]
+    }
+
+    #[test]
+    fn test_json_timestamp_is_rfc3339() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Json, temp_dir.path().to_path_buf(), "app".to_string());
+        let logger = Logger::new(config);
+
+        logger.info("JSON timestamp test");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.json")).unwrap();
+        let timestamp = json_string_field(content.lines().next().unwrap(), "timestamp");
+
+        #[cfg(feature = "chrono")]
+        assert!(chrono::DateTime::parse_from_rfc3339(timestamp).is_ok(), "not RFC 3339: {}", timestamp);
+        assert_eq!(&timestamp[10..11], "T");
+        let designator = &timestamp[19..];
+        assert!(designator == "Z" || designator.starts_with('+') || designator.starts_with('-'));
+    }
+
+    #[test]
+    fn test_text_timestamp_stays_human_readable() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        let logger = Logger::new(config);
+
+        logger.info("Text timestamp test");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert_eq!(&content[11..12], " ");
+        assert!(content.contains("] INFO: Text timestamp test"));
+    }
+
+    #[test]
+    fn test_json_array_closed_on_flush() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Json, temp_dir.path().to_path_buf(), "app".to_string());
+        config.json_array = true;
+        let logger = Logger::new(config);
+
+        logger.info("First");
+        logger.warning("Second");
+        logger.error("Third");
+        logger.flush();
+
+        let content = fs::read_to_string(temp_dir.path().join("app.json")).unwrap();
+        assert!(content.starts_with("[\n{"));
+        assert!(content.ends_with("}\n]\n"));
+        assert_eq!(content.matches("\n{").count(), 3);
+        assert_eq!(content.matches("},\n{").count(), 2);
+    }
+
+    #[test]
+    fn test_module_path_in_json() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Json, temp_dir.path().to_path_buf(), "app".to_string());
+        let logger = Logger::new(config);
+
+        logger.log_from(LogLevel::Info, "From macro", module_path!(), file!(), line!());
+        logger.info("From function");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.json")).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(json_string_field(lines[0], "module"), "FreedomLogger::core::logger::tests");
+        assert!(lines[1].contains("\"module\":null"));
+    }
+
+    #[test]
+    fn test_fixed_clock_pins_timestamp() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.use_utc = true;
+        let frozen = timestamp_from_utc(2025, 9, 6, 15, 30, 45).unwrap();
+        let logger = Logger::with_clock(config, Arc::new(move || frozen));
+
+        logger.info("Frozen in time");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert_eq!(content, "[2025-09-06 15:30:45] INFO: Frozen in time\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_file_mode_applies_to_log_and_backups() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::with_rotation(
+            Pattern::Basic,
+            temp_dir.path().to_path_buf(),
+            "private".to_string(),
+            LogLevel::Info,
+            50,
+            2,
+        );
+        config.file_mode = Some(0o600);
+        let logger = Logger::new(config);
+
+        logger.info("Sensitive entry that fills the first file");
+        logger.info("Sensitive entry after rotation");
+
+        for name in ["private.log", "private.1.log"] {
+            let mode = fs::metadata(temp_dir.path().join(name)).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600, "{} has mode {:o}", name, mode & 0o777);
+        }
+    }
+
+    #[test]
+    fn test_daily_files_follow_the_clock_date() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.use_utc = true;
+        config.daily_files = true;
+
+        let day_one = timestamp_from_utc(2025, 9, 6, 23, 59, 59).unwrap();
+        let now = Arc::new(Mutex::new(day_one));
+        let clock_now = Arc::clone(&now);
+        let logger = Logger::with_clock(config, Arc::new(move || *clock_now.lock().unwrap()));
+
+        logger.info("Late on day one");
+        *now.lock().unwrap() = day_one + std::time::Duration::from_secs(2);
+        logger.info("Early on day two");
+
+        let day_one_log = fs::read_to_string(temp_dir.path().join("app-2025-09-06.log")).unwrap();
+        let day_two_log = fs::read_to_string(temp_dir.path().join("app-2025-09-07.log")).unwrap();
+        assert_eq!(day_one_log, "[2025-09-06 23:59:59] INFO: Late on day one\n");
+        assert_eq!(day_two_log, "[2025-09-07 00:00:01] INFO: Early on day two\n");
+        assert!(!temp_dir.path().join("app.log").exists());
+    }
+
+    #[test]
+    fn test_rotation_index_lists_backups_with_time_spans() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.use_utc = true;
+        config.max_file_size = 60;
+        config.rotation_index = true;
+
+        let start = timestamp_from_utc(2025, 9, 6, 12, 0, 0).unwrap();
+        let now = Arc::new(Mutex::new(start));
+        let clock_now = Arc::clone(&now);
+        let logger = Logger::with_clock(config, Arc::new(move || *clock_now.lock().unwrap()));
+
+        // Two entries fill a file; the third and fifth rotate
+        for second in 1..=5 {
+            *now.lock().unwrap() = start + std::time::Duration::from_secs(second);
+            logger.info(&format!("Entry {}", second));
+        }
+
+        let index = fs::read_to_string(temp_dir.path().join("app.index.json")).unwrap();
+        let records: Vec<serde_json::Value> = index.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(records.len(), 2);
+
+        // Oldest first; the first backup has moved on to app.2.log
+        let expected = [("app.2.log", "12:00:01", "12:00:02"), ("app.1.log", "12:00:03", "12:00:04")];
+        for (record, (file_name, start_time, end_time)) in records.iter().zip(expected) {
+            assert_eq!(record["backup_file"], file_name);
+            assert_eq!(record["start_time"], format!("2025-09-06T{}Z", start_time));
+            assert_eq!(record["end_time"], format!("2025-09-06T{}Z", end_time));
+            let size = fs::metadata(temp_dir.path().join(file_name)).unwrap().len();
+            assert_eq!(record["size"], size);
+        }
+    }
+
+    #[test]
+    fn test_current_log_path_is_the_written_file() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Json, temp_dir.path().to_path_buf(), "app".to_string());
+        config.use_utc = true;
+        config.daily_files = true;
+
+        let day_one = timestamp_from_utc(2025, 9, 6, 23, 59, 59).unwrap();
+        let now = Arc::new(Mutex::new(day_one));
+        let clock_now = Arc::clone(&now);
+        let logger = Logger::with_clock(config, Arc::new(move || *clock_now.lock().unwrap()));
+
+        logger.info("Day one");
+        assert_eq!(logger.current_log_path(), temp_dir.path().join("app-2025-09-06.json"));
+        assert!(fs::read_to_string(logger.current_log_path()).unwrap().contains("Day one"));
+
+        *now.lock().unwrap() = day_one + std::time::Duration::from_secs(2);
+        assert_eq!(logger.current_log_path(), temp_dir.path().join("app-2025-09-07.json"));
+        logger.info("Day two");
+        assert!(fs::read_to_string(logger.current_log_path()).unwrap().contains("Day two"));
+    }
+
+    #[test]
+    fn test_fallback_to_temp_when_directory_unwritable() {
+        let temp_dir = tempdir().unwrap();
+        // A regular file as parent can't hold a directory (unlike a read-only
+        // directory, this also fails when the tests run as root)
+        let blocker = temp_dir.path().join("not_a_directory");
+        fs::write(&blocker, "").unwrap();
+
+        let file_name = format!("freedom_logger_fallback_{}", std::process::id());
+        let mut config = LoggerConfig::basic(Pattern::Basic, blocker.join("logs"), file_name.clone());
+        config.fallback_to_temp = true;
+        let logger = Logger::new(config);
+
+        logger.info("Saved by the fallback");
+
+        let temp_log = std::env::temp_dir().join(format!("{}.log", file_name));
+        let content = fs::read_to_string(&temp_log).unwrap();
+        fs::remove_file(&temp_log).unwrap();
+        assert!(content.contains("INFO: Saved by the fallback"));
+        assert!(!blocker.join("logs").exists());
+    }
+
+    #[test]
+    fn test_buffered_output_flushed_on_error() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("app.log");
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.buffered = true;
+        let logger = Logger::new(config);
+
+        logger.debug("Buffered debug");
+        assert!(!log_path.exists());
+
+        logger.error("Severe error");
+        let content = fs::read_to_string(&log_path).unwrap();
+        assert!(content.contains("DEBUG: Buffered debug"));
+        assert!(content.contains("ERROR: Severe error"));
+
+        logger.debug("Another buffered debug");
+        assert!(!fs::read_to_string(&log_path).unwrap().contains("Another buffered debug"));
+
+        logger.flush();
+        assert!(fs::read_to_string(&log_path).unwrap().contains("Another buffered debug"));
+    }
+
+    #[test]
+    fn test_on_error_callback_fires_on_rotation_failure() {
+        use std::sync::atomic::AtomicUsize;
+
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::with_rotation(
+            Pattern::Basic,
+            temp_dir.path().to_path_buf(),
+            "app".to_string(),
+            LogLevel::Trace,
+            10,
+            1,
+        );
+        let failures = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&failures);
+        config.on_error = Some(Arc::new(move |error: &LoggerError| {
+            if matches!(error, LoggerError::RotationFailed { .. }) {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+            panic!("a bad callback must not break logging");
+        }));
+
+        // A non-empty directory in the backup slot makes rotation fail
+        let blocked_backup = temp_dir.path().join("app.1.log");
+        fs::create_dir(&blocked_backup).unwrap();
+        fs::write(blocked_backup.join("keep"), "x").unwrap();
+        fs::write(temp_dir.path().join("app.log"), "over the size limit").unwrap();
+
+        let logger = Logger::new(config);
+        logger.info("Triggers a failing rotation");
+
+        assert_eq!(failures.load(Ordering::SeqCst), 1);
+        assert!(fs::read_to_string(temp_dir.path().join("app.log")).unwrap().contains("INFO: Triggers a failing rotation"));
+        assert!(temp_dir.path().join("logger_errors.log").exists());
+    }
+
+    #[test]
+    fn test_level_off_writes_nothing() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::with_level(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string(), LogLevel::Off);
+        let logger = Logger::new(config);
+
+        logger.error("Muted error");
+        logger.warning("Muted warning");
+        logger.info("Muted info");
+        logger.debug("Muted debug");
+        logger.trace("Muted trace");
+        logger.log_at(LogLevel::Error, "Muted log_at");
+
+        assert!(!temp_dir.path().join("app.log").exists());
+    }
+
+    #[test]
+    fn test_set_level_off_mutes_at_runtime() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        let logger = Logger::new(config);
+        assert_eq!(logger.current_level(), None);
+
+        logger.info("Before muting");
+        logger.set_level(LogLevel::Off);
+        logger.error("While muted");
+        logger.set_level(LogLevel::Info);
+        logger.info("After unmuting");
+        logger.debug("Filtered debug");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert!(!content.contains("While muted"));
+        assert!(!content.contains("Filtered debug"));
+        assert_eq!(content.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_max_message_len_truncates_message() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Json, temp_dir.path().to_path_buf(), "app".to_string());
+        config.max_message_len = Some(6);
+        let logger = Logger::new(config);
+
+        logger.info("ab🦀cdefgh");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.json")).unwrap();
+        assert_eq!(json_string_field(&content, "message"), "ab🦀…[truncated 6 bytes]");
+    }
+
+    #[cfg(feature = "redaction")]
+    #[test]
+    fn test_redactors_mask_email_in_text_and_json() {
+        use crate::format::email_redactor;
+
+        for pattern in [Pattern::Basic, Pattern::Json] {
+            let temp_dir = tempdir().unwrap();
+            let mut config = LoggerConfig::basic(pattern, temp_dir.path().to_path_buf(), "app".to_string());
+            config.redactors = vec![email_redactor("***")];
+            let log_path = config.get_log_file_path();
+            let logger = Logger::new(config);
+
+            logger.info("Password reset for alice@example.com");
+
+            let content = fs::read_to_string(log_path).unwrap();
+            assert!(content.contains("Password reset for ***"));
+            assert!(!content.contains("alice@example.com"));
+        }
+    }
+
+    #[test]
+    fn test_unnamed_threads_have_distinct_ids() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Extended, temp_dir.path().to_path_buf(), "app".to_string());
+        let logger = Arc::new(Logger::new(config));
+
+        let handles: Vec<_> = (0..2)
+            .map(|i| {
+                let logger = Arc::clone(&logger);
+                thread::spawn(move || logger.info(&format!("From unnamed thread {}", i)))
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        let thread_tags: Vec<&str> = content
+            .lines()
+            .map(|line| {
+                let start = line.find("[unnamed#").expect("thread tag missing");
+                &line[start..start + line[start..].find(']').unwrap()]
+            })
+            .collect();
+        assert_eq!(thread_tags.len(), 2);
+        assert_ne!(thread_tags[0], thread_tags[1]);
+    }
+
+    #[test]
+    fn test_pid_and_hostname_in_json() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Json, temp_dir.path().to_path_buf(), "app".to_string());
+        config.include_pid = true;
+        config.include_hostname = true;
+        let logger = Logger::new(config);
+
+        logger.info("With instance fields");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.json")).unwrap();
+        assert!(content.contains(&format!("\"pid\":{},", std::process::id())));
+        assert_eq!(json_string_field(&content, "hostname"), read_hostname());
+    }
+
+    #[test]
+    fn test_pid_omitted_by_default() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Json, temp_dir.path().to_path_buf(), "app".to_string());
+        let logger = Logger::new(config);
+
+        logger.info("Without instance fields");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.json")).unwrap();
+        assert!(!content.contains("\"pid\""));
+        assert!(!content.contains("\"hostname\""));
+        assert!(!content.contains("\"seq\""));
+    }
+
+    #[test]
+    fn test_repeated_rotation_failures_are_collapsed() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::with_rotation(
+            Pattern::Basic,
+            temp_dir.path().to_path_buf(),
+            "app".to_string(),
+            LogLevel::Trace,
+            10,
+            1,
+        );
+
+        // A non-empty directory in the backup slot makes every rotation fail
+        let blocked_backup = temp_dir.path().join("app.1.log");
+        fs::create_dir(&blocked_backup).unwrap();
+        fs::write(blocked_backup.join("keep"), "x").unwrap();
+
+        let logger = Logger::new(config);
+        for index in 0..100 {
+            logger.info(&format!("Rotation attempt {}", index));
+        }
+
+        let errors = fs::read_to_string(temp_dir.path().join("logger_errors.log")).unwrap();
+        assert!(errors.lines().count() < 10);
+        assert!(errors.contains("Log rotation failed"));
+    }
+
+    #[test]
+    fn test_json_fields_restrict_output() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Json, temp_dir.path().to_path_buf(), "app".to_string());
+        config.json_fields = Some(vec!["timestamp".to_string(), "level".to_string(), "message".to_string()]);
+        let logger = Logger::new(config);
+
+        logger.info("Only three fields");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.json")).unwrap();
+        assert!(content.starts_with("{\"timestamp\":"));
+        assert!(content.contains(",\"level\":\"INFO\",\"message\":\"Only three fields\"}"));
+        assert!(!content.contains("\"file\""));
+        assert!(!content.contains("\"thread\""));
+    }
+
+    #[test]
+    fn test_strict_mode_panics_on_unwritable_directory() {
+        let temp_dir = tempdir().unwrap();
+        // A regular file as the log directory can't be written, even as root
+        let blocker = temp_dir.path().join("not_a_directory");
+        fs::write(&blocker, "x").unwrap();
+
+        let mut config = LoggerConfig::basic(Pattern::Basic, blocker, "app".to_string());
+        config.strict = true;
+        let logger = Logger::new(config);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| logger.info("Strict write")));
+        let message = panic_message(result.unwrap_err().as_ref());
+        assert!(message.contains("FreedomLogger strict mode: Failed to create log file"));
+        assert!(message.contains("not_a_directory"));
+    }
+
+    #[test]
+    fn test_file_destinations_rotate_on_same_write() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::with_rotation(
+            Pattern::Basic,
+            temp_dir.path().to_path_buf(),
+            "app".to_string(),
+            LogLevel::Trace,
+            10,
+            2,
+        );
+        config.destinations = vec![Destination::File, Destination::File];
+        let logger = Logger::new(config);
+
+        logger.info("First entry");
+        logger.info("Second entry triggers rotation");
+        logger.info("Third entry triggers rotation");
+
+        // Both destinations moved to each new file together
+        let newest_backup = fs::read_to_string(temp_dir.path().join("app.1.log")).unwrap();
+        let current = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert_eq!(newest_backup.matches("INFO: Second entry").count(), 2);
+        assert_eq!(newest_backup.lines().count(), 2);
+        assert_eq!(current.matches("INFO: Third entry").count(), 2);
+        assert_eq!(current.lines().count(), 2);
+        assert!(!temp_dir.path().join("app.3.log").exists());
+    }
+
+    #[test]
+    fn test_loggers_from_cloned_config_have_independent_levels() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::with_level(Pattern::Basic, temp_dir.path().to_path_buf(), "first".to_string(), LogLevel::Info);
+        let mut second_config = config.clone();
+        second_config.file_name = "second".to_string();
+
+        let first = Logger::new(config);
+        let second = Logger::new(second_config);
+        first.set_level(LogLevel::Error);
+
+        assert_eq!(first.current_level(), Some(LogLevel::Error));
+        assert_eq!(second.current_level(), Some(LogLevel::Info));
+        first.info("Muted");
+        second.info("Still written");
+
+        assert!(!temp_dir.path().join("first.log").exists());
+        let content = fs::read_to_string(temp_dir.path().join("second.log")).unwrap();
+        assert!(content.contains("INFO: Still written"));
+    }
+
+    #[test]
+    fn test_current_config_reflects_init_and_set_level() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::with_rotation(
+            Pattern::Detailed,
+            temp_dir.path().to_path_buf(),
+            "app".to_string(),
+            LogLevel::Info,
+            2048,
+            3,
+        );
+        config.destinations = vec![Destination::File, Destination::Memory(5)];
+        let logger = Logger::new(config);
+
+        let snapshot = logger.current_config();
+        assert_eq!(snapshot.file_path, temp_dir.path());
+        assert_eq!(snapshot.file_name, "app");
+        assert_eq!(snapshot.log_file_path, temp_dir.path().join("app.log"));
+        assert_eq!(snapshot.log_level, Some(LogLevel::Info));
+        assert_eq!(snapshot.pattern, Pattern::Detailed);
+        assert_eq!((snapshot.max_file_size, snapshot.max_backup_files), (2048, 3));
+        assert_eq!(snapshot.destinations, vec![Destination::File, Destination::Memory(5)]);
+
+        logger.set_level(LogLevel::Trace);
+        assert_eq!(logger.current_config().log_level, Some(LogLevel::Trace));
+        assert_eq!(snapshot.log_level, Some(LogLevel::Info));
+    }
+
+    #[test]
+    fn test_logger_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Logger>();
+        assert_send_sync::<LoggerCore>();
+    }
+
+    #[test]
+    fn test_line_rotation_happens_exactly_at_max_lines() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.rotation_strategy = RotationStrategy::Lines(5);
+        let logger = Logger::new(config);
+
+        for index in 1..=6 {
+            logger.info(&format!("Line {}", index));
+        }
+
+        let backup = fs::read_to_string(temp_dir.path().join("app.1.log")).unwrap();
+        let current = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert_eq!(backup.lines().count(), 5);
+        assert!(backup.ends_with("INFO: Line 5\n"));
+        assert_eq!(current.lines().count(), 1);
+        assert!(current.ends_with("INFO: Line 6\n"));
+    }
+
+    #[test]
+    fn test_size_or_daily_rotates_on_size_mid_day() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.use_utc = true;
+        config.max_file_size = 30;
+        config.rotation_strategy = RotationStrategy::SizeOrDaily;
+        let noon = timestamp_from_utc(2025, 9, 6, 12, 0, 0).unwrap();
+        let logger = Logger::with_clock(config, Arc::new(move || noon));
+
+        logger.info("First entry fills the file");
+        logger.info("Second entry, same day");
+
+        // No day boundary was crossed: the size limit alone rotated the file
+        let backup = fs::read_to_string(temp_dir.path().join("app.2025-09-06.1.log")).unwrap();
+        let current = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert!(backup.ends_with("INFO: First entry fills the file\n"));
+        assert!(current.ends_with("INFO: Second entry, same day\n"));
+        assert_eq!(current.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_utf8_bom_once_per_file_and_after_rotation() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.write_utf8_bom = true;
+        config.max_file_size = 80;
+        let logger = Logger::new(config);
+
+        logger.info("First entry");
+        logger.info("Second entry");
+        logger.info("Third entry rotates the file");
+
+        let backup = fs::read(temp_dir.path().join("app.1.log")).unwrap();
+        let current = fs::read(temp_dir.path().join("app.log")).unwrap();
+        for bytes in [&backup, &current] {
+            assert_eq!(&bytes[..3], [0xEF, 0xBB, 0xBF]);
+            assert_eq!(String::from_utf8_lossy(bytes).matches('\u{feff}').count(), 1);
+        }
+        assert_eq!(String::from_utf8_lossy(&backup).lines().count(), 2);
+        assert!(String::from_utf8_lossy(&current).ends_with("INFO: Third entry rotates the file\n"));
+    }
+
+    #[test]
+    fn test_sanitize_text_keeps_message_on_one_line() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        let logger = Logger::new(config);
+
+        logger.info("real entry\n[2025-09-06 15:30:45] ERROR: fake entry\x1b[2J");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert_eq!(content.lines().count(), 1);
+        assert!(content.ends_with("INFO: real entry\\n[2025-09-06 15:30:45] ERROR: fake entry\\x1b[2J\n"));
+    }
+
+    #[test]
+    fn test_backtrace_on_error_appends_caller_frames() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.backtrace_on_error = true;
+        let logger = Logger::new(config);
+
+        logger.warning("no stack for warnings");
+        logger.error("connection lost");
+
+        // Frames stay on their own lines even though sanitize_text is on
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert!(!lines[0].contains("backtrace"));
+        assert!(lines[1].ends_with("ERROR: connection lost"));
+        assert_eq!(lines[2], "backtrace:");
+        assert!(lines[3].starts_with("  at "));
+        assert!(content.contains("test_backtrace_on_error_appends_caller_frames"));
+        assert!(!content.contains("Logger::log"));
+    }
+
+    #[test]
+    fn test_log_hex_stays_multi_line_with_default_config() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        let logger = Logger::new(config);
+
+        logger.log_hex(LogLevel::Info, b"0123456789abcdef\ntail");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("INFO: hexdump (21 bytes)"));
+        assert!(lines[1].starts_with("00000000  30 31 32 33"));
+        assert!(lines[2].starts_with("00000010  0a 74 61 69 6c"));
+        assert!(lines[2].ends_with("|.tail|"));
+    }
+
+    #[test]
+    fn test_dedup_consecutive_summarizes_repeats() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.dedup_consecutive = true;
+        let logger = Logger::new(config);
+
+        for _ in 0..5 {
+            logger.error("connection refused");
+        }
+        logger.info("connection refused");
+        logger.info("reconnected");
+        logger.info("reconnected");
+        logger.flush();
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].ends_with("ERROR: connection refused"));
+        assert!(lines[1].ends_with("ERROR: ... (repeated 4 times)"));
+        // Same message at another level is not a repeat
+        assert!(lines[2].ends_with("INFO: connection refused"));
+        assert!(lines[3].ends_with("INFO: reconnected"));
+        assert!(lines[4].ends_with("INFO: ... (repeated 1 time)"));
+    }
+
+    #[test]
+    fn test_html_pattern_writes_one_escaped_table() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Html, temp_dir.path().to_path_buf(), "app".to_string());
+        let logger = Logger::new(config);
+
+        logger.info("<script>alert(1)</script>");
+        logger.flush();
+        logger.error("second & last");
+        logger.flush();
+
+        let content = fs::read_to_string(temp_dir.path().join("app.html")).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], crate::format::HTML_TABLE_START);
+        assert!(lines[1..3].iter().all(|line| line.starts_with("<tr class=") && line.ends_with("</tr>")));
+        assert_eq!(lines[3], "</table>");
+        assert!(!content.contains("<script>"));
+        assert!(lines[1].contains("<td>&lt;script&gt;alert(1)&lt;/script&gt;</td>"));
+        assert!(lines[2].contains("<td style=\"color:#d32f2f\">ERROR</td>"));
+    }
+
+    #[test]
+    fn test_crlf_line_ending_in_text_and_json_files() {
+        for (pattern, extension) in [(Pattern::Basic, "log"), (Pattern::Json, "json")] {
+            let temp_dir = tempdir().unwrap();
+            let mut config = LoggerConfig::basic(pattern, temp_dir.path().to_path_buf(), "app".to_string());
+            config.line_ending = LineEnding::Crlf;
+            let logger = Logger::new(config);
+
+            logger.info("first");
+            logger.info("second");
+
+            let bytes = fs::read(temp_dir.path().join(format!("app.{}", extension))).unwrap();
+            let content = String::from_utf8(bytes).unwrap();
+            assert_eq!(content.matches("\r\n").count(), 2);
+            assert_eq!(content.matches('\n').count(), 2);
+            assert!(content.ends_with("\r\n"));
+        }
+    }
+
+    #[test]
+    fn test_low_disk_space_warns_once_and_pauses_below_error() {
+        let temp_dir = tempdir().unwrap();
+        let warnings = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&warnings);
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.min_free_bytes = Some(1024);
+        config.pause_on_low_disk = true;
+        config.on_error = Some(Arc::new(move |error: &LoggerError| seen.lock().unwrap().push(error.clone())));
+        let mut logger = Logger::new(config);
+        Arc::get_mut(&mut logger.core).unwrap().disk_space = Some(DiskSpaceMonitor::with_query(1024, |_| Some(512)));
+
+        logger.info("paused");
+        logger.warning("paused too");
+        logger.error("still written");
+
+        let warnings = warnings.lock().unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0],
+            LoggerError::LowDiskSpace { available_bytes: 512, min_free_bytes: 1024, .. }
+        ));
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert_eq!(content.lines().count(), 1);
+        assert!(content.contains("ERROR: still written"));
+    }
+
+    #[test]
+    fn test_log_counts_per_level_and_filtered() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::with_level(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string(), LogLevel::Info);
+        let logger = Logger::new(config);
+
+        logger.error("e");
+        for _ in 0..2 {
+            logger.warning("w");
+        }
+        for _ in 0..3 {
+            logger.info("i");
+        }
+        logger.debug("filtered");
+        logger.trace("filtered");
+        logger.log_hex(LogLevel::Debug, b"filtered");
+
+        assert_eq!(logger.log_counts(), LogCounts {
+            error: 1,
+            warning: 2,
+            info: 3,
+            debug: 0,
+            trace: 0,
+            filtered: 3,
+            suppressed: 0,
+            dropped: 0,
+        });
+        assert_eq!(logger.log_counts().total_emitted(), 6);
+        assert_eq!(logger.log_counts().emitted(LogLevel::Warning), 2);
+    }
+
+    #[test]
+    fn test_static_fields_on_every_json_line() {
+        use crate::core::context::scope;
+
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Json, temp_dir.path().to_path_buf(), "app".to_string());
+        config.static_fields = vec![
+            ("service".to_string(), "api".to_string()),
+            ("env".to_string(), "prod".to_string()),
+        ];
+        let logger = Logger::new(config);
+
+        logger.info("first");
+        {
+            let _guard = scope("env", "staging");
+            logger.warning("second");
+        }
+        logger.error("third");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.json")).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        for line in &lines {
+            assert_eq!(json_string_field(line, "service"), "api");
+        }
+        assert_eq!(json_string_field(lines[0], "env"), "prod");
+        assert_eq!(json_string_field(lines[1], "env"), "staging");
+        assert_eq!(lines[1].matches("\"env\"").count(), 1);
+        assert_eq!(json_string_field(lines[2], "env"), "prod");
+    }
+
+    #[test]
+    fn test_on_rotate_receives_log_and_backup_path() {
+        let temp_dir = tempdir().unwrap();
+        let rotations = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&rotations);
+        let mut config = LoggerConfig::with_rotation(
+            Pattern::Basic,
+            temp_dir.path().to_path_buf(),
+            "app".to_string(),
+            LogLevel::Info,
+            100,
+            3,
+        );
+        config.on_rotate = Some(Arc::new(move |path: &Path, backup: &Path| {
+            seen.lock().unwrap().push((path.to_path_buf(), backup.to_path_buf(), backup.exists()));
+            panic!("ignored");
+        }));
+        let logger = Logger::new(config);
+
+        for index in 0..5 {
+            logger.info(&format!("entry {} padded to fill the file quickly", index));
+        }
+
+        let rotations = rotations.lock().unwrap();
+        assert!(!rotations.is_empty());
+        let (path, backup, backup_existed) = &rotations[0];
+        assert_eq!(path, &temp_dir.path().join("app.log"));
+        assert_eq!(backup, &temp_dir.path().join("app.1.log"));
+        assert!(backup_existed);
+        assert!(fs::read_to_string(temp_dir.path().join("app.log")).unwrap().contains("entry 4"));
+    }
+
+    #[test]
+    fn test_custom_error_log_name_receives_rotation_failure() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::with_rotation(
+            Pattern::Basic,
+            temp_dir.path().to_path_buf(),
+            "app".to_string(),
+            LogLevel::Trace,
+            10,
+            1,
+        );
+        config.error_log_name = Some("diagnostics.txt".to_string());
+
+        // A non-empty directory in the backup slot makes rotation fail
+        let blocked_backup = temp_dir.path().join("app.1.log");
+        fs::create_dir(&blocked_backup).unwrap();
+        fs::write(blocked_backup.join("keep"), "x").unwrap();
+        fs::write(temp_dir.path().join("app.log"), "over the size limit").unwrap();
+
+        let logger = Logger::new(config);
+        logger.info("Triggers a failing rotation");
+
+        let errors = fs::read_to_string(temp_dir.path().join("diagnostics.txt")).unwrap();
+        assert!(errors.contains("FreedomLogger Error: Log rotation failed"));
+        assert!(!temp_dir.path().join("logger_errors.log").exists());
+    }
+
+    #[test]
+    fn test_error_log_dir_is_created_and_used() {
+        let temp_dir = tempdir().unwrap();
+        let diagnostics = temp_dir.path().join("diagnostics").join("freedom");
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().join("logs"), "app".to_string());
+        config.error_log_dir = Some(diagnostics.clone());
+        let logger = Logger::new(config);
+
+        logger.core.handle_error(LoggerError::SerializationFailed { reason: "test".to_string() });
+
+        let errors = fs::read_to_string(diagnostics.join("logger_errors.log")).unwrap();
+        assert!(errors.contains("Failed to format log entry: test"));
+        assert!(!temp_dir.path().join("logs").join("logger_errors.log").exists());
+
+        // A directory that can't be created falls back to the log directory
+        let blocked = temp_dir.path().join("blocked");
+        fs::write(&blocked, "a file, not a directory").unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.error_log_dir = Some(blocked.join("errors"));
+        let logger = Logger::new(config);
+
+        logger.core.handle_error(LoggerError::SerializationFailed { reason: "fallback".to_string() });
+
+        let errors = fs::read_to_string(temp_dir.path().join("logger_errors.log")).unwrap();
+        assert!(errors.contains("Failed to format log entry: fallback"));
+    }
+
+    #[test]
+    fn test_json_message_with_newlines_stays_one_line() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Json, temp_dir.path().to_path_buf(), "app".to_string());
+        let logger = Logger::new(config);
+
+        let message = "first line\nsecond line\r\nthird\x0bfourth\x0c\u{2028}end";
+        logger.info(message);
+
+        let content = fs::read_to_string(temp_dir.path().join("app.json")).unwrap();
+        assert_eq!(content.matches('\n').count(), 1);
+        assert_eq!(content.lines().count(), 1);
+        let entry: serde_json::Value = serde_json::from_str(content.trim_end()).unwrap();
+        assert_eq!(entry["message"], message);
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_compress_live_writes_gzip_and_rotates_finished_stream() {
+        use std::io::Read;
+        use flate2::read::MultiGzDecoder;
+
+        let decompress = |path: &Path| {
+            let mut content = String::new();
+            MultiGzDecoder::new(fs::File::open(path).unwrap()).read_to_string(&mut content).unwrap();
+            content
+        };
+
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Custom("{message}".to_string()), temp_dir.path().to_path_buf(), "app".to_string());
+        config.compress_live = true;
+        config.max_file_size = 1;
+        let logger = Logger::new(config);
+
+        logger.info("First");
+        logger.info("Second");
+        logger.flush();
+        let log_path = temp_dir.path().join("app.log.gz");
+        assert_eq!(decompress(&log_path), "Second\n");
+        assert_eq!(decompress(&temp_dir.path().join("app.log.1.gz")), "First\n");
+
+        logger.info("Third");
+        logger.flush();
+        logger.info("Fourth");
+        drop(logger);
+        assert!(!temp_dir.path().join("app.log").exists());
+        assert_eq!(decompress(&log_path), "Fourth\n");
+        assert_eq!(decompress(&temp_dir.path().join("app.log.1.gz")), "Third\n");
+        assert_eq!(decompress(&temp_dir.path().join("app.log.2.gz")), "Second\n");
+        assert_eq!(decompress(&temp_dir.path().join("app.log.3.gz")), "First\n");
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_compress_live_appends_members_across_flushes() {
+        use std::io::Read;
+        use flate2::read::MultiGzDecoder;
+
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Custom("{message}".to_string()), temp_dir.path().to_path_buf(), "app".to_string());
+        config.compress_live = true;
+        let logger = Logger::new(config);
+
+        for index in 0..5 {
+            logger.info(&format!("Line {}", index));
+            if index % 2 == 0 {
+                logger.flush();
+            }
+        }
+        logger.flush();
+
+        let mut content = String::new();
+        MultiGzDecoder::new(fs::File::open(temp_dir.path().join("app.log.gz")).unwrap())
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "Line 0\nLine 1\nLine 2\nLine 3\nLine 4\n");
+    }
+
+    #[test]
+    fn test_buffer_used_tracks_capacity_and_flush() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("app.log");
+        let mut config = LoggerConfig::basic(Pattern::Custom("{message}".to_string()), temp_dir.path().to_path_buf(), "app".to_string());
+        config.buffered = true;
+        config.buffer_capacity = 64;
+        let logger = Logger::new(config);
+        assert_eq!(logger.buffer_used(), 0);
+
+        // 11 bytes per line ("Entry 0001" plus the newline)
+        for index in 0..5 {
+            logger.info(&format!("Entry {:04}", index));
+        }
+        assert_eq!(logger.buffer_used(), 55);
+        assert!(!log_path.exists());
+
+        // The sixth line reaches the capacity and writes the buffer out
+        logger.info("Entry 0005");
+        assert_eq!(logger.buffer_used(), 0);
+        assert_eq!(fs::read_to_string(&log_path).unwrap().lines().count(), 6);
+
+        logger.info("Entry 0006");
+        assert_eq!(logger.buffer_used(), 11);
+        logger.flush();
+        assert_eq!(logger.buffer_used(), 0);
+        assert_eq!(fs::read_to_string(&log_path).unwrap().lines().count(), 7);
+    }
+
+    #[test]
+    fn test_sequence_numbers_unique_across_threads() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Json, temp_dir.path().to_path_buf(), "app".to_string());
+        config.include_sequence = true;
+        let logger = Arc::new(Logger::new(config));
+
+        let handles: Vec<_> = (0..5)
+            .map(|index| {
+                let logger = Arc::clone(&logger);
+                thread::spawn(move || {
+                    logger.info(&format!("Thread {} first", index));
+                    logger.info(&format!("Thread {} second", index));
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let content = fs::read_to_string(temp_dir.path().join("app.json")).unwrap();
+        let numbers: Vec<u64> = content
+            .lines()
+            .map(|line| {
+                let entry: serde_json::Value = serde_json::from_str(line).unwrap();
+                entry["seq"].as_u64().unwrap()
+            })
+            .collect();
+        // Numbered under the write lock: file order is sequence order
+        assert_eq!(numbers, (0..10).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn test_multi_line_entries_never_interleave() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.sanitize_text = false;
+        // Two loggers on one file: one direct, one writing buffered batches
+        let direct = Arc::new(Logger::new(config.clone()));
+        config.buffered = true;
+        let buffered = Arc::new(Logger::new(config));
+
+        // The middle line is larger than typical I/O buffers
+        let padding = "x".repeat(10_000);
+        let handles: Vec<_> = (0..8)
+            .map(|index| {
+                let logger = Arc::clone(if index % 2 == 0 { &direct } else { &buffered });
+                let padding = padding.clone();
+                thread::spawn(move || {
+                    for entry in 0..50 {
+                        let id = format!("T{} E{}", index, entry);
+                        logger.info(&format!("{} first\n{} middle {}\n{} last", id, id, padding, id));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        buffered.flush();
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 8 * 50 * 3);
+        for entry in lines.chunks(3) {
+            let id = entry[1].split(" middle ").next().unwrap();
+            assert!(entry[0].ends_with(&format!("INFO: {} first", id)), "split entry: {}", entry[0]);
+            assert_eq!(entry[1], format!("{} middle {}", id, padding));
+            assert_eq!(entry[2], format!("{} last", id));
+        }
+    }
+
+    #[test]
+    fn test_sequence_in_custom_pattern() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Custom("#{seq} {message}".to_string()), temp_dir.path().to_path_buf(), "app".to_string());
+        config.include_sequence = true;
+        let logger = Logger::new(config);
+
+        logger.info("First");
+        logger.warning("Second");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        assert_eq!(content, "#0 First\n#1 Second\n");
+    }
+
+    #[test]
+    fn test_console_fallback_after_repeated_file_failures() {
+        // The child process logs with a file path that cannot be created;
+        // the parent checks its stderr
+        if let Some(blocker) = std::env::var_os("FREEDOMLOGGER_FALLBACK_CHILD") {
+            let log_dir = PathBuf::from(blocker).join("logs");
+            let mut config = LoggerConfig::basic(Pattern::Custom("{level}|{message}".to_string()), log_dir, "app".to_string());
+            config.console_fallback_after = Some(2);
+            let logger = Logger::new(config);
+            for index in 1..=4 {
+                logger.info(&format!("Entry {}", index));
+            }
+            return;
+        }
+
+        let temp_dir = tempdir().unwrap();
+        let blocker = temp_dir.path().join("not_a_directory");
+        fs::write(&blocker, "").unwrap();
+
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "core::logger::tests::test_console_fallback_after_repeated_file_failures", "--nocapture"])
+            .env("FREEDOMLOGGER_FALLBACK_CHILD", &blocker)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let lines: Vec<&str> = stderr.lines().filter(|line| line.contains("Entry") || line.contains("FreedomLogger:")).collect();
+        assert_eq!(lines.len(), 4, "stderr: {}", stderr);
+        assert!(lines[0].contains("WARNING: FreedomLogger:") && lines[0].contains("failed 2 times in a row"));
+        assert_eq!(lines[1..], ["INFO|Entry 2", "INFO|Entry 3", "INFO|Entry 4"]);
+    }
+
+    #[test]
+    fn test_format_entry_matches_written_line() {
+        let temp_dir = tempdir().unwrap();
+        let fixed = timestamp_from_utc(2025, 9, 6, 15, 30, 45).unwrap();
+        for pattern in [Pattern::Detailed, Pattern::Json] {
+            let mut config = LoggerConfig::basic(pattern.clone(), temp_dir.path().to_path_buf(), "app".to_string());
+            config.include_sequence = true;
+            config.static_fields = vec![("service".to_string(), "api".to_string())];
+            let logger = Logger::with_clock(config.clone(), Arc::new(move || fixed));
+
+            let formatted = logger.format_entry(LogLevel::Warning, "Disk almost full");
+            logger.log(LogLevel::Warning, "Disk almost full", None, file!(), line!() - 1);
+            drop(logger);
+
+            let content = fs::read_to_string(config.get_log_file_path()).unwrap();
+            assert_eq!(content, format!("{}\n", formatted));
+        }
+    }
+
+    #[test]
+    fn test_otel_json_pattern_writes_otel_schema() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::OtelJson, temp_dir.path().to_path_buf(), "app".to_string());
+        config.static_fields = vec![("service".to_string(), "api".to_string())];
+        let logger = Logger::new(config);
+
+        logger.error("Payment failed");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.json")).unwrap();
+        let entry: serde_json::Value = serde_json::from_str(content.trim_end()).unwrap();
+        assert_eq!(entry["SeverityText"], "ERROR");
+        assert_eq!(entry["SeverityNumber"], 17);
+        assert_eq!(entry["Body"], "Payment failed");
+        assert_eq!(entry["Attributes"]["service"], "api");
+        assert!(entry["Timestamp"].as_str().unwrap().contains('T'));
+    }
+
+    #[test]
+    fn test_gelf_pattern_writes_gelf_message() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Gelf, temp_dir.path().to_path_buf(), "app".to_string());
+        config.static_fields = vec![("service".to_string(), "api".to_string())];
+        let logger = Logger::new(config);
+
+        logger.warning("Disk almost full");
+
+        let content = fs::read_to_string(temp_dir.path().join("app.json")).unwrap();
+        let entry: serde_json::Value = serde_json::from_str(content.trim_end()).unwrap();
+        assert_eq!(entry["version"], "1.1");
+        assert_eq!(entry["level"].as_u64(), Some(4));
+        assert!(entry["timestamp"].as_f64().unwrap() > 1_500_000_000.0);
+        assert!(entry["host"].is_string());
+        assert_eq!(entry["short_message"], "Disk almost full");
+        assert_eq!(entry["_service"], "api");
+    }
+
+    #[test]
+    fn test_transient_write_failures_are_retried() {
+        use std::sync::atomic::AtomicUsize;
+        use crate::error::LoggerResult;
+
+        /// Fails the first two attempts of every entry, then keeps the line
+        struct FlakyWriter {
+            attempts: AtomicUsize,
+            lines: Mutex<Vec<String>>,
+        }
+
+        impl LogWriter for FlakyWriter {
+            fn write_entry(&self, _info: &LogInfo, formatted: &str, path: &Path) -> LoggerResult<()> {
+                if self.attempts.fetch_add(1, Ordering::Relaxed) % 3 < 2 {
+                    return Err(LoggerError::FileCreationFailed {
+                        path: path.display().to_string(),
+                        reason: "Stale NFS file handle".to_string(),
+                    });
+                }
+                self.lines.lock().unwrap().push(formatted.to_string());
+                Ok(())
+            }
+        }
+
+        let temp_dir = tempdir().unwrap();
+        let errors = Arc::new(AtomicUsize::new(0));
+        let flaky = Arc::new(FlakyWriter { attempts: AtomicUsize::new(0), lines: Mutex::new(Vec::new()) });
+        let mut config = LoggerConfig::basic(Pattern::Custom("{message}".to_string()), temp_dir.path().to_path_buf(), "app".to_string());
+        config.destinations = Vec::new();
+        config.custom_writer = Some(flaky.clone());
+        config.max_open_retries = 2;
+        config.on_error = Some(Arc::new({
+            let errors = Arc::clone(&errors);
+            move |_| {
+                errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }));
+        let logger = Logger::new(config);
+
+        logger.info("Eventually written");
+        logger.warning("Also written");
+
+        assert_eq!(*flaky.lines.lock().unwrap(), ["Eventually written", "Also written"]);
+        assert_eq!(flaky.attempts.load(Ordering::Relaxed), 6);
+        assert_eq!(errors.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn test_level_methods_report_caller_location() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Detailed, temp_dir.path().to_path_buf(), "app".to_string());
+        config.destinations = vec![Destination::Memory(10)];
+        let logger = Logger::new(config);
+
+        let line = line!() + 1;
+        logger.warning("Caller marker");
+        logger.log_hex(LogLevel::Info, b"hi");
+
+        let lines = logger.captured_lines();
+        assert!(lines[0].contains(&format!("{}:{}", file!(), line)), "{}", lines[0]);
+        assert!(lines[1].contains(&format!("{}:{}", file!(), line + 1)), "{}", lines[1]);
+    }
+
+    #[test]
+    fn test_flush_interval_writes_buffered_output() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("app.log");
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.buffered = true;
+        config.flush_interval = Some(std::time::Duration::from_millis(200));
+        let logger = Logger::new(config);
+
+        logger.info("Flushed by the interval");
+        assert!(!log_path.exists());
+
+        // No manual flush: the flush thread writes the buffer out
+        thread::sleep(std::time::Duration::from_millis(600));
+        let content = fs::read_to_string(&log_path).unwrap();
+        assert!(content.contains("INFO: Flushed by the interval"));
+
+        logger.shutdown();
+        logger.info("After shutdown");
+        thread::sleep(std::time::Duration::from_millis(400));
+        assert!(!fs::read_to_string(&log_path).unwrap().contains("After shutdown"));
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_suppress_patterns_drop_matching_messages() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.suppress_patterns = vec![regex::Regex::new("heartbeat").unwrap()];
+        let log_path = config.get_log_file_path();
+        let logger = Logger::new(config);
+
+        logger.info("heartbeat from worker 1");
+        logger.info("Request handled");
+        logger.debug("Sending heartbeat");
+        logger.warning("Disk almost full");
+
+        let content = fs::read_to_string(&log_path).unwrap();
+        assert!(!content.contains("heartbeat"));
+        assert!(content.contains("INFO: Request handled"));
+        assert!(content.contains("WARNING: Disk almost full"));
+
+        let counts = logger.log_counts();
+        assert_eq!(counts.suppressed, 2);
+        assert_eq!(counts.total_emitted(), 2);
+    }
+
+    #[test]
+    fn test_separate_error_file_gets_only_errors_and_rotates_alone() {
+        let temp_dir = tempdir().unwrap();
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.separate_error_file = Some(LogLevel::Error);
+        config.max_file_size = 200;
+        let logger = Logger::new(config);
+
+        logger.info("Starting");
+        logger.error("First failure");
+        logger.warning("Slow response");
+        logger.error("Second failure");
+        logger.debug("Done");
+
+        let main = fs::read_to_string(temp_dir.path().join("app.log")).unwrap();
+        for line in ["INFO: Starting", "ERROR: First failure", "WARNING: Slow response", "ERROR: Second failure", "DEBUG: Done"] {
+            assert!(main.contains(line), "missing {:?} in {:?}", line, main);
+        }
+        let errors = fs::read_to_string(temp_dir.path().join("app.error.log")).unwrap();
+        let error_lines: Vec<&str> = errors.lines().collect();
+        assert_eq!(error_lines.len(), 2);
+        assert!(error_lines[0].ends_with("ERROR: First failure"));
+        assert!(error_lines[1].ends_with("ERROR: Second failure"));
+
+        // Enough INFO lines to rotate the main file, which leaves the error file alone
+        for index in 0..10 {
+            logger.info(&format!("Filler line {}", index));
+        }
+        assert!(temp_dir.path().join("app.1.log").exists());
+        assert!(!temp_dir.path().join("app.error.1.log").exists());
+        assert_eq!(fs::read_to_string(temp_dir.path().join("app.error.log")).unwrap(), errors);
+    }
+}
@@ -0,0 +1,98 @@
+//! Per-level log counters for FreedomLogger
+//!
+//! Every logger counts the entries it emits per level and the messages it
+//! filters out, for dashboards ("logs per level per minute": sample
+//! `log_counts()` once a minute and take the difference). The counters are
+//! plain atomics, so counting adds no lock to the logging path.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::core::config::LogLevel;
+
+/// Counts of a logger since it was created (see `Logger::log_counts`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LogCounts {
+    /// ERROR entries emitted
+    pub error: u64,
+    /// WARNING entries emitted
+    pub warning: u64,
+    /// INFO entries emitted
+    pub info: u64,
+    /// DEBUG entries emitted
+    pub debug: u64,
+    /// TRACE entries emitted
+    pub trace: u64,
+    /// Messages rejected by the level filter
+    pub filtered: u64,
+    /// Messages dropped by `LoggerConfig::suppress_patterns` (not counted in their level)
+    pub suppressed: u64,
+    /// Entries dropped by a full async queue (also counted in their level)
+    pub dropped: u64,
+}
+
+impl LogCounts {
+    /// Entries emitted at one level (0 for Off)
+    pub fn emitted(&self, level: LogLevel) -> u64 {
+        match level {
+            LogLevel::Off => 0,
+            LogLevel::Error => self.error,
+            LogLevel::Warning => self.warning,
+            LogLevel::Info => self.info,
+            LogLevel::Debug => self.debug,
+            LogLevel::Trace => self.trace,
+        }
+    }
+
+    /// Entries emitted at all levels
+    pub fn total_emitted(&self) -> u64 {
+        self.error + self.warning + self.info + self.debug + self.trace
+    }
+}
+
+/// Lock-free counters behind `LogCounts`
+#[derive(Debug, Default)]
+pub(crate) struct LevelCounters {
+    /// Emitted entries, indexed by level - 1 (Error .. Trace)
+    emitted: [AtomicU64; 5],
+    /// Messages rejected by the level filter
+    filtered: AtomicU64,
+    /// Messages matching a suppress pattern
+    suppressed: AtomicU64,
+}
+
+impl LevelCounters {
+    /// Count an entry that passed the level filter
+    pub(crate) fn record(&self, level: LogLevel) {
+        if let Some(counter) = (level as usize).checked_sub(1).and_then(|index| self.emitted.get(index)) {
+            counter.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Count a message rejected by the level filter
+    pub(crate) fn record_filtered(&self) {
+        self.filtered.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Count a message dropped by a suppress pattern
+    #[cfg_attr(not(feature = "regex"), allow(dead_code))]
+    pub(crate) fn record_suppressed(&self) {
+        self.suppressed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Read the counters
+    ///
+    /// # Arguments
+    /// * `dropped` - Entries dropped by the async queue (counted there)
+    pub(crate) fn snapshot(&self, dropped: u64) -> LogCounts {
+        let emitted = |level: LogLevel| self.emitted[level as usize - 1].load(Ordering::Relaxed);
+        LogCounts {
+            error: emitted(LogLevel::Error),
+            warning: emitted(LogLevel::Warning),
+            info: emitted(LogLevel::Info),
+            debug: emitted(LogLevel::Debug),
+            trace: emitted(LogLevel::Trace),
+            filtered: self.filtered.load(Ordering::Relaxed),
+            suppressed: self.suppressed.load(Ordering::Relaxed),
+            dropped,
+        }
+    }
+}
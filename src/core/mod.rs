@@ -1,21 +1,50 @@
-/// Core module for FreedomLogger
-///
-/// Contains the main logger implementation and all supporting components:
-/// - Logger: Main logging orchestrator
-/// - LoggerConfig: Configuration management
-/// - Writers: Text and JSON output handlers
-/// - Configuration types: LogLevel, Pattern, etc.
+//! Core module for FreedomLogger
+//!
+//! Contains the main logger implementation and all supporting components:
+//! - Logger: Main logging orchestrator
+//! - AsyncQueue: Background writer thread for async mode (internal)
+//! - LoggerConfig: Configuration management (and TOML loading with `config-file`)
+//! - Clock: Timestamp source (system time, or a fixed clock in tests)
+//! - Context: Thread-local fields attached to every log line
+//! - DiskSpace: Free space check for `min_free_bytes` (internal)
+//! - FlushThread: Periodic flush of buffered output for `flush_interval` (internal)
+//! - Global: Slot holding the global logger (init once, explicit reconfigure)
+//! - Metrics: Per-level counts of emitted and filtered entries
+//! - Sampling: Per-call-site rate limiting for the sampling macros
+//! - Span: Scoped entry/exit timing for the `log_span!` macro
+//! - Time: Time backend (chrono, or std with `minimal-time`)
+//! - Writers: Text and JSON output handlers
+//! - Configuration types: LogLevel, Pattern, etc.
 
 // Re-export the main Logger struct
 pub use logger::Logger;
 
 // Re-export configuration types for public API
-pub use config::{LogLevel, Pattern, LoggerConfig};
+pub use config::{LogLevel, Pattern, LoggerConfig, LoggerConfigSnapshot, Destination, RotationStrategy, LineEnding, Framing, CustomFormatter, ErrorCallback, RotateCallback};
+
+// Re-export the per-level counts
+pub use metrics::LogCounts;
+
+// Re-export the timestamp source
+pub use clock::{Clock, SystemClock};
+pub use time::{Date, Timestamp};
 
 // Re-export writers for potential advanced usage
-pub use writers::{TextWriter, JsonWriter};
+pub use writers::{LogWriter, TextWriter, JsonWriter, ConsoleWriter, MemoryWriter};
 
 // Import all core modules
+mod async_queue;
+pub mod clock;
 pub mod config;
+#[cfg(feature = "config-file")]
+pub mod config_file;
+pub mod context;
+mod disk_space;
+mod flush_thread;
+pub(crate) mod global;
 pub mod logger;
+pub mod metrics;
+pub mod sampling;
+pub mod span;
+pub mod time;
 pub mod writers;
\ No newline at end of file
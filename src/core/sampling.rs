@@ -0,0 +1,187 @@
+//! Per-call-site sampling and throttling for FreedomLogger
+//!
+//! Backs the `log_*_every!`, `log_*_throttle!` and `log_*_once!` macros, which
+//! keep chatty loops from flooding the log with identical lines.
+//!
+//! Each macro invocation expands to its own `static` sampler, so state is
+//! keyed by call site (file and line) without any global lookup:
+//! - EveryN: emit the 1st, (N+1)th, (2N+1)th, ... invocation (lock-free counter)
+//! - Throttle: emit at most once per interval, reporting how many were suppressed
+//! - LogOnce: emit only the first invocation (lock-free flag)
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use crate::core::time::Instant;
+
+/// Sampler that lets every Nth invocation through
+#[derive(Debug)]
+pub struct EveryN {
+    /// Number of invocations seen at this call site
+    count: AtomicU64,
+}
+
+impl EveryN {
+    /// Create a new sampler (const so it can live in a per-call-site static)
+    pub const fn new() -> Self {
+        Self {
+            count: AtomicU64::new(0),
+        }
+    }
+
+    /// Register an invocation and check if it should be logged
+    ///
+    /// # Arguments
+    /// * `n` - Log one out of every `n` invocations (0 and 1 log everything)
+    ///
+    /// # Returns
+    /// True for the 1st, (n+1)th, (2n+1)th, ... invocation
+    pub fn should_log(&self, n: u64) -> bool {
+        let seen = self.count.fetch_add(1, Ordering::Relaxed);
+        n <= 1 || seen.is_multiple_of(n)
+    }
+}
+
+impl Default for EveryN {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sampler that lets at most one invocation through per interval
+#[derive(Debug)]
+pub struct Throttle {
+    /// Time of the last emitted message and number suppressed since then
+    state: Mutex<Option<(Instant, u64)>>,
+}
+
+impl Throttle {
+    /// Create a new throttle (const so it can live in a per-call-site static)
+    pub const fn new() -> Self {
+        Self {
+            state: Mutex::new(None),
+        }
+    }
+
+    /// Register an invocation and check if it should be logged
+    ///
+    /// # Arguments
+    /// * `interval` - Minimum time between two emitted messages
+    ///
+    /// # Returns
+    /// Some(suppressed) if this invocation should be logged, where `suppressed`
+    /// is the number of invocations dropped since the previous emitted one.
+    /// None if this invocation should be dropped.
+    pub fn should_log(&self, interval: Duration) -> Option<u64> {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+
+        match *state {
+            Some((last_emitted, suppressed)) if now.duration_since(last_emitted) < interval => {
+                *state = Some((last_emitted, suppressed + 1));
+                None
+            }
+            Some((_, suppressed)) => {
+                *state = Some((now, 0));
+                Some(suppressed)
+            }
+            None => {
+                *state = Some((now, 0));
+                Some(0)
+            }
+        }
+    }
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sampler that lets only the first invocation through
+#[derive(Debug)]
+pub struct LogOnce {
+    /// Set once the call site has logged
+    done: AtomicBool,
+}
+
+impl LogOnce {
+    /// Create a new flag (const so it can live in a per-call-site static)
+    pub const fn new() -> Self {
+        Self {
+            done: AtomicBool::new(false),
+        }
+    }
+
+    /// Register an invocation and check if it should be logged
+    ///
+    /// # Returns
+    /// True for the first invocation only, even with concurrent callers
+    pub fn should_log(&self) -> bool {
+        !self.done.swap(true, Ordering::Relaxed)
+    }
+}
+
+impl Default for LogOnce {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Append a "(suppressed N)" note when messages were dropped by a throttle
+///
+/// # Returns
+/// The message unchanged when nothing was suppressed
+pub fn with_suppressed_note(message: String, suppressed: u64) -> String {
+    if suppressed == 0 {
+        message
+    } else {
+        format!("{} (suppressed {})", message, suppressed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_n_lets_one_in_n_through() {
+        let sampler = EveryN::new();
+        let emitted = (0..100).filter(|_| sampler.should_log(10)).count();
+        assert_eq!(emitted, 10);
+    }
+
+    #[test]
+    fn test_every_n_zero_and_one_log_everything() {
+        let sampler = EveryN::new();
+        assert!((0..5).all(|_| sampler.should_log(1)));
+        assert!((0..5).all(|_| sampler.should_log(0)));
+    }
+
+    #[test]
+    fn test_throttle_counts_suppressed_invocations() {
+        let throttle = Throttle::new();
+        let interval = Duration::from_millis(50);
+
+        assert_eq!(throttle.should_log(interval), Some(0));
+        assert_eq!(throttle.should_log(interval), None);
+        assert_eq!(throttle.should_log(interval), None);
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(throttle.should_log(interval), Some(2));
+    }
+
+    #[test]
+    fn test_log_once_lets_only_first_through() {
+        let once = LogOnce::new();
+        assert!(once.should_log());
+        assert!((0..10).all(|_| !once.should_log()));
+    }
+
+    #[test]
+    fn test_suppressed_note() {
+        assert_eq!(with_suppressed_note("msg".to_string(), 0), "msg");
+        assert_eq!(with_suppressed_note("msg".to_string(), 3), "msg (suppressed 3)");
+    }
+}
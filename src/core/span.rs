@@ -0,0 +1,161 @@
+//! Scoped timing spans for FreedomLogger
+//!
+//! Backs the `log_span!` macro: a `LogSpan` logs "entered NAME" at TRACE
+//! when it is created and "exited NAME (took 12.3ms)" when it is dropped,
+//! so profiling a block needs no manual timers:
+//!
+//! `let _span = log_span!("load_config");`
+//!
+//! Spans opened inside another span on the same thread are indented two
+//! spaces per level, so nested timings read as a tree. Durations are
+//! written by `format::format_duration`.
+
+use std::cell::Cell;
+use std::ops::Deref;
+use std::sync::Arc;
+use crate::core::time::Instant;
+use crate::core::config::LogLevel;
+use crate::core::logger::Logger;
+use crate::format::format_duration;
+
+thread_local! {
+    /// Number of spans currently open on this thread
+    static SPAN_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Logger a span writes to: borrowed, or shared (the global logger)
+enum SpanLogger<'a> {
+    Borrowed(&'a Logger),
+    Shared(Arc<Logger>),
+}
+
+impl Deref for SpanLogger<'_> {
+    type Target = Logger;
+
+    fn deref(&self) -> &Logger {
+        match self {
+            SpanLogger::Borrowed(logger) => logger,
+            SpanLogger::Shared(logger) => logger,
+        }
+    }
+}
+
+/// Guard that logs the entry and timed exit of a scope
+///
+/// Keep it in a named binding (`let _span = ...`): `let _ = ...` drops it
+/// immediately and logs a zero-length span.
+#[must_use = "the span ends when this guard is dropped"]
+pub struct LogSpan<'a> {
+    /// Logger to write to (None when tracing is compiled out)
+    logger: Option<SpanLogger<'a>>,
+    /// Name of the operation
+    name: String,
+    /// Call site of the span
+    module: &'static str,
+    file: &'static str,
+    line: u32,
+    /// Nesting level on this thread (0 for an outermost span)
+    depth: usize,
+    /// Time the span was entered
+    start: Instant,
+}
+
+impl<'a> LogSpan<'a> {
+    /// Open a span and log its entry at TRACE
+    ///
+    /// # Arguments
+    /// * `logger` - Logger to write the entry and exit lines to
+    /// * `name` - Name of the operation
+    /// * `module`, `file`, `line` - Call site (`module_path!()`, `file!()`, `line!()`)
+    pub fn enter(logger: &'a Logger, name: &str, module: &'static str, file: &'static str, line: u32) -> Self {
+        Self::open(Some(SpanLogger::Borrowed(logger)), name, module, file, line)
+    }
+
+    /// Open a span on a shared logger, kept alive until the span ends
+    ///
+    /// Used for the global logger, which `reconfigure` may replace meanwhile.
+    pub fn enter_shared(logger: Arc<Logger>, name: &str, module: &'static str, file: &'static str, line: u32) -> LogSpan<'static> {
+        LogSpan::open(Some(SpanLogger::Shared(logger)), name, module, file, line)
+    }
+
+    /// Open a span that logs nothing (used when TRACE is compiled out)
+    pub fn disabled(name: &str, module: &'static str, file: &'static str, line: u32) -> Self {
+        Self::open(None, name, module, file, line)
+    }
+
+    fn open(logger: Option<SpanLogger<'a>>, name: &str, module: &'static str, file: &'static str, line: u32) -> Self {
+        let depth = SPAN_DEPTH.with(|depth| depth.replace(depth.get() + 1));
+        let span = Self {
+            logger,
+            name: name.to_string(),
+            module,
+            file,
+            line,
+            depth,
+            start: Instant::now(),
+        };
+        span.log(&format!("entered {}", span.name));
+        span
+    }
+
+    /// Nesting level of this span (0 for an outermost span)
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Write an indented TRACE line, if TRACE is enabled
+    fn log(&self, message: &str) {
+        if let Some(logger) = &self.logger {
+            if logger.is_enabled(LogLevel::Trace) {
+                let indented = format!("{}{}", "  ".repeat(self.depth), message);
+                logger.log_from(LogLevel::Trace, &indented, self.module, self.file, self.line);
+            }
+        }
+    }
+}
+
+impl Drop for LogSpan<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        self.log(&format!("exited {} (took {})", self.name, format_duration(elapsed)));
+        SPAN_DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{LoggerConfig, Pattern};
+    use std::fs;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_span_logs_entry_and_timed_exit() {
+        let temp_dir = tempdir().unwrap();
+        let config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "span".to_string());
+        let logger = Logger::new(config);
+
+        {
+            let outer = LogSpan::enter(&logger, "outer", module_path!(), file!(), line!());
+            let inner = LogSpan::enter(&logger, "inner", module_path!(), file!(), line!());
+            assert_eq!((outer.depth(), inner.depth()), (0, 1));
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        let content = fs::read_to_string(temp_dir.path().join("span.log")).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].ends_with("TRACE: entered outer"));
+        assert!(lines[1].ends_with("TRACE:   entered inner"));
+        assert!(lines[2].contains("TRACE:   exited inner (took "));
+        assert!(lines[3].contains("TRACE: exited outer (took "));
+        assert!(lines[3].ends_with("ms)"));
+    }
+
+    #[test]
+    fn test_disabled_span_logs_nothing() {
+        let span = LogSpan::disabled("quiet", module_path!(), file!(), line!());
+        assert_eq!(span.depth(), 0);
+    }
+}
@@ -0,0 +1,349 @@
+//! Time backend for FreedomLogger
+//!
+//! Everything the logger needs from the calendar goes through this module:
+//! the current time, the date of an instant (daily files, rotation) and the
+//! dates in file names. Two implementations, selected by feature:
+//! - chrono (default): `Timestamp` is `chrono::DateTime<Local>` and `Date` is
+//!   `chrono::NaiveDate`, so local time and UTC both work
+//! - `minimal-time`: std only (`SystemTime`), for builds without chrono
+//!   (embedded, wasm). std has no time zone data, so every time is UTC and
+//!   `LoggerConfig::use_utc` makes no difference
+//!
+//! Both provide the same functions; timestamp text is formatted by
+//! `format::timestamp`.
+//!
+//! std has no clock on `wasm32-unknown-unknown` (`SystemTime::now` and
+//! `Instant::now` panic). With the `wasm` feature the browser clock is used
+//! instead: chrono's `wasmbind` support, or `Date.now()` for `minimal-time`,
+//! and `performance.now()` for the monotonic `Instant`.
+//!
+//! Build without chrono: `cargo build --no-default-features --features minimal-time`
+
+#[cfg(not(any(feature = "chrono", feature = "minimal-time")))]
+compile_error!("FreedomLogger needs a time backend: enable the `chrono` (default) or `minimal-time` feature");
+
+#[cfg(not(feature = "minimal-time"))]
+pub use chrono_backend::*;
+#[cfg(feature = "minimal-time")]
+pub use std_backend::*;
+
+/// Monotonic clock for spans, sampling and rate limits
+#[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+pub(crate) use std::time::Instant;
+/// Monotonic clock for spans, sampling and rate limits (`performance.now()`)
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub(crate) use web_time::Instant;
+
+/// Time backend based on chrono (local time zone support)
+#[cfg(not(feature = "minimal-time"))]
+mod chrono_backend {
+    use std::time::SystemTime;
+    use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+
+    /// An instant, in the local time zone
+    pub type Timestamp = DateTime<Local>;
+
+    /// A calendar date
+    pub type Date = NaiveDate;
+
+    /// Get the current time
+    pub fn now() -> Timestamp {
+        Local::now()
+    }
+
+    /// Whole seconds since the Unix epoch (rounded down)
+    pub fn unix_seconds(instant: Timestamp) -> i64 {
+        instant.timestamp()
+    }
+
+    /// Whole milliseconds since the Unix epoch (rounded down)
+    pub fn unix_millis(instant: Timestamp) -> i64 {
+        instant.timestamp_millis()
+    }
+
+    /// Date of an instant (the UTC date with `use_utc`, otherwise the local date)
+    pub fn date_of(instant: Timestamp, use_utc: bool) -> Date {
+        if use_utc {
+            instant.with_timezone(&Utc).date_naive()
+        } else {
+            instant.date_naive()
+        }
+    }
+
+    /// Date of a file time (e.g. a modification time), like `date_of`
+    pub fn date_of_system_time(time: SystemTime, use_utc: bool) -> Date {
+        date_of(DateTime::<Local>::from(time), use_utc)
+    }
+
+    /// Format a date as used in file names: "2025-09-06"
+    pub fn format_date(date: Date) -> String {
+        date.format("%Y-%m-%d").to_string()
+    }
+
+    /// Parse a date written by `format_date`
+    pub fn parse_date(text: &str) -> Option<Date> {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()
+    }
+
+    /// Build an instant from a UTC date and time (None if it doesn't exist)
+    ///
+    /// Handy for fixed clocks: `Logger::with_clock(config, Arc::new(move || instant))`
+    pub fn timestamp_from_utc(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Option<Timestamp> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, second)
+            .single()
+            .map(|instant| instant.with_timezone(&Local))
+    }
+}
+
+/// Time backend based on `std::time::SystemTime` (UTC only)
+#[cfg(feature = "minimal-time")]
+mod std_backend {
+    use std::fmt;
+    use std::ops::{Add, Sub};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    const SECONDS_PER_DAY: i64 = 86_400;
+
+    /// An instant (always shown in UTC)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Timestamp(SystemTime);
+
+    impl Timestamp {
+        /// The instant as a `SystemTime`
+        pub fn system_time(&self) -> SystemTime {
+            self.0
+        }
+    }
+
+    impl From<SystemTime> for Timestamp {
+        fn from(time: SystemTime) -> Self {
+            Self(time)
+        }
+    }
+
+    impl Add<Duration> for Timestamp {
+        type Output = Self;
+
+        fn add(self, duration: Duration) -> Self {
+            Self(self.0 + duration)
+        }
+    }
+
+    impl Sub<Duration> for Timestamp {
+        type Output = Self;
+
+        fn sub(self, duration: Duration) -> Self {
+            Self(self.0 - duration)
+        }
+    }
+
+    /// A calendar date (proleptic Gregorian)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    pub struct Date {
+        year: i32,
+        month: u32,
+        day: u32,
+    }
+
+    impl Date {
+        /// Build a date (None if it doesn't exist, e.g. February 30)
+        pub fn from_ymd_opt(year: i32, month: u32, day: u32) -> Option<Self> {
+            let valid = (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month);
+            valid.then_some(Self { year, month, day })
+        }
+
+        /// Year (e.g. 2025)
+        pub fn year(&self) -> i32 {
+            self.year
+        }
+
+        /// Month, 1 to 12
+        pub fn month(&self) -> u32 {
+            self.month
+        }
+
+        /// Day of the month, 1 to 31
+        pub fn day(&self) -> u32 {
+            self.day
+        }
+
+        /// The following day (None past the representable range)
+        pub fn succ_opt(&self) -> Option<Self> {
+            let (year, month, day) = civil_from_days(days_from_civil(*self) + 1);
+            Date::from_ymd_opt(year, month, day)
+        }
+    }
+
+    impl fmt::Display for Date {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+        }
+    }
+
+    /// Get the current time
+    #[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+    pub fn now() -> Timestamp {
+        Timestamp(SystemTime::now())
+    }
+
+    /// Get the current time from the browser (`Date.now()`)
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    pub fn now() -> Timestamp {
+        let since_epoch = web_time::SystemTime::now()
+            .duration_since(web_time::UNIX_EPOCH)
+            .unwrap_or_default();
+        Timestamp(UNIX_EPOCH + since_epoch)
+    }
+
+    /// Whole seconds since the Unix epoch (rounded down)
+    pub fn unix_seconds(instant: Timestamp) -> i64 {
+        match instant.0.duration_since(UNIX_EPOCH) {
+            Ok(after) => after.as_secs() as i64,
+            Err(before) => {
+                let before = before.duration();
+                -(before.as_secs() as i64) - i64::from(before.subsec_nanos() > 0)
+            }
+        }
+    }
+
+    /// Whole milliseconds since the Unix epoch (rounded down)
+    pub fn unix_millis(instant: Timestamp) -> i64 {
+        match instant.0.duration_since(UNIX_EPOCH) {
+            Ok(after) => after.as_millis() as i64,
+            Err(before) => {
+                let before = before.duration();
+                -(before.as_millis() as i64) - i64::from(before.subsec_nanos() % 1_000_000 > 0)
+            }
+        }
+    }
+
+    /// Date of an instant (always the UTC date)
+    pub fn date_of(instant: Timestamp, _use_utc: bool) -> Date {
+        utc_fields(instant).0
+    }
+
+    /// Date of a file time (e.g. a modification time), like `date_of`
+    pub fn date_of_system_time(time: SystemTime, use_utc: bool) -> Date {
+        date_of(Timestamp(time), use_utc)
+    }
+
+    /// Format a date as used in file names: "2025-09-06"
+    pub fn format_date(date: Date) -> String {
+        date.to_string()
+    }
+
+    /// Parse a date written by `format_date`
+    pub fn parse_date(text: &str) -> Option<Date> {
+        let mut parts = text.split('-');
+        let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+        let digits = |part: &str, len: usize| part.len() == len && part.bytes().all(|byte| byte.is_ascii_digit());
+        if parts.next().is_some() || !digits(year, 4) || !digits(month, 2) || !digits(day, 2) {
+            return None;
+        }
+        Date::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+    }
+
+    /// Build an instant from a UTC date and time (None if it doesn't exist)
+    ///
+    /// Handy for fixed clocks: `Logger::with_clock(config, Arc::new(move || instant))`
+    pub fn timestamp_from_utc(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> Option<Timestamp> {
+        let date = Date::from_ymd_opt(year, month, day)?;
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        let seconds = days_from_civil(date) * SECONDS_PER_DAY
+            + i64::from(hour * 3600 + minute * 60 + second);
+        let offset = Duration::from_secs(seconds.unsigned_abs());
+        let time = if seconds >= 0 {
+            UNIX_EPOCH.checked_add(offset)
+        } else {
+            UNIX_EPOCH.checked_sub(offset)
+        };
+        time.map(Timestamp)
+    }
+
+    /// UTC date, hour, minute and second of an instant
+    pub(crate) fn utc_fields(instant: Timestamp) -> (Date, u32, u32, u32) {
+        let seconds = unix_seconds(instant);
+        let (year, month, day) = civil_from_days(seconds.div_euclid(SECONDS_PER_DAY));
+        let of_day = seconds.rem_euclid(SECONDS_PER_DAY) as u32;
+        (Date { year, month, day }, of_day / 3600, of_day / 60 % 60, of_day % 60)
+    }
+
+    fn is_leap_year(year: i32) -> bool {
+        year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+    }
+
+    fn days_in_month(year: i32, month: u32) -> u32 {
+        match month {
+            2 if is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /// Days since 1970-01-01 (negative before), after Howard Hinnant's
+    /// `days_from_civil`
+    fn days_from_civil(date: Date) -> i64 {
+        let year = i64::from(date.year) - i64::from(date.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year.rem_euclid(400);
+        let month = i64::from(date.month);
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + i64::from(date.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// Year, month and day of a day count from `days_from_civil`
+    fn civil_from_days(days: i64) -> (i32, u32, u32) {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        (year as i32, month, day)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_civil_dates_round_trip() {
+            for (year, month, day, days) in [(1970, 1, 1, 0), (2000, 2, 29, 11_016), (2025, 9, 6, 20_337), (1969, 12, 31, -1)] {
+                let date = Date::from_ymd_opt(year, month, day).unwrap();
+                assert_eq!(days_from_civil(date), days);
+                assert_eq!(civil_from_days(days), (year, month, day));
+            }
+            assert_eq!(Date::from_ymd_opt(2024, 2, 28).unwrap().succ_opt(), Date::from_ymd_opt(2024, 2, 29));
+            assert_eq!(Date::from_ymd_opt(2025, 12, 31).unwrap().succ_opt(), Date::from_ymd_opt(2026, 1, 1));
+            assert!(Date::from_ymd_opt(2025, 2, 29).is_none());
+            assert!(Date::from_ymd_opt(2025, 13, 1).is_none());
+        }
+
+        #[test]
+        fn test_date_text_round_trip() {
+            let date = Date::from_ymd_opt(2025, 9, 6).unwrap();
+            assert_eq!(format_date(date), "2025-09-06");
+            assert_eq!(parse_date("2025-09-06"), Some(date));
+            assert_eq!(parse_date("2025-9-6"), None);
+            assert_eq!(parse_date("2025-09-06-1"), None);
+        }
+
+        #[test]
+        fn test_utc_fields_of_built_instant() {
+            let instant = timestamp_from_utc(2025, 9, 6, 23, 59, 59).unwrap();
+            assert_eq!(utc_fields(instant), (Date::from_ymd_opt(2025, 9, 6).unwrap(), 23, 59, 59));
+            assert_eq!(date_of(instant + Duration::from_secs(1), false), Date::from_ymd_opt(2025, 9, 7).unwrap());
+
+            let before_epoch = timestamp_from_utc(1969, 12, 31, 23, 59, 58).unwrap();
+            assert_eq!(unix_seconds(before_epoch), -2);
+            assert_eq!(utc_fields(before_epoch).1, 23);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Write buffer for the file writers of FreedomLogger
+//!
+//! With `LoggerConfig::buffered` the text and JSON writers collect formatted
+//! entries in memory and write them to the file in one go, instead of opening
+//! and writing the file for every entry.
+//!
+//! Buffered output reaches the file when:
+//! - the buffer reaches its capacity
+//! - the logger is flushed (`Logger::flush()`, shutdown, drop, before rotation)
+//! - an entry at or above `LoggerConfig::flush_on_level` is logged
+//!
+//! Entries still in the buffer are lost if the process crashes.
+
+use std::sync::Mutex;
+
+/// Default buffer capacity in bytes
+pub const DEFAULT_BUFFER_CAPACITY: usize = 8 * 1024;
+
+/// Pending output of a buffered file writer
+#[derive(Debug)]
+pub struct WriteBuffer {
+    /// Size in bytes at which the buffer should be written out
+    capacity: usize,
+    /// Formatted entries not yet written to the file
+    pending: Mutex<String>,
+}
+
+impl WriteBuffer {
+    /// Create an empty buffer
+    ///
+    /// # Arguments
+    /// * `capacity` - Size in bytes at which `push` reports the buffer as full
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            pending: Mutex::new(String::new()),
+        }
+    }
+
+    /// Add output to the buffer
+    ///
+    /// # Returns
+    /// True when the buffer reached its capacity and should be written out
+    pub fn push(&self, text: &str) -> bool {
+        let mut pending = self.pending.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        pending.push_str(text);
+        pending.len() >= self.capacity
+    }
+
+    /// Size in bytes at which the buffer is written out
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Bytes waiting to be written
+    pub fn len(&self) -> usize {
+        self.pending.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).len()
+    }
+
+    /// Check if nothing is waiting to be written
+    pub fn is_empty(&self) -> bool {
+        self.pending.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).is_empty()
+    }
+
+    /// Remove and return all pending output
+    pub fn take(&self) -> String {
+        std::mem::take(&mut *self.pending.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+    }
+}
+
+impl Default for WriteBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_BUFFER_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_reports_full_at_capacity() {
+        let buffer = WriteBuffer::new(10);
+        assert!(!buffer.push("12345"));
+        assert_eq!(buffer.len(), 5);
+        assert!(buffer.push("67890"));
+
+        assert_eq!(buffer.take(), "1234567890");
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.len(), 0);
+    }
+}
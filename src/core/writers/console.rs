@@ -0,0 +1,98 @@
+//! Console writer for FreedomLogger
+//!
+//! Writes formatted log messages to standard output (or standard error)
+//! instead of a file. Useful for CLI tools and containers where logs are
+//! collected from stdout, and for tools whose stdout carries their results.
+//! With color enabled each line is colored by level (`LogLevel::ansi_color`).
+
+use std::io::{self, Write};
+use std::path::Path;
+use crate::core::config::ANSI_RESET;
+use crate::error::{LoggerError, LoggerResult};
+use crate::format::LogInfo;
+use super::LogWriter;
+
+/// Console writer for plain text output on stdout or stderr
+#[derive(Debug)]
+pub struct ConsoleWriter {
+    /// Write to stderr instead of stdout
+    stderr: bool,
+    /// Color lines by level with ANSI escape codes
+    color: bool,
+}
+
+impl Default for ConsoleWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConsoleWriter {
+    /// Create a new console writer instance (stdout)
+    pub fn new() -> Self {
+        Self { stderr: false, color: false }
+    }
+
+    /// Create a console writer for stderr
+    pub fn stderr() -> Self {
+        Self { stderr: true, color: false }
+    }
+
+    /// Color each line by level (`LogLevel::ansi_color`)
+    pub fn with_color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Write a formatted log message to stdout (or stderr)
+    ///
+    /// # Arguments
+    /// * `message` - The fully formatted log message to write
+    ///
+    /// # Returns
+    /// Ok(()) on success, LoggerError on failure (e.g. closed stdout)
+    pub fn write_message(&self, message: &str) -> LoggerResult<()> {
+        let (result, stream) = if self.stderr {
+            (write_line(&mut io::stderr().lock(), message), "<stderr>")
+        } else {
+            (write_line(&mut io::stdout().lock(), message), "<stdout>")
+        };
+        result.map_err(|_| LoggerError::DiskFull {
+            path: stream.to_string(),
+            bytes_attempted: message.len() + 1,
+        })
+    }
+}
+
+/// Write one line and flush it
+fn write_line(handle: &mut impl Write, message: &str) -> io::Result<()> {
+    writeln!(handle, "{}", message)?;
+    handle.flush()
+}
+
+impl LogWriter for ConsoleWriter {
+    fn write_entry(&self, info: &LogInfo, formatted: &str, _path: &Path) -> LoggerResult<()> {
+        if self.color {
+            self.write_message(&colorize(info, formatted))
+        } else {
+            self.write_message(formatted)
+        }
+    }
+}
+
+/// Wrap a formatted line in the color codes of its level
+fn colorize(info: &LogInfo, formatted: &str) -> String {
+    format!("{}{}{}", info.level.ansi_color(), formatted, ANSI_RESET)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::LogLevel;
+
+    #[test]
+    fn test_colorize_uses_level_color() {
+        let info = LogInfo::new("disk full", LogLevel::Error, "2025-09-06 15:30:45");
+        assert_eq!(colorize(&info, "ERROR: disk full"), "\x1b[31mERROR: disk full\x1b[0m");
+    }
+}
@@ -0,0 +1,129 @@
+//! Windows Event Log writer for FreedomLogger (`eventlog` feature)
+//!
+//! Reports each entry to the Windows Event Log under an event source name,
+//! so Windows services show up in Event Viewer next to other services.
+//! Selected with `Destination::EventLog(source)`.
+//!
+//! Levels map to event types: ERROR -> Error, WARNING -> Warning,
+//! everything else -> Information.
+//!
+//! The event source is registered on the first entry. If registration or
+//! reporting fails, the error is routed to the error log and the other
+//! destinations keep working.
+
+use crate::core::config::LogLevel;
+
+/// Event Log entry type for a level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventType {
+    Error,
+    Warning,
+    Information,
+}
+
+/// Map a log level to its Event Log entry type
+pub fn event_type(level: LogLevel) -> EventType {
+    match level {
+        LogLevel::Error => EventType::Error,
+        LogLevel::Warning => EventType::Warning,
+        _ => EventType::Information,
+    }
+}
+
+#[cfg(windows)]
+pub use platform::WindowsEventWriter;
+
+#[cfg(windows)]
+#[allow(unsafe_code)]
+mod platform {
+    use std::path::Path;
+    use std::sync::Mutex;
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::Foundation::HANDLE;
+    use windows::Win32::System::EventLog::{
+        DeregisterEventSource, RegisterEventSourceW, ReportEventW, EVENTLOG_ERROR_TYPE,
+        EVENTLOG_INFORMATION_TYPE, EVENTLOG_WARNING_TYPE, REPORT_EVENT_TYPE,
+    };
+    use crate::core::writers::LogWriter;
+    use crate::error::{LoggerError, LoggerResult};
+    use crate::format::LogInfo;
+    use super::{event_type, EventType};
+
+    /// Writer reporting entries to the Windows Event Log
+    #[derive(Debug)]
+    pub struct WindowsEventWriter {
+        /// Event source name shown in Event Viewer
+        source: String,
+        /// Registered event source handle (as an address, so the writer is Send + Sync)
+        handle: Mutex<Option<usize>>,
+    }
+
+    impl WindowsEventWriter {
+        /// Create a writer for the given event source (registered on first use)
+        pub fn new(source: &str) -> Self {
+            Self {
+                source: source.to_string(),
+                handle: Mutex::new(None),
+            }
+        }
+
+        /// Get the event source handle, registering the source if needed
+        fn event_source(&self) -> LoggerResult<HANDLE> {
+            let mut handle = self.handle.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            if let Some(address) = *handle {
+                return Ok(HANDLE(address as *mut _));
+            }
+
+            let registered = unsafe { RegisterEventSourceW(PCWSTR::null(), &HSTRING::from(self.source.as_str())) }
+                .map_err(|error| self.error(format!("Failed to register event source: {}", error)))?;
+            *handle = Some(registered.0 as usize);
+            Ok(registered)
+        }
+
+        fn error(&self, reason: String) -> LoggerError {
+            LoggerError::FileCreationFailed {
+                path: format!("<eventlog:{}>", self.source),
+                reason,
+            }
+        }
+    }
+
+    impl LogWriter for WindowsEventWriter {
+        fn write_entry(&self, info: &LogInfo, formatted: &str, _path: &Path) -> LoggerResult<()> {
+            let event_source = self.event_source()?;
+            let kind: REPORT_EVENT_TYPE = match event_type(info.level) {
+                EventType::Error => EVENTLOG_ERROR_TYPE,
+                EventType::Warning => EVENTLOG_WARNING_TYPE,
+                EventType::Information => EVENTLOG_INFORMATION_TYPE,
+            };
+
+            let message = HSTRING::from(formatted);
+            let strings = [PCWSTR(message.as_ptr())];
+            unsafe { ReportEventW(event_source, kind, 0, 0, None, 0, Some(&strings), None) }
+                .map_err(|error| self.error(format!("Failed to report event: {}", error)))
+        }
+    }
+
+    impl Drop for WindowsEventWriter {
+        fn drop(&mut self) {
+            let handle = self.handle.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+            if let Some(address) = handle {
+                let _ = unsafe { DeregisterEventSource(HANDLE(address as *mut _)) };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_to_event_type() {
+        assert_eq!(event_type(LogLevel::Error), EventType::Error);
+        assert_eq!(event_type(LogLevel::Warning), EventType::Warning);
+        assert_eq!(event_type(LogLevel::Info), EventType::Information);
+        assert_eq!(event_type(LogLevel::Debug), EventType::Information);
+        assert_eq!(event_type(LogLevel::Trace), EventType::Information);
+    }
+}
@@ -0,0 +1,214 @@
+//! Console fallback for a failing file writer in FreedomLogger
+//!
+//! With `LoggerConfig::console_fallback_after` the file writer is wrapped in
+//! a `FallbackWriter`. After that many consecutive failed writes (disk gone,
+//! permissions changed, ...) entries are written to stderr instead of being
+//! lost, with a one-time warning about the switch. The file is retried once
+//! per retry interval; the first successful write switches back.
+//!
+//! Every failed file write is still returned as an error, so it reaches
+//! `on_error` and the (rate limited) error log as before.
+
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use crate::core::time::Instant;
+use crate::core::config::LogLevel;
+use crate::error::LoggerResult;
+use crate::format::{LogInfo, format_basic};
+use super::{ConsoleWriter, LogWriter};
+
+/// Default time between two attempts to write to the file again
+pub const DEFAULT_FALLBACK_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Writer switching to a fallback writer while its primary writer fails
+pub struct FallbackWriter {
+    /// Writer used normally (the file writer)
+    primary: Box<dyn LogWriter>,
+    /// Writer used while the primary fails (stderr by default)
+    fallback: Box<dyn LogWriter>,
+    /// Consecutive primary failures before switching
+    max_failures: u32,
+    /// Time between two attempts of the primary while switched
+    retry_interval: Duration,
+    /// Failure count and switch state
+    state: Mutex<FallbackState>,
+}
+
+/// Consecutive failures and, while switched, the next primary attempt
+#[derive(Debug, Default)]
+struct FallbackState {
+    failures: u32,
+    next_retry: Option<Instant>,
+}
+
+impl FallbackWriter {
+    /// Wrap `primary`, falling back to stderr after `max_failures` failures in a row
+    pub fn new(primary: Box<dyn LogWriter>, max_failures: u32) -> Self {
+        Self::with_fallback(primary, Box::new(ConsoleWriter::stderr()), max_failures)
+    }
+
+    /// Wrap `primary` with a custom fallback writer
+    pub fn with_fallback(primary: Box<dyn LogWriter>, fallback: Box<dyn LogWriter>, max_failures: u32) -> Self {
+        Self {
+            primary,
+            fallback,
+            max_failures,
+            retry_interval: DEFAULT_FALLBACK_RETRY_INTERVAL,
+            state: Mutex::new(FallbackState::default()),
+        }
+    }
+
+    /// Set the time between two attempts of the primary while switched
+    pub fn with_retry_interval(mut self, retry_interval: Duration) -> Self {
+        self.retry_interval = retry_interval;
+        self
+    }
+
+    /// Check if entries currently go to the fallback writer
+    pub fn is_falling_back(&self) -> bool {
+        self.lock_state().next_retry.is_some()
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, FallbackState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Write a notice about a switch to the fallback writer (best effort)
+    fn notice(&self, info: &LogInfo, message: &str, path: &Path) {
+        let notice = LogInfo::new(message, LogLevel::Warning, info.timestamp);
+        let _ = self.fallback.write_entry(&notice, &format_basic(&notice), path);
+    }
+}
+
+impl std::fmt::Debug for FallbackWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FallbackWriter")
+            .field("max_failures", &self.max_failures)
+            .field("retry_interval", &self.retry_interval)
+            .field("state", &self.state)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LogWriter for FallbackWriter {
+    fn write_entry(&self, info: &LogInfo, formatted: &str, path: &Path) -> LoggerResult<()> {
+        let mut state = self.lock_state();
+        let now = Instant::now();
+        if state.next_retry.is_some_and(|next_retry| now < next_retry) {
+            return self.fallback.write_entry(info, formatted, path);
+        }
+
+        match self.primary.write_entry(info, formatted, path) {
+            Ok(()) => {
+                state.failures = 0;
+                if state.next_retry.take().is_some() {
+                    self.notice(info, &format!("FreedomLogger: writing to {} again", path.display()), path);
+                }
+                Ok(())
+            }
+            Err(error) => {
+                state.failures = state.failures.saturating_add(1);
+                if state.failures >= self.max_failures {
+                    if state.next_retry.is_none() {
+                        self.notice(info, &format!(
+                            "FreedomLogger: {} failed {} times in a row, writing to the fallback until it recovers",
+                            path.display(),
+                            state.failures,
+                        ), path);
+                    }
+                    state.next_retry = Some(now + self.retry_interval);
+                    let _ = self.fallback.write_entry(info, formatted, path);
+                }
+                Err(error)
+            }
+        }
+    }
+
+    fn flush(&self, path: &Path) -> LoggerResult<()> {
+        let _ = self.fallback.flush(path);
+        self.primary.flush(path)
+    }
+
+    fn buffered_bytes(&self) -> usize {
+        self.primary.buffered_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use crate::core::writers::MemoryWriter;
+    use crate::error::LoggerError;
+
+    /// Primary writer failing while `broken` is set
+    struct Switchable {
+        broken: Arc<AtomicBool>,
+    }
+
+    impl LogWriter for Switchable {
+        fn write_entry(&self, _info: &LogInfo, _formatted: &str, path: &Path) -> LoggerResult<()> {
+            if self.broken.load(Ordering::Relaxed) {
+                return Err(LoggerError::DiskFull { path: path.display().to_string(), bytes_attempted: 0 });
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_switches_after_consecutive_failures_and_back() {
+        let broken = Arc::new(AtomicBool::new(true));
+        let memory = Arc::new(MemoryWriter::new(20));
+        let writer = FallbackWriter::with_fallback(
+            Box::new(Switchable { broken: Arc::clone(&broken) }),
+            Box::new(Arc::clone(&memory)),
+            2,
+        )
+        .with_retry_interval(Duration::ZERO);
+        let path = Path::new("app.log");
+        let write = |message: &str| {
+            let info = LogInfo::new(message, LogLevel::Info, "2025-09-06 15:30:45");
+            writer.write_entry(&info, message, path)
+        };
+
+        assert!(write("first").is_err());
+        assert!(!writer.is_falling_back());
+        assert!(memory.lines().is_empty());
+
+        assert!(write("second").is_err());
+        assert!(write("third").is_err());
+        assert!(writer.is_falling_back());
+        let lines = memory.lines();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].contains("WARNING: FreedomLogger: app.log failed 2 times in a row"));
+        assert_eq!(lines[1..], ["second".to_string(), "third".to_string()]);
+
+        broken.store(false, Ordering::Relaxed);
+        assert!(write("fourth").is_ok());
+        assert!(!writer.is_falling_back());
+        assert!(memory.lines()[3].contains("FreedomLogger: writing to app.log again"));
+        assert_eq!(memory.lines().len(), 4);
+    }
+
+    #[test]
+    fn test_fallback_used_until_retry_is_due() {
+        let broken = Arc::new(AtomicBool::new(true));
+        let memory = Arc::new(MemoryWriter::new(20));
+        let writer = FallbackWriter::with_fallback(
+            Box::new(Switchable { broken: Arc::clone(&broken) }),
+            Box::new(Arc::clone(&memory)),
+            1,
+        );
+        let info = LogInfo::new("entry", LogLevel::Info, "2025-09-06 15:30:45");
+
+        assert!(writer.write_entry(&info, "entry", Path::new("app.log")).is_err());
+        broken.store(false, Ordering::Relaxed);
+
+        // Not retried yet: the entry goes to the fallback without an error
+        assert!(writer.write_entry(&info, "entry", Path::new("app.log")).is_ok());
+        assert!(writer.is_falling_back());
+        assert_eq!(memory.lines().len(), 3);
+    }
+}
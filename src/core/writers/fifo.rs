@@ -0,0 +1,245 @@
+//! Named pipe (FIFO) writer for FreedomLogger (Unix only)
+//!
+//! Writes formatted lines into a FIFO read by an external log shipper.
+//! Unlike the file writers, the FIFO is opened once (and reopened only after
+//! the reader went away) with `O_NONBLOCK`, so logging never waits for a
+//! reader to connect or to catch up:
+//! - No reader connected: the entry is not delivered
+//! - Pipe full (`WouldBlock`): the entry is not delivered
+//!
+//! Undelivered entries are dropped by default. With a buffer limit they are
+//! kept (up to that many bytes) and sent on the next write or flush; entries
+//! that don't fit are dropped. Lines up to PIPE_BUF (4096 bytes on Linux)
+//! are written atomically, so a reader never sees half an entry.
+//!
+//! Entries end with "\n" by default; with `Framing::LengthPrefixed` each is
+//! preceded by its 4-byte big-endian length instead.
+//!
+//! Selected with `Destination::Fifo(path)`; the buffer limit comes from
+//! `LoggerConfig::fifo_buffer_size`, the framing from `LoggerConfig::fifo_framing`.
+
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use crate::core::config::Framing;
+use crate::error::{IoResultExt, LoggerResult};
+use crate::format::LogInfo;
+use super::LogWriter;
+
+/// Open FIFO and the bytes not yet delivered
+#[derive(Debug, Default)]
+struct FifoState {
+    /// Write end of the FIFO (None until a reader is connected)
+    file: Option<File>,
+    /// Undelivered bytes (only kept with a buffer limit)
+    pending: Vec<u8>,
+}
+
+/// Writer for a named pipe that never blocks the caller
+#[derive(Debug)]
+pub struct FifoWriter {
+    /// Path of the FIFO (created beforehand, e.g. with mkfifo)
+    path: PathBuf,
+    /// Maximum undelivered bytes kept; None drops them
+    buffer_limit: Option<usize>,
+    /// Delimiting of entries in the stream
+    framing: Framing,
+    /// Open handle and pending bytes
+    state: Mutex<FifoState>,
+}
+
+impl FifoWriter {
+    /// Create a writer for the FIFO at `path` (opened on the first write)
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            buffer_limit: None,
+            framing: Framing::Newline,
+            state: Mutex::new(FifoState::default()),
+        }
+    }
+
+    /// Keep undelivered entries instead of dropping them
+    ///
+    /// # Arguments
+    /// * `buffer_limit` - Maximum bytes kept, or None to drop undelivered entries
+    pub fn with_buffer_limit(mut self, buffer_limit: Option<usize>) -> Self {
+        self.buffer_limit = buffer_limit;
+        self
+    }
+
+    /// Delimit entries with `framing` instead of a newline
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = framing;
+        self
+    }
+
+    /// Write one formatted line to the FIFO
+    ///
+    /// # Returns
+    /// Ok(()) also when the line was dropped (no reader, pipe full);
+    /// LoggerError only if the FIFO can't be opened or written for another reason
+    pub fn write_line(&self, line: &str) -> LoggerResult<()> {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let frame_len = line.len() + self.framing.overhead();
+        let fits = self.buffer_limit.is_none_or(|limit| state.pending.len() + frame_len <= limit);
+
+        if fits {
+            self.framing.encode(line, &mut state.pending);
+        }
+        let result = self.deliver(&mut state);
+
+        if self.buffer_limit.is_none() {
+            state.pending.clear();
+        }
+        result
+    }
+
+    /// Send as many pending bytes as the FIFO accepts right now
+    fn deliver(&self, state: &mut FifoState) -> LoggerResult<()> {
+        let FifoState { file, pending } = state;
+        while !pending.is_empty() {
+            let Some(open_file) = self.open(file)? else {
+                return Ok(());
+            };
+
+            match open_file.write(pending) {
+                Ok(written) => {
+                    pending.drain(..written);
+                }
+                Err(error) if error.kind() == ErrorKind::WouldBlock => return Ok(()),
+                Err(error) if error.kind() == ErrorKind::Interrupted => {}
+                Err(error) if error.kind() == ErrorKind::BrokenPipe => {
+                    // The reader went away: reopen once another one connects
+                    *file = None;
+                    return Ok(());
+                }
+                Err(error) => return Err(error).with_write(&self.path, pending.len()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the open FIFO, opening it if a reader is connected
+    ///
+    /// # Returns
+    /// None if there is no reader yet
+    fn open<'a>(&self, file: &'a mut Option<File>) -> LoggerResult<Option<&'a mut File>> {
+        if file.is_none() {
+            let opened = OpenOptions::new()
+                .write(true)
+                .custom_flags(libc::O_NONBLOCK)
+                .open(&self.path);
+            match opened {
+                Ok(opened) => *file = Some(opened),
+                // ENXIO: a FIFO opened for writing without a reader
+                Err(error) if error.raw_os_error() == Some(libc::ENXIO) => return Ok(None),
+                Err(error) => return Err(error).with_path(&self.path),
+            }
+        }
+        Ok(file.as_mut())
+    }
+}
+
+impl LogWriter for FifoWriter {
+    fn write_entry(&self, _info: &LogInfo, formatted: &str, _path: &Path) -> LoggerResult<()> {
+        self.write_line(formatted)
+    }
+
+    /// Tries once more to deliver buffered entries
+    fn flush(&self, _path: &Path) -> LoggerResult<()> {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.deliver(&mut state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{Destination, LoggerConfig, Pattern};
+    use crate::core::logger::Logger;
+    use std::ffi::CString;
+    use std::io::Read;
+    use std::os::unix::ffi::OsStrExt;
+    use std::thread;
+    use std::time::{Duration, Instant};
+    use tempfile::tempdir;
+
+    /// Create a FIFO at `path`
+    #[allow(unsafe_code)]
+    fn make_fifo(path: &Path) {
+        let c_path = CString::new(path.as_os_str().as_bytes()).unwrap();
+        assert_eq!(unsafe { libc::mkfifo(c_path.as_ptr(), 0o600) }, 0);
+    }
+
+    /// Open the read end without waiting for a writer
+    fn open_reader(path: &Path) -> File {
+        OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(path)
+            .unwrap()
+    }
+
+    /// Read from the FIFO on a thread until `expected` lines arrived (or 5s passed)
+    fn read_lines(mut reader: File, expected: usize) -> thread::JoinHandle<String> {
+        thread::spawn(move || {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            let mut received = Vec::new();
+            let mut chunk = [0u8; 1024];
+            while received.iter().filter(|&&byte| byte == b'\n').count() < expected && Instant::now() < deadline {
+                match reader.read(&mut chunk) {
+                    Ok(read) if read > 0 => received.extend_from_slice(&chunk[..read]),
+                    _ => thread::sleep(Duration::from_millis(5)),
+                }
+            }
+            String::from_utf8(received).unwrap()
+        })
+    }
+
+    #[test]
+    fn test_fifo_delivers_lines_to_reader() {
+        let temp_dir = tempdir().unwrap();
+        let fifo_path = temp_dir.path().join("app.fifo");
+        make_fifo(&fifo_path);
+
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.destinations = vec![Destination::Fifo(fifo_path.clone())];
+        let logger = Logger::new(config);
+
+        let reader = read_lines(open_reader(&fifo_path), 2);
+        logger.info("First");
+        logger.warning("Second");
+
+        let received = reader.join().unwrap();
+        let lines: Vec<&str> = received.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("INFO: First"));
+        assert!(lines[1].ends_with("WARNING: Second"));
+        assert!(!temp_dir.path().join("app.log").exists());
+    }
+
+    #[test]
+    fn test_fifo_without_reader_drops_or_buffers() {
+        let temp_dir = tempdir().unwrap();
+        let fifo_path = temp_dir.path().join("app.fifo");
+        make_fifo(&fifo_path);
+
+        // Neither writer blocks while nobody reads
+        let dropping = FifoWriter::new(&fifo_path);
+        dropping.write_line("INFO: Dropped").unwrap();
+        let buffering = FifoWriter::new(&fifo_path).with_buffer_limit(Some(1024));
+        buffering.write_line("INFO: Buffered").unwrap();
+
+        let reader = read_lines(open_reader(&fifo_path), 2);
+        buffering.flush(&fifo_path).unwrap();
+        dropping.write_line("INFO: Delivered").unwrap();
+
+        let received = reader.join().unwrap();
+        assert!(received.contains("INFO: Buffered\n"));
+        assert!(received.contains("INFO: Delivered\n"));
+        assert!(!received.contains("Dropped"));
+    }
+}
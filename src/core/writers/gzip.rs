@@ -0,0 +1,165 @@
+//! Compressed log file writer for FreedomLogger (`compression` feature)
+//!
+//! With `LoggerConfig::compress_live` the log file itself is gzip
+//! compressed while it is written (app.log.gz), for services that write
+//! more than the disk can hold uncompressed.
+//!
+//! The file stays open between entries and the encoder buffers compressed
+//! data. `flush()` (called by `Logger::flush()`, before every rotation and on
+//! drop) finishes the gzip member and closes the file; the next entry starts
+//! a new member appended to the same file. A file made of several members is
+//! still one valid .gz file (`gunzip`, `zcat`, `MultiGzDecoder`).
+//!
+//! Entries still in the encoder are lost on a crash, and the last member of
+//! a file being written can only be read after a flush.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use crate::core::config::LineEnding;
+use crate::error::{IoResultExt, LoggerError, LoggerResult};
+use crate::format::LogInfo;
+use super::{LogWriter, append_options};
+
+/// Open gzip member of the current file
+#[derive(Debug)]
+struct LiveMember {
+    /// File the member is written to
+    path: PathBuf,
+    /// Compressor writing into the file
+    encoder: GzEncoder<File>,
+}
+
+/// Gzip compressing writer for the formatted lines of any pattern
+#[derive(Debug)]
+pub struct GzipWriter {
+    /// Unix permissions for newly created log files (None = system default)
+    file_mode: Option<u32>,
+    /// Terminator appended to every line
+    line_ending: LineEnding,
+    /// Member being written (None until the first entry and after a flush)
+    member: Mutex<Option<LiveMember>>,
+}
+
+impl Default for GzipWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GzipWriter {
+    /// Create a new gzip writer instance
+    pub fn new() -> Self {
+        Self { file_mode: None, line_ending: LineEnding::Lf, member: Mutex::new(None) }
+    }
+
+    /// Set the Unix permissions of newly created log files (e.g. 0o600)
+    ///
+    /// No effect on other platforms.
+    pub fn with_file_mode(mut self, file_mode: Option<u32>) -> Self {
+        self.file_mode = file_mode;
+        self
+    }
+
+    /// Terminate lines with "\r\n" instead of "\n" (or back)
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Compress a formatted log message into the file
+    ///
+    /// Opens a new member when nothing is open or the path changed
+    /// (finishing the member of the previous path first).
+    ///
+    /// # Arguments
+    /// * `message` - The fully formatted log message to write
+    /// * `file_path` - Full path to the compressed log file
+    pub fn write_message(&self, message: &str, file_path: &Path) -> LoggerResult<()> {
+        let mut member = self.member.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if member.as_ref().is_some_and(|open| open.path != file_path) {
+            Self::finish(member.take())?;
+        }
+
+        let open = match member.as_mut() {
+            Some(open) => open,
+            None => member.insert(self.open(file_path)?),
+        };
+        let line = format!("{}{}", message, self.line_ending.as_str());
+        open.encoder.write_all(line.as_bytes()).with_write(file_path, line.len())
+    }
+
+    /// Finish the open member and close the file
+    pub fn finish_member(&self) -> LoggerResult<()> {
+        let mut member = self.member.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        Self::finish(member.take())
+    }
+
+    /// Start a member at the end of the file (creating it and its directory)
+    fn open(&self, file_path: &Path) -> LoggerResult<LiveMember> {
+        if let Some(parent_dir) = file_path.parent() {
+            if !parent_dir.exists() {
+                fs::create_dir_all(parent_dir).map_err(|_| LoggerError::DirectoryCreationFailed {
+                    path: parent_dir.display().to_string(),
+                    reason: "Failed to create parent directories".to_string(),
+                })?;
+            }
+        }
+        let file = append_options(self.file_mode).open(file_path).with_path(file_path)?;
+        Ok(LiveMember {
+            path: file_path.to_path_buf(),
+            encoder: GzEncoder::new(file, Compression::default()),
+        })
+    }
+
+    /// Write the gzip trailer of a member
+    fn finish(member: Option<LiveMember>) -> LoggerResult<()> {
+        match member {
+            Some(LiveMember { path, encoder }) => encoder.finish().map(drop).with_write(&path, 0),
+            None => Ok(()),
+        }
+    }
+}
+
+impl LogWriter for GzipWriter {
+    fn write_entry(&self, _info: &LogInfo, formatted: &str, path: &Path) -> LoggerResult<()> {
+        self.write_message(formatted, path)
+    }
+
+    /// Completes the gzip member, so the file is readable (and can be rotated)
+    fn flush(&self, _path: &Path) -> LoggerResult<()> {
+        self.finish_member()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use flate2::read::MultiGzDecoder;
+    use tempfile::tempdir;
+
+    fn decompress(path: &Path) -> String {
+        let mut content = String::new();
+        MultiGzDecoder::new(File::open(path).unwrap()).read_to_string(&mut content).unwrap();
+        content
+    }
+
+    #[test]
+    fn test_members_are_appended_after_flush() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("app.log.gz");
+
+        let writer = GzipWriter::new();
+        writer.write_message("first", &log_path).unwrap();
+        writer.write_message("second", &log_path).unwrap();
+        writer.finish_member().unwrap();
+        writer.write_message("third", &log_path).unwrap();
+        writer.finish_member().unwrap();
+
+        assert_eq!(decompress(&log_path), "first\nsecond\nthird\n");
+    }
+}
@@ -0,0 +1,263 @@
+//! HTML file writer for FreedomLogger
+//!
+//! Writes the rows of `Pattern::Html` into one HTML table per file (.html
+//! extension), for embedding logs in a web report. The table is opened by the
+//! first entry and closed by `flush()` (called by `Logger::flush()`,
+//! `Logger::shutdown()`, before rotation and on drop). Writing again after a
+//! flush removes the closing tag first, so the file stays one table.
+//!
+//! A hard crash skips the flush and leaves the table unterminated; browsers
+//! still show it, and appending `</table>` repairs the file.
+
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use crate::core::config::LineEnding;
+use crate::error::{IoResultExt, LoggerError, LoggerResult};
+use crate::format::{LogInfo, HTML_TABLE_END, HTML_TABLE_START};
+use super::{LogWriter, WriteBuffer, append_entry, append_options};
+
+/// HTML file writer keeping all rows of a file in one table
+#[derive(Debug)]
+pub struct HtmlWriter {
+    /// Unix permissions for newly created log files (None = system default)
+    file_mode: Option<u32>,
+    /// Pending rows when buffering is enabled
+    buffer: Option<WriteBuffer>,
+    /// Terminator of every line (rows and table tags)
+    line_ending: LineEnding,
+    /// Start new files with the UTF-8 byte order mark
+    utf8_bom: bool,
+}
+
+impl Default for HtmlWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HtmlWriter {
+    /// Create a new HTML writer instance
+    pub fn new() -> Self {
+        Self { file_mode: None, buffer: None, line_ending: LineEnding::Lf, utf8_bom: false }
+    }
+
+    /// Set the Unix permissions of newly created log files (e.g. 0o600)
+    ///
+    /// No effect on other platforms.
+    pub fn with_file_mode(mut self, file_mode: Option<u32>) -> Self {
+        self.file_mode = file_mode;
+        self
+    }
+
+    /// Collect rows in memory and write them in batches (see `WriteBuffer`)
+    ///
+    /// # Arguments
+    /// * `capacity` - Buffer size in bytes, or None to write every row directly
+    pub fn with_buffer(mut self, capacity: Option<usize>) -> Self {
+        self.buffer = capacity.map(WriteBuffer::new);
+        self
+    }
+
+    /// Terminate lines with "\r\n" instead of "\n" (or back)
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Start new files with the UTF-8 byte order mark (`UTF8_BOM`)
+    pub fn with_utf8_bom(mut self, utf8_bom: bool) -> Self {
+        self.utf8_bom = utf8_bom;
+        self
+    }
+
+    /// Write one formatted row to the table in the file
+    ///
+    /// # Arguments
+    /// * `row` - A `<tr>` row from `format_html`
+    /// * `file_path` - Full path to the HTML log file
+    ///
+    /// # Returns
+    /// Ok(()) on success, LoggerError on failure
+    pub fn write_row(&self, row: &str, file_path: &Path) -> LoggerResult<()> {
+        // The table only needs opening when nothing is waiting in the buffer
+        // (a non-empty buffer already started or continued the table)
+        let prefix = match &self.buffer {
+            Some(buffer) if !buffer.is_empty() => String::new(),
+            _ => self.open_table(&self.open_file(file_path, row.len())?, file_path)?,
+        };
+        let entry = format!("{}{}{}", prefix, row, self.line_ending.as_str());
+
+        match &self.buffer {
+            Some(buffer) => {
+                if buffer.push(&entry) {
+                    self.write_pending(file_path)?;
+                }
+                Ok(())
+            }
+            None => self.append_to_file(&entry, file_path),
+        }
+    }
+
+    /// Write all buffered rows to the file (no-op without buffering)
+    pub fn write_pending(&self, file_path: &Path) -> LoggerResult<()> {
+        let pending = match &self.buffer {
+            Some(buffer) => buffer.take(),
+            None => return Ok(()),
+        };
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+        self.append_to_file(&pending, file_path)
+    }
+
+    /// Close the table, unless the file is missing, empty or already closed
+    ///
+    /// # Arguments
+    /// * `file_path` - Full path to the HTML log file
+    pub fn close_table(&self, file_path: &Path) -> LoggerResult<()> {
+        let file = match fs::OpenOptions::new().read(true).append(true).open(file_path) {
+            Ok(file) => file,
+            Err(_) => return Ok(()), // Nothing written yet
+        };
+
+        let table_end = self.table_end();
+        let len = file.metadata().with_write(file_path, table_end.len())?.len();
+        if len == 0 || self.ends_with_table_end(&file, len).with_write(file_path, table_end.len())? {
+            return Ok(());
+        }
+        append_entry(&file, &table_end, false, file_path)
+    }
+
+    /// Open the HTML file in append mode (readable to inspect the table end)
+    fn open_file(&self, file_path: &Path, bytes_attempted: usize) -> LoggerResult<File> {
+        self.ensure_directory_exists(file_path)?;
+        append_options(self.file_mode)
+            .read(true)
+            .open(file_path)
+            .with_write(file_path, bytes_attempted)
+    }
+
+    /// Append text to the file in one write
+    fn append_to_file(&self, text: &str, file_path: &Path) -> LoggerResult<()> {
+        let file = self.open_file(file_path, text.len())?;
+        append_entry(&file, text, self.utf8_bom, file_path)
+    }
+
+    /// Prepare the file for the next row
+    ///
+    /// An empty file starts the table, a closed table is reopened by removing
+    /// the closing tag, and an open table needs nothing.
+    ///
+    /// # Returns
+    /// Text to write before the row
+    fn open_table(&self, file: &File, file_path: &Path) -> LoggerResult<String> {
+        let read_failed = |_| LoggerError::FileCreationFailed {
+            path: file_path.display().to_string(),
+            reason: "Failed to inspect HTML table file".to_string(),
+        };
+
+        let len = file.metadata().map_err(read_failed)?.len();
+        if len == 0 {
+            return Ok(format!("{}{}", HTML_TABLE_START, self.line_ending.as_str()));
+        }
+
+        if self.ends_with_table_end(file, len).map_err(read_failed)? {
+            file.set_len(len - self.table_end().len() as u64).map_err(read_failed)?;
+        }
+        Ok(String::new())
+    }
+
+    /// Written by `flush()` to close the table
+    fn table_end(&self) -> String {
+        format!("{}{}", HTML_TABLE_END, self.line_ending.as_str())
+    }
+
+    /// Check if the file ends with the closing tag written by `flush()`
+    fn ends_with_table_end(&self, mut file: &File, len: u64) -> std::io::Result<bool> {
+        let table_end = self.table_end();
+        let end_len = table_end.len() as u64;
+        if len < end_len {
+            return Ok(false);
+        }
+
+        let mut tail = vec![0u8; table_end.len()];
+        file.seek(SeekFrom::Start(len - end_len))?;
+        file.read_exact(&mut tail)?;
+        Ok(tail == table_end.as_bytes())
+    }
+
+    /// Ensure the directory for the HTML file exists
+    fn ensure_directory_exists(&self, file_path: &Path) -> LoggerResult<()> {
+        if let Some(parent_dir) = file_path.parent() {
+            if !parent_dir.exists() {
+                fs::create_dir_all(parent_dir)
+                    .map_err(|_| LoggerError::DirectoryCreationFailed {
+                        path: parent_dir.display().to_string(),
+                        reason: "Failed to create parent directories for HTML file".to_string(),
+                    })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl LogWriter for HtmlWriter {
+    /// Writes the pattern-formatted `<tr>` row
+    fn write_entry(&self, _info: &LogInfo, formatted: &str, path: &Path) -> LoggerResult<()> {
+        self.write_row(formatted, path)
+    }
+
+    /// Writes out buffered rows, then closes the table
+    fn flush(&self, path: &Path) -> LoggerResult<()> {
+        self.write_pending(path)?;
+        self.close_table(path)
+    }
+
+    fn buffered_bytes(&self) -> usize {
+        self.buffer.as_ref().map_or(0, WriteBuffer::len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_table_opened_once_closed_on_flush_and_reopened() {
+        let temp_dir = tempdir().unwrap();
+        let html_path = temp_dir.path().join("test.html");
+        let writer = HtmlWriter::new();
+
+        writer.write_row("<tr><td>1</td></tr>", &html_path).unwrap();
+        writer.write_row("<tr><td>2</td></tr>", &html_path).unwrap();
+        writer.flush(&html_path).unwrap();
+        writer.flush(&html_path).unwrap();
+        writer.write_row("<tr><td>3</td></tr>", &html_path).unwrap();
+        writer.flush(&html_path).unwrap();
+
+        let content = fs::read_to_string(&html_path).unwrap();
+        assert_eq!(
+            content,
+            format!("{}\n<tr><td>1</td></tr>\n<tr><td>2</td></tr>\n<tr><td>3</td></tr>\n</table>\n", HTML_TABLE_START)
+        );
+    }
+
+    #[test]
+    fn test_buffered_rows_get_one_table() {
+        let temp_dir = tempdir().unwrap();
+        let html_path = temp_dir.path().join("test.html");
+        let writer = HtmlWriter::new().with_buffer(Some(1024));
+
+        writer.write_row("<tr><td>1</td></tr>", &html_path).unwrap();
+        writer.write_row("<tr><td>2</td></tr>", &html_path).unwrap();
+        assert!(!html_path.exists() || fs::read_to_string(&html_path).unwrap().is_empty());
+        writer.flush(&html_path).unwrap();
+
+        let content = fs::read_to_string(&html_path).unwrap();
+        assert_eq!(content.matches("<table").count(), 1);
+        assert!(content.ends_with("<tr><td>2</td></tr>\n</table>\n"));
+    }
+}
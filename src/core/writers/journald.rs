@@ -0,0 +1,154 @@
+//! journald writer for FreedomLogger (`journald` feature, Unix only)
+//!
+//! Sends each entry to the systemd journal over its native protocol socket
+//! (/run/systemd/journal/socket), with structured fields instead of a
+//! formatted line:
+//! - MESSAGE: the log message
+//! - PRIORITY: syslog priority (ERROR 3, WARNING 4, INFO 6, DEBUG/TRACE 7)
+//! - CODE_FILE / CODE_LINE: call site from LogInfo, when available
+//! - CODE_MODULE: module path, when captured by the macros
+//!
+//! Selected with `Destination::Journald`. When the process doesn't run under
+//! systemd (no journal socket), every write fails with an error routed to the
+//! error log and the other destinations keep working.
+
+use std::os::unix::net::UnixDatagram;
+use std::path::Path;
+use crate::core::config::LogLevel;
+use crate::core::writers::LogWriter;
+use crate::error::{LoggerError, LoggerResult};
+use crate::format::LogInfo;
+
+/// Path of the journald native protocol socket
+pub const JOURNALD_SOCKET: &str = "/run/systemd/journal/socket";
+
+/// Map a log level to its syslog priority
+pub fn priority(level: LogLevel) -> u8 {
+    level.syslog_severity()
+}
+
+/// Encode an entry as a journald native protocol datagram
+///
+/// Single-line values are written as `KEY=value\n`; values containing a
+/// newline use the binary form `KEY\n<length as u64 LE><value>\n`.
+pub fn format_fields(info: &LogInfo) -> Vec<u8> {
+    let mut datagram = Vec::new();
+    push_field(&mut datagram, "MESSAGE", info.message);
+    push_field(&mut datagram, "PRIORITY", &priority(info.level).to_string());
+    if let Some(file) = info.file {
+        push_field(&mut datagram, "CODE_FILE", file);
+    }
+    if let Some(line) = info.line {
+        push_field(&mut datagram, "CODE_LINE", &line.to_string());
+    }
+    if let Some(module) = info.module {
+        push_field(&mut datagram, "CODE_MODULE", module);
+    }
+    datagram
+}
+
+/// Append one field in the native protocol encoding
+fn push_field(datagram: &mut Vec<u8>, key: &str, value: &str) {
+    datagram.extend_from_slice(key.as_bytes());
+    if value.contains('\n') {
+        datagram.push(b'\n');
+        datagram.extend_from_slice(&(value.len() as u64).to_le_bytes());
+    } else {
+        datagram.push(b'=');
+    }
+    datagram.extend_from_slice(value.as_bytes());
+    datagram.push(b'\n');
+}
+
+/// Writer sending entries to the systemd journal
+#[derive(Debug)]
+pub struct JournaldWriter {
+    /// Socket path (the systemd default unless overridden in tests)
+    socket_path: String,
+}
+
+impl Default for JournaldWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JournaldWriter {
+    /// Create a writer for the default journald socket
+    pub fn new() -> Self {
+        Self::with_socket(JOURNALD_SOCKET)
+    }
+
+    /// Create a writer for a specific socket path
+    pub fn with_socket(socket_path: &str) -> Self {
+        Self {
+            socket_path: socket_path.to_string(),
+        }
+    }
+
+    /// Send one datagram to the journal socket
+    fn send(&self, datagram: &[u8]) -> std::io::Result<()> {
+        let socket = UnixDatagram::unbound()?;
+        let sent = socket.send_to(datagram, &self.socket_path)?;
+        if sent < datagram.len() {
+            return Err(std::io::Error::from(std::io::ErrorKind::WriteZero));
+        }
+        Ok(())
+    }
+}
+
+impl LogWriter for JournaldWriter {
+    fn write_entry(&self, info: &LogInfo, _formatted: &str, _path: &Path) -> LoggerResult<()> {
+        let datagram = format_fields(info);
+        self.send(&datagram).map_err(|error| LoggerError::FileCreationFailed {
+            path: self.socket_path.clone(),
+            reason: format!("Failed to send entry to journald: {}", error),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_mapping() {
+        assert_eq!(priority(LogLevel::Error), 3);
+        assert_eq!(priority(LogLevel::Warning), 4);
+        assert_eq!(priority(LogLevel::Info), 6);
+        assert_eq!(priority(LogLevel::Trace), 7);
+    }
+
+    #[test]
+    fn test_field_formatting() {
+        let info = LogInfo::new("Disk almost full", LogLevel::Warning, "ts")
+            .with_location("src/disk.rs", 42)
+            .with_module("app::disk");
+
+        let datagram = String::from_utf8(format_fields(&info)).unwrap();
+        assert_eq!(
+            datagram,
+            "MESSAGE=Disk almost full\nPRIORITY=4\nCODE_FILE=src/disk.rs\nCODE_LINE=42\nCODE_MODULE=app::disk\n"
+        );
+    }
+
+    #[test]
+    fn test_multiline_message_uses_length_prefix() {
+        let info = LogInfo::new("first\nsecond", LogLevel::Info, "ts");
+
+        let datagram = format_fields(&info);
+        let mut expected = b"MESSAGE\n".to_vec();
+        expected.extend_from_slice(&12u64.to_le_bytes());
+        expected.extend_from_slice(b"first\nsecond\nPRIORITY=6\n");
+        assert_eq!(datagram, expected);
+    }
+
+    #[test]
+    fn test_missing_socket_is_an_error() {
+        let writer = JournaldWriter::with_socket("/nonexistent/journal/socket");
+        let info = LogInfo::new("Lost", LogLevel::Info, "ts");
+
+        let result = writer.write_entry(&info, "formatted", Path::new("app.log"));
+        assert!(matches!(result, Err(LoggerError::FileCreationFailed { .. })));
+    }
+}
@@ -1,30 +1,150 @@
-/// JSON file writer for FreedomLogger
-///
-/// Handles writing structured log data to JSON format files (.json extension).
-/// Each log entry is written as a single JSON object per line (JSONL format).
-/// This format is ideal for log aggregation tools, databases, and structured analysis.
-///
-/// Future v2 enhancement: This writer will be extended to support database output
-/// by converting the JSON structure to database inserts.
-
-use std::fs::{self, OpenOptions};
-use std::io::{BufWriter, Write};
+//! JSON file writer for FreedomLogger
+//!
+//! Handles writing structured log data to JSON format files (.json extension).
+//! Each log entry is written as a single JSON object per line (JSONL format).
+//! This format is ideal for log aggregation tools, databases, and structured analysis.
+//!
+//! Array mode writes a single top-level JSON array instead, for tools that
+//! cannot read JSONL. The array is opened by the first entry and closed by
+//! `flush()` (called by `Logger::flush()`, `Logger::shutdown()` and on drop).
+//! Writing again after a flush reopens the array, so the file stays one array.
+//! A hard crash (kill -9, power loss) skips the flush and leaves the array
+//! unterminated; repair such a file by appending a closing `]` line.
+//!
+//! OpenTelemetry mode (`Pattern::OtelJson`) writes the field names of the
+//! OTel log data model instead: Timestamp, SeverityText, SeverityNumber, Body
+//! and an Attributes object holding the call site, thread, process and
+//! context/static fields, for collectors that map these names directly.
+//!
+//! Future v2 enhancement: This writer will be extended to support database output
+//! by converting the JSON structure to database inserts.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::Path;
-use crate::error::{LoggerError, LoggerResult};
-use crate::format::LogInfo;
-use crate::core::config::LogLevel;
+use crate::error::{IoResultExt, LoggerError, LoggerResult};
+use crate::core::config::LineEnding;
+use crate::format::{LogInfo, escape_json_string};
+use super::{LogWriter, WriteBuffer, append_entry, append_options};
+
+/// Indentation used for each field in pretty mode
+const PRETTY_INDENT: &str = "  ";
 
 /// JSON file writer for structured log output
 ///
 /// Outputs each log entry as a JSON object with consistent field structure.
 /// Uses JSONL format (one JSON object per line) for easy parsing by log processors.
+/// Pretty mode writes indented multi-line objects separated by a blank line instead,
+/// which is easier to read but no longer valid JSONL.
+/// Array mode wraps all entries in one JSON array (see module docs).
 #[derive(Debug)]
-pub struct JsonWriter;
+pub struct JsonWriter {
+    /// Write indented multi-line JSON instead of one object per line
+    pretty: bool,
+    /// Write a top-level JSON array instead of JSONL
+    array: bool,
+    /// Unix permissions for newly created log files (None = system default)
+    file_mode: Option<u32>,
+    /// Pending entries when buffering is enabled
+    buffer: Option<WriteBuffer>,
+    /// Keys to emit, in this order (None = all fields)
+    fields: Option<Vec<String>>,
+    /// Add the numeric OpenTelemetry severity after "level"
+    severity_number: bool,
+    /// Terminator of every line (entries, pretty fields, array brackets)
+    line_ending: LineEnding,
+    /// Write the OpenTelemetry schema (fields and severity_number don't apply)
+    otel: bool,
+    /// Start new files with the UTF-8 byte order mark
+    utf8_bom: bool,
+}
+
+impl Default for JsonWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl JsonWriter {
-    /// Create a new JSON writer instance
+    /// Create a new JSON writer instance (compact JSONL output)
     pub fn new() -> Self {
-        Self
+        Self {
+            pretty: false,
+            array: false,
+            file_mode: None,
+            buffer: None,
+            fields: None,
+            severity_number: false,
+            line_ending: LineEnding::Lf,
+            otel: false,
+            utf8_bom: false,
+        }
+    }
+
+    /// Enable or disable pretty-printed (indented, multi-line) output
+    pub fn with_pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Enable or disable array mode (one top-level JSON array per file)
+    pub fn with_array(mut self, array: bool) -> Self {
+        self.array = array;
+        self
+    }
+
+    /// Set the Unix permissions of newly created log files (e.g. 0o600)
+    ///
+    /// No effect on other platforms.
+    pub fn with_file_mode(mut self, file_mode: Option<u32>) -> Self {
+        self.file_mode = file_mode;
+        self
+    }
+
+    /// Collect entries in memory and write them in batches (see `WriteBuffer`)
+    ///
+    /// # Arguments
+    /// * `capacity` - Buffer size in bytes, or None to write every entry directly
+    pub fn with_buffer(mut self, capacity: Option<usize>) -> Self {
+        self.buffer = capacity.map(WriteBuffer::new);
+        self
+    }
+
+    /// Restrict and order the emitted keys
+    ///
+    /// Names can be built-in fields (timestamp, level, message, module, file,
+    /// line, thread, thread_id, pid, hostname, seq) or context keys; names missing
+    /// from an entry are skipped.
+    ///
+    /// # Arguments
+    /// * `fields` - Keys in output order, or None to emit every field
+    pub fn with_fields(mut self, fields: Option<Vec<String>>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Enable or disable the numeric "severity_number" field
+    pub fn with_severity_number(mut self, severity_number: bool) -> Self {
+        self.severity_number = severity_number;
+        self
+    }
+
+    /// Terminate lines with "\r\n" instead of "\n" (or back)
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Enable or disable the OpenTelemetry schema (see module docs)
+    pub fn with_otel(mut self, otel: bool) -> Self {
+        self.otel = otel;
+        self
+    }
+
+    /// Start new files with the UTF-8 byte order mark (`UTF8_BOM`)
+    pub fn with_utf8_bom(mut self, utf8_bom: bool) -> Self {
+        self.utf8_bom = utf8_bom;
+        self
     }
 
     /// Write log information as JSON to the specified file
@@ -42,80 +162,303 @@ impl JsonWriter {
         // Step 1: Convert LogInfo to JSON string
         let json_string = self.format_as_json(log_info);
 
-        // Step 2: Ensure directory exists
-        self.ensure_directory_exists(file_path)?;
+        // Step 2: Build the entry (pretty entries are followed by a blank line)
+        // Array mode puts the separator before the entry instead, which
+        // depends on what is already in the file or the buffer
+        let newline = self.line_ending.as_str();
+        let entry = if self.array {
+            let prefix = match &self.buffer {
+                Some(buffer) if !buffer.is_empty() => format!(",{}", newline),
+                _ => self.open_array(&self.open_file(file_path, json_string.len())?, file_path)?,
+            };
+            format!("{}{}", prefix, json_string)
+        } else if self.pretty {
+            format!("{}{}{}", json_string, newline, newline)
+        } else {
+            format!("{}{}", json_string, newline)
+        };
+
+        // Step 3: Buffer the entry or write it directly
+        match &self.buffer {
+            Some(buffer) => {
+                if buffer.push(&entry) {
+                    self.write_pending(file_path)?;
+                }
+                Ok(())
+            }
+            None => self.append_to_file(&entry, file_path),
+        }
+    }
+
+    /// Write all buffered entries to the file (no-op without buffering)
+    pub fn write_pending(&self, file_path: &Path) -> LoggerResult<()> {
+        let pending = match &self.buffer {
+            Some(buffer) => buffer.take(),
+            None => return Ok(()),
+        };
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+        self.append_to_file(&pending, file_path)
+    }
 
-        // Step 3: Open file in append mode
-        let file = OpenOptions::new()
-            .create(true)
-            .append(true)
+    /// Open the JSON file in append mode (readable to inspect an existing array)
+    fn open_file(&self, file_path: &Path, bytes_attempted: usize) -> LoggerResult<File> {
+        self.ensure_directory_exists(file_path)?;
+        append_options(self.file_mode)
+            .read(self.array)
             .open(file_path)
-            .map_err(|_| LoggerError::FileCreationFailed {
-                path: file_path.display().to_string(),
-                reason: "Failed to open JSON file for writing".to_string(),
-            })?;
-
-        // Step 4: Write JSON line
-        let mut writer = BufWriter::new(file);
-        writeln!(writer, "{}", json_string)
-            .map_err(|_| LoggerError::DiskFull {
-                path: file_path.display().to_string(),
-                bytes_attempted: json_string.len() + 1,
-            })?;
-
-        // Step 5: Flush to ensure data is written
-        writer.flush()
-            .map_err(|_| LoggerError::DiskFull {
-                path: file_path.display().to_string(),
-                bytes_attempted: json_string.len() + 1,
-            })?;
+            .with_write(file_path, bytes_attempted)
+    }
 
-        Ok(())
+    /// Append text to the file in one write
+    fn append_to_file(&self, text: &str, file_path: &Path) -> LoggerResult<()> {
+        let file = self.open_file(file_path, text.len())?;
+        append_entry(&file, text, self.utf8_bom, file_path)
+    }
+
+    /// Prepare an array-mode file for the next entry
+    ///
+    /// Inspects the end of the file to decide how the entry is introduced:
+    /// an empty file starts the array, a closed array is reopened by removing
+    /// the closing bracket, and an open array just gets a comma.
+    ///
+    /// # Returns
+    /// Text to write before the entry
+    fn open_array(&self, file: &File, file_path: &Path) -> LoggerResult<String> {
+        let read_failed = |_| LoggerError::FileCreationFailed {
+            path: file_path.display().to_string(),
+            reason: "Failed to inspect JSON array file".to_string(),
+        };
+
+        let newline = self.line_ending.as_str();
+        let len = file.metadata().map_err(read_failed)?.len();
+        if len == 0 {
+            return Ok(format!("[{}", newline));
+        }
+
+        if self.ends_with_array_close(file, len).map_err(read_failed)? {
+            file.set_len(len - self.array_close().len() as u64).map_err(read_failed)?;
+        }
+        Ok(format!(",{}", newline))
+    }
+
+    /// Written by `flush()` to close the array in array mode
+    fn array_close(&self) -> String {
+        let newline = self.line_ending.as_str();
+        format!("{}]{}", newline, newline)
+    }
+
+    /// Check if the file ends with the closing bracket written by `flush()`
+    fn ends_with_array_close(&self, mut file: &File, len: u64) -> std::io::Result<bool> {
+        let array_close = self.array_close();
+        let close_len = array_close.len() as u64;
+        if len < close_len {
+            return Ok(false);
+        }
+
+        let mut tail = vec![0u8; array_close.len()];
+        file.seek(SeekFrom::Start(len - close_len))?;
+        file.read_exact(&mut tail)?;
+        Ok(tail == array_close.as_bytes())
+    }
+
+    /// Close the JSON array in array mode
+    ///
+    /// Does nothing outside array mode, for a missing or empty file,
+    /// or when the array is already closed.
+    ///
+    /// # Arguments
+    /// * `file_path` - Full path to the JSON log file
+    pub fn close_array(&self, file_path: &Path) -> LoggerResult<()> {
+        if !self.array {
+            return Ok(());
+        }
+
+        let file = match OpenOptions::new().read(true).append(true).open(file_path) {
+            Ok(file) => file,
+            Err(_) => return Ok(()), // Nothing written yet
+        };
+
+        let array_close = self.array_close();
+        let write_failed = |_| LoggerError::DiskFull {
+            path: file_path.display().to_string(),
+            bytes_attempted: array_close.len(),
+        };
+
+        let len = file.metadata().map_err(write_failed)?.len();
+        if len == 0 || self.ends_with_array_close(&file, len).map_err(write_failed)? {
+            return Ok(());
+        }
+
+        (&file).write_all(array_close.as_bytes()).map_err(write_failed)
     }
 
     /// Convert LogInfo to JSON string format
     ///
     /// Creates structured JSON with consistent field names for all log entries.
     /// Missing optional fields are represented as null in JSON.
-    fn format_as_json(&self, log_info: &LogInfo) -> String {
+    /// Compact single-line JSON by default, indented multi-line JSON in pretty mode.
+    pub fn format_as_json(&self, log_info: &LogInfo) -> String {
+        if self.otel {
+            return self.join(&self.otel_fields(log_info));
+        }
+
         // Manual JSON construction to avoid external dependencies
-        let mut json_parts = Vec::new();
+        // Each field is a (key, already-encoded JSON value) pair
+        let mut json_fields: Vec<(String, String)> = vec![
+            // Required fields
+            ("timestamp".to_string(), self.json_string(log_info.timestamp)),
+            ("level".to_string(), self.json_string(log_info.level.as_str())),
+            ("message".to_string(), self.json_string(log_info.message)),
+
+            // Optional fields - include as null if not present
+            ("module".to_string(), match log_info.module {
+                Some(module) => self.json_string(module),
+                None => "null".to_string(),
+            }),
+            ("file".to_string(), match log_info.file {
+                Some(file) => self.json_string(file),
+                None => "null".to_string(),
+            }),
+            ("line".to_string(), match log_info.line {
+                Some(line) => line.to_string(),
+                None => "null".to_string(),
+            }),
+            ("thread".to_string(), match log_info.thread {
+                Some(thread) => self.json_string(thread),
+                None => "null".to_string(),
+            }),
+            ("thread_id".to_string(), match log_info.thread_id {
+                Some(thread_id) => thread_id.to_string(),
+                None => "null".to_string(),
+            }),
+        ];
+
+        // Optional fields - only present when enabled in the config
+        if self.severity_number {
+            json_fields.insert(2, ("severity_number".to_string(), log_info.level.severity_number().to_string()));
+        }
+        if let Some(pid) = log_info.pid {
+            json_fields.push(("pid".to_string(), pid.to_string()));
+        }
+        if let Some(hostname) = log_info.hostname {
+            json_fields.push(("hostname".to_string(), self.json_string(hostname)));
+        }
+        if let Some(seq) = log_info.seq {
+            json_fields.push(("seq".to_string(), seq.to_string()));
+        }
 
-        // Required fields
-        json_parts.push(format!("\"timestamp\":\"{}\"", self.escape_json_string(log_info.timestamp)));
-        json_parts.push(format!("\"level\":\"{}\"", log_info.level.as_str()));
-        json_parts.push(format!("\"message\":\"{}\"", self.escape_json_string(log_info.message)));
+        // Context fields - added as extra top-level string keys
+        for (key, value) in log_info.fields {
+            json_fields.push((key.clone(), self.json_string(value)));
+        }
 
-        // Optional fields - include as null if not present
-        match log_info.file {
-            Some(file) => json_parts.push(format!("\"file\":\"{}\"", self.escape_json_string(file))),
-            None => json_parts.push("\"file\":null".to_string()),
+        // Keep only the selected fields, in the configured order
+        if let Some(selected) = &self.fields {
+            json_fields = selected
+                .iter()
+                .filter_map(|name| json_fields.iter().find(|(key, _)| key == name).cloned())
+                .collect();
         }
 
-        match log_info.line {
-            Some(line) => json_parts.push(format!("\"line\":{}", line)),
-            None => json_parts.push("\"line\":null".to_string()),
+        // Combine into final JSON object
+        self.join(&json_fields)
+    }
+
+    /// Top-level fields of the OpenTelemetry schema
+    ///
+    /// Attributes use the OTel semantic convention names and are left out
+    /// when not available; context and static fields follow them.
+    fn otel_fields(&self, log_info: &LogInfo) -> Vec<(String, String)> {
+        let mut attributes: Vec<(String, String)> = Vec::new();
+        if let Some(module) = log_info.module {
+            attributes.push(("code.namespace".to_string(), self.json_string(module)));
         }
+        if let Some(file) = log_info.file {
+            attributes.push(("code.filepath".to_string(), self.json_string(file)));
+        }
+        if let Some(line) = log_info.line {
+            attributes.push(("code.lineno".to_string(), line.to_string()));
+        }
+        if let Some(thread) = log_info.thread {
+            attributes.push(("thread.name".to_string(), self.json_string(thread)));
+        }
+        if let Some(thread_id) = log_info.thread_id {
+            attributes.push(("thread.id".to_string(), thread_id.to_string()));
+        }
+        if let Some(pid) = log_info.pid {
+            attributes.push(("process.pid".to_string(), pid.to_string()));
+        }
+        if let Some(hostname) = log_info.hostname {
+            attributes.push(("host.name".to_string(), self.json_string(hostname)));
+        }
+        if let Some(seq) = log_info.seq {
+            attributes.push(("seq".to_string(), seq.to_string()));
+        }
+        for (key, value) in log_info.fields {
+            attributes.push((key.clone(), self.json_string(value)));
+        }
+
+        vec![
+            ("Timestamp".to_string(), self.json_string(log_info.timestamp)),
+            ("SeverityText".to_string(), self.json_string(log_info.level.as_str())),
+            ("SeverityNumber".to_string(), log_info.level.severity_number().to_string()),
+            ("Body".to_string(), self.json_string(log_info.message)),
+            // Nested object, always on one line (also in pretty mode)
+            ("Attributes".to_string(), self.join_compact(&attributes)),
+        ]
+    }
 
-        match log_info.thread {
-            Some(thread) => json_parts.push(format!("\"thread\":\"{}\"", self.escape_json_string(thread))),
-            None => json_parts.push("\"thread\":null".to_string()),
+    /// Join fields into one JSON object, pretty or compact
+    fn join(&self, json_fields: &[(String, String)]) -> String {
+        if self.pretty {
+            self.join_pretty(json_fields)
+        } else {
+            let json = self.join_compact(json_fields);
+            // JSONL: one physical line per entry (all values are escaped)
+            debug_assert!(!json.contains(['\n', '\r']), "compact JSON entry spans several lines: {}", json);
+            json
         }
+    }
 
-        // Combine into final JSON object
-        format!("{{{}}}", json_parts.join(","))
+    /// Encode a value as a quoted, escaped JSON string
+    fn json_string(&self, value: &str) -> String {
+        format!("\"{}\"", self.escape_json_string(value))
+    }
+
+    /// Join fields into a single-line JSON object: {"key":value,...}
+    fn join_compact(&self, json_fields: &[(String, String)]) -> String {
+        let parts: Vec<String> = json_fields
+            .iter()
+            .map(|(key, value)| format!("\"{}\":{}", self.escape_json_string(key), value))
+            .collect();
+        format!("{{{}}}", parts.join(","))
+    }
+
+    /// Join fields into an indented multi-line JSON object
+    ///
+    /// All values are scalars (or a one-line object), so a single indentation
+    /// level is enough:
+    /// {
+    ///   "key": value,
+    ///   ...
+    /// }
+    fn join_pretty(&self, json_fields: &[(String, String)]) -> String {
+        let parts: Vec<String> = json_fields
+            .iter()
+            .map(|(key, value)| format!("{}\"{}\": {}", PRETTY_INDENT, self.escape_json_string(key), value))
+            .collect();
+        let newline = self.line_ending.as_str();
+        format!("{{{}{}{}}}", newline, parts.join(&format!(",{}", newline)), newline)
     }
 
     /// Escape special characters in JSON strings
     ///
     /// Handles quotes, newlines, and other characters that need escaping in JSON.
     fn escape_json_string(&self, input: &str) -> String {
-        input
-            .replace("\\", "\\\\")  // Escape backslashes first
-            .replace("\"", "\\\"")  // Escape quotes
-            .replace("\n", "\\n")   // Escape newlines
-            .replace("\r", "\\r")   // Escape carriage returns
-            .replace("\t", "\\t")   // Escape tabs
+        escape_json_string(input)
     }
 
     /// Ensure the directory for the JSON file exists
@@ -135,10 +478,27 @@ impl JsonWriter {
     }
 }
 
+impl LogWriter for JsonWriter {
+    /// Builds the JSON from `info` itself, ignoring the pattern-formatted line
+    fn write_entry(&self, info: &LogInfo, _formatted: &str, path: &Path) -> LoggerResult<()> {
+        self.write_log_entry(info, path)
+    }
+
+    /// Writes out buffered entries, then closes the array in array mode
+    fn flush(&self, path: &Path) -> LoggerResult<()> {
+        self.write_pending(path)?;
+        self.close_array(path)
+    }
+    fn buffered_bytes(&self) -> usize {
+        self.buffer.as_ref().map_or(0, WriteBuffer::len)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::format::LogInfo;
+    use crate::core::config::LogLevel;
     use tempfile::tempdir;
     use std::fs;
 
@@ -166,7 +526,8 @@ mod tests {
 
         let log_info = LogInfo::new("Detailed test", LogLevel::Debug, "2025-09-06 15:30:45")
             .with_location("test.rs", 42)
-            .with_thread("main");
+            .with_thread("main")
+            .with_thread_id(7);
 
         let writer = JsonWriter::new();
         writer.write_log_entry(&log_info, &json_path).unwrap();
@@ -175,6 +536,7 @@ mod tests {
         assert!(content.contains("\"file\":\"test.rs\""));
         assert!(content.contains("\"line\":42"));
         assert!(content.contains("\"thread\":\"main\""));
+        assert!(content.contains("\"thread_id\":7"));
     }
 
     #[test]
@@ -204,4 +566,131 @@ mod tests {
         assert!(lines[0].contains("First message"));
         assert!(lines[1].contains("Second message"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_pretty_json_is_indented_multiline() {
+        let log_info = LogInfo::new("Pretty message", LogLevel::Info, "2025-09-06T15:30:45Z");
+
+        let compact = JsonWriter::new().format_as_json(&log_info);
+        assert!(!compact.contains('\n'));
+
+        let pretty = JsonWriter::new().with_pretty(true).format_as_json(&log_info);
+        assert!(pretty.starts_with("{\n  \"timestamp\": \"2025-09-06T15:30:45Z\",\n"));
+        assert!(pretty.contains("\n  \"message\": \"Pretty message\",\n"));
+        assert!(pretty.ends_with("\n  \"thread\": null,\n  \"thread_id\": null\n}"));
+    }
+
+    #[test]
+    fn test_pretty_entries_separated_by_blank_line() {
+        let temp_dir = tempdir().unwrap();
+        let json_path = temp_dir.path().join("pretty.json");
+        let writer = JsonWriter::new().with_pretty(true);
+
+        writer.write_log_entry(&LogInfo::new("First", LogLevel::Info, "t1"), &json_path).unwrap();
+        writer.write_log_entry(&LogInfo::new("Second", LogLevel::Info, "t2"), &json_path).unwrap();
+
+        let content = fs::read_to_string(&json_path).unwrap();
+        let entries: Vec<&str> = content.trim_end().split("\n\n").collect();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].contains("\"message\": \"First\""));
+        assert!(entries[1].contains("\"message\": \"Second\""));
+    }
+
+    /// Split a closed array-mode file into its entries
+    fn array_entries(content: &str) -> Vec<&str> {
+        let inner = content
+            .strip_prefix("[\n")
+            .and_then(|rest| rest.strip_suffix("\n]\n"))
+            .expect("file is not a closed JSON array");
+        inner.split(",\n").collect()
+    }
+
+    #[test]
+    fn test_array_mode_writes_closed_json_array() {
+        let temp_dir = tempdir().unwrap();
+        let json_path = temp_dir.path().join("array.json");
+        let writer = JsonWriter::new().with_array(true);
+
+        for message in ["First", "Second", "Third"] {
+            writer.write_log_entry(&LogInfo::new(message, LogLevel::Info, "t"), &json_path).unwrap();
+        }
+        writer.flush(&json_path).unwrap();
+
+        let content = fs::read_to_string(&json_path).unwrap();
+        let entries = array_entries(&content);
+        assert_eq!(entries.len(), 3);
+        assert!(entries.iter().all(|entry| entry.starts_with('{') && entry.ends_with('}')));
+        assert!(entries[2].contains("\"message\":\"Third\""));
+    }
+
+    #[test]
+    fn test_array_mode_reopens_after_flush() {
+        let temp_dir = tempdir().unwrap();
+        let json_path = temp_dir.path().join("reopen.json");
+        let writer = JsonWriter::new().with_array(true);
+
+        writer.write_log_entry(&LogInfo::new("First", LogLevel::Info, "t"), &json_path).unwrap();
+        writer.flush(&json_path).unwrap();
+        writer.flush(&json_path).unwrap(); // Already closed - no second bracket
+        writer.write_log_entry(&LogInfo::new("Second", LogLevel::Info, "t"), &json_path).unwrap();
+        writer.flush(&json_path).unwrap();
+
+        let content = fs::read_to_string(&json_path).unwrap();
+        assert_eq!(array_entries(&content).len(), 2);
+    }
+
+    #[test]
+    fn test_selected_fields_in_configured_order() {
+        let fields = vec![("request_id".to_string(), "42".to_string())];
+        let info = LogInfo::new("Selected", LogLevel::Info, "2025-09-06T15:30:45Z")
+            .with_location("main.rs", 7)
+            .with_thread("main")
+            .with_fields(&fields);
+        let writer = JsonWriter::new().with_fields(Some(vec![
+            "level".to_string(),
+            "request_id".to_string(),
+            "message".to_string(),
+            "missing".to_string(),
+        ]));
+
+        assert_eq!(
+            writer.format_as_json(&info),
+            "{\"level\":\"INFO\",\"request_id\":\"42\",\"message\":\"Selected\"}"
+        );
+    }
+
+    #[test]
+    fn test_severity_number_field() {
+        let info = LogInfo::new("Numbered", LogLevel::Warning, "2025-09-06T15:30:45Z");
+
+        let json = JsonWriter::new().with_severity_number(true).format_as_json(&info);
+        assert!(json.contains("\"level\":\"WARNING\",\"severity_number\":13,\"message\""));
+        assert!(!JsonWriter::new().format_as_json(&info).contains("severity_number"));
+    }
+
+    #[test]
+    fn test_otel_schema_with_attributes() {
+        let fields = vec![
+            ("service".to_string(), "api".to_string()),
+            ("request_id".to_string(), "42".to_string()),
+        ];
+        let info = LogInfo::new("Disk almost full", LogLevel::Warning, "2025-09-06T15:30:45Z")
+            .with_location("main.rs", 7)
+            .with_module("app::disk")
+            .with_fields(&fields);
+
+        let json = JsonWriter::new().with_otel(true).format_as_json(&info);
+        let entry: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(entry["SeverityText"], "WARNING");
+        assert_eq!(entry["SeverityNumber"], 13);
+        assert_eq!(entry["Body"], "Disk almost full");
+        assert_eq!(entry["Timestamp"], "2025-09-06T15:30:45Z");
+        assert_eq!(entry["Attributes"]["service"], "api");
+        assert_eq!(entry["Attributes"]["request_id"], "42");
+        assert_eq!(entry["Attributes"]["code.namespace"], "app::disk");
+        assert_eq!(entry["Attributes"]["code.lineno"], 7);
+        assert!(entry.get("request_id").is_none());
+        assert!(entry["Attributes"].get("thread.name").is_none());
+    }
+}
@@ -0,0 +1,69 @@
+//! Shared writer interface for FreedomLogger
+//!
+//! Every output destination implements `LogWriter`, so the logger can fan
+//! a single log entry out to several writers (file + console + memory, ...).
+//! Each writer fails independently: an error in one writer is reported
+//! through the logger's error handling and does not stop the others.
+//!
+//! Applications can plug in their own sink (HTTP endpoint, message queue, ...)
+//! by implementing `LogWriter` and setting `LoggerConfig::custom_writer`.
+//!
+//! Thread-safety requirements:
+//! - Implementations must be `Send + Sync`: the logger is shared between threads
+//! - `write_entry` is called while the logger holds its write lock, so calls are
+//!   never concurrent for one logger, but may come from any thread
+//! - Keep `write_entry` fast and non-blocking where possible; a slow writer
+//!   delays every logging thread
+
+use std::path::Path;
+use std::sync::Arc;
+use crate::error::LoggerResult;
+use crate::format::LogInfo;
+
+/// Output destination for formatted log entries
+pub trait LogWriter: Send + Sync {
+    /// Write one log entry
+    ///
+    /// # Arguments
+    /// * `info` - Complete log information (for writers with their own format)
+    /// * `formatted` - The entry formatted with the configured pattern
+    /// * `path` - Current log file path (ignored by non-file writers)
+    ///
+    /// # Returns
+    /// Ok(()) on success, LoggerError on failure (routed to the error log)
+    fn write_entry(&self, info: &LogInfo, formatted: &str, path: &Path) -> LoggerResult<()>;
+
+    /// Finish any pending output (e.g. close a JSON array)
+    ///
+    /// Called by `Logger::flush()`, `Logger::shutdown()` and before rotation.
+    /// The default does nothing, for writers that complete every entry immediately.
+    ///
+    /// # Arguments
+    /// * `path` - Current log file path (ignored by non-file writers)
+    fn flush(&self, _path: &Path) -> LoggerResult<()> {
+        Ok(())
+    }
+
+    /// Bytes of output held in memory until the next flush
+    ///
+    /// Reported by `Logger::buffer_used()`. The default is 0, for writers
+    /// without a buffer.
+    fn buffered_bytes(&self) -> usize {
+        0
+    }
+}
+
+/// Shared writers (e.g. a memory writer the logger also reads from)
+impl<T: LogWriter + ?Sized> LogWriter for Arc<T> {
+    fn write_entry(&self, info: &LogInfo, formatted: &str, path: &Path) -> LoggerResult<()> {
+        (**self).write_entry(info, formatted, path)
+    }
+
+    fn flush(&self, path: &Path) -> LoggerResult<()> {
+        (**self).flush(path)
+    }
+
+    fn buffered_bytes(&self) -> usize {
+        (**self).buffered_bytes()
+    }
+}
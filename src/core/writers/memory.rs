@@ -0,0 +1,113 @@
+//! In-memory writer for FreedomLogger
+//!
+//! Keeps the last N formatted log lines in a ring buffer instead of writing
+//! them to disk. Useful for:
+//! - Unit tests that want to assert on emitted logs without touching the filesystem
+//! - Embedded scenarios without a filesystem
+//!
+//! When the buffer is full, the oldest line is dropped for every new line.
+
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Mutex;
+use crate::error::LoggerResult;
+use crate::format::LogInfo;
+use super::LogWriter;
+
+/// Ring-buffer writer that retains the most recent formatted lines
+#[derive(Debug)]
+pub struct MemoryWriter {
+    /// Captured lines, oldest first
+    lines: Mutex<VecDeque<String>>,
+    /// Maximum number of lines to keep
+    capacity: usize,
+}
+
+impl MemoryWriter {
+    /// Create a new memory writer
+    ///
+    /// # Arguments
+    /// * `capacity` - Maximum number of lines to keep (oldest are dropped first)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity,
+        }
+    }
+
+    /// Store a formatted log message, dropping the oldest line when full
+    ///
+    /// # Arguments
+    /// * `message` - The fully formatted log message
+    ///
+    /// # Returns
+    /// Always Ok(()) - writing to memory cannot fail
+    pub fn write_message(&self, message: &str) -> LoggerResult<()> {
+        if self.capacity == 0 {
+            return Ok(());
+        }
+
+        let mut lines = self.lines.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        while lines.len() >= self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(message.to_string());
+        Ok(())
+    }
+
+    /// Get a copy of all captured lines, oldest first
+    pub fn lines(&self) -> Vec<String> {
+        let lines = self.lines.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        lines.iter().cloned().collect()
+    }
+
+    /// Remove all captured lines
+    pub fn clear(&self) {
+        let mut lines = self.lines.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        lines.clear();
+    }
+
+    /// Get the maximum number of lines kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+impl LogWriter for MemoryWriter {
+    fn write_entry(&self, _info: &LogInfo, formatted: &str, _path: &Path) -> LoggerResult<()> {
+        self.write_message(formatted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines_are_captured_in_order() {
+        let writer = MemoryWriter::new(10);
+        writer.write_message("First").unwrap();
+        writer.write_message("Second").unwrap();
+
+        assert_eq!(writer.lines(), vec!["First", "Second"]);
+    }
+
+    #[test]
+    fn test_ring_buffer_caps_at_capacity() {
+        let writer = MemoryWriter::new(3);
+        for i in 1..=5 {
+            writer.write_message(&format!("Line {}", i)).unwrap();
+        }
+
+        assert_eq!(writer.lines(), vec!["Line 3", "Line 4", "Line 5"]);
+    }
+
+    #[test]
+    fn test_clear_removes_all_lines() {
+        let writer = MemoryWriter::new(3);
+        writer.write_message("Line").unwrap();
+        writer.clear();
+
+        assert!(writer.lines().is_empty());
+    }
+}
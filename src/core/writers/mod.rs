@@ -1,18 +1,113 @@
-/// Writers module for FreedomLogger
-///
-/// This module contains all log writers responsible for outputting formatted
-/// log data to different destinations and formats:
-///
-/// - TextWriter: Plain text files (.log extension)
-/// - JsonWriter: Structured JSON files (.json extension)
-///
-/// Future v2 enhancements will extend JsonWriter to support database output
-/// while maintaining the same interface.
+//! Writers module for FreedomLogger
+//!
+//! This module contains all log writers responsible for outputting formatted
+//! log data to different destinations and formats:
+//!
+//! - TextWriter: Plain text files (.log extension)
+//! - JsonWriter: Structured JSON files (.json extension)
+//! - HtmlWriter: One HTML table per file (.html extension)
+//! - ConsoleWriter: Formatted lines on stdout
+//! - MemoryWriter: Ring buffer of the last N formatted lines (tests, embedded)
+//! - WriteBuffer: Optional in-memory buffering for the file writers
+//! - FallbackWriter: Stderr output while the file writer keeps failing
+//! - RetryWriter: Bounded retries with backoff for transient write failures
+//! - WindowsEventWriter: Windows Event Log (`eventlog` feature, Windows only)
+//! - JournaldWriter: systemd journal (`journald` feature, Unix only)
+//! - FifoWriter: Named pipe read by an external shipper, never blocking (Unix only)
+//! - GzipWriter: Gzip compressed log file (`compression` feature)
+//! - WebConsoleWriter: Browser console (`wasm` feature, wasm32 only)
+//!
+//! All writers implement the `LogWriter` trait so the logger can write
+//! each entry to several destinations at once.
+//!
+//! Future v2 enhancements will extend JsonWriter to support database output
+//! while maintaining the same interface.
 
-// Re-export all writer types
+use std::borrow::Cow;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use crate::error::{IoResultExt, LoggerResult};
+
+// Re-export the shared writer interface and all writer types
+pub use log_writer::LogWriter;
 pub use text::TextWriter;
 pub use json::JsonWriter;
+pub use html::HtmlWriter;
+pub use console::ConsoleWriter;
+pub use memory::MemoryWriter;
+pub use buffer::{WriteBuffer, DEFAULT_BUFFER_CAPACITY};
+pub use fallback::{FallbackWriter, DEFAULT_FALLBACK_RETRY_INTERVAL};
+pub use retry::{RetryWriter, MAX_OPEN_RETRIES};
+#[cfg(all(windows, feature = "eventlog"))]
+pub use eventlog::WindowsEventWriter;
+#[cfg(all(unix, feature = "journald"))]
+pub use journald::JournaldWriter;
+#[cfg(unix)]
+pub use fifo::FifoWriter;
+#[cfg(feature = "compression")]
+pub use gzip::GzipWriter;
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub use web_console::WebConsoleWriter;
 
 // Import writer implementations
+pub mod log_writer;
 pub mod text;
-pub mod json;
\ No newline at end of file
+pub mod json;
+pub mod html;
+pub mod console;
+pub mod memory;
+pub mod buffer;
+pub mod fallback;
+pub mod retry;
+#[cfg(feature = "eventlog")]
+pub mod eventlog;
+#[cfg(all(unix, feature = "journald"))]
+pub mod journald;
+#[cfg(unix)]
+pub mod fifo;
+#[cfg(feature = "compression")]
+pub mod gzip;
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+pub mod web_console;
+
+/// Options for opening a log file in append mode, creating it if needed
+///
+/// On Unix a newly created file gets `file_mode` (still reduced by the umask);
+/// existing files keep their permissions. Ignored on other platforms.
+pub(crate) fn append_options(file_mode: Option<u32>) -> OpenOptions {
+    let mut options = OpenOptions::new();
+    options.create(true).append(true);
+
+    #[cfg(unix)]
+    if let Some(mode) = file_mode {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(mode);
+    }
+    #[cfg(not(unix))]
+    let _ = file_mode;
+
+    options
+}
+
+/// UTF-8 byte order mark, written at the start of new files with `write_utf8_bom`
+pub const UTF8_BOM: &str = "\u{feff}";
+
+/// Append a complete entry (or batch of buffered entries) to a log file
+///
+/// The text is handed to the file in one write, never piece by piece, so a
+/// multi-line entry stays contiguous: the logger's write lock keeps other
+/// threads out, and append mode keeps other processes appending to the same
+/// file from landing in the middle of it.
+///
+/// With `utf8_bom` an empty file (new, truncated or fresh after rotation)
+/// gets the UTF-8 BOM in front, in the same write.
+pub(crate) fn append_entry(mut file: &File, text: &str, utf8_bom: bool, file_path: &Path) -> LoggerResult<()> {
+    let text = if utf8_bom && file.metadata().is_ok_and(|metadata| metadata.len() == 0) {
+        Cow::Owned(format!("{}{}", UTF8_BOM, text))
+    } else {
+        Cow::Borrowed(text)
+    };
+    file.write_all(text.as_bytes()).with_write(file_path, text.len())?;
+    file.flush().with_write(file_path, text.len())
+}
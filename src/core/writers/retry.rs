@@ -0,0 +1,133 @@
+//! Retries of failed writes for FreedomLogger
+//!
+//! With `LoggerConfig::max_open_retries` every writer is wrapped in a
+//! `RetryWriter`. A write that fails with an I/O error (file or directory
+//! creation, permission, disk full) is tried again after a short wait that
+//! doubles per attempt, so a brief NFS hiccup or a momentary lock doesn't
+//! lose the line. ERROR entries get twice as many retries.
+//!
+//! Retries are bounded: at most `MAX_OPEN_RETRIES` (ERROR: twice that) with
+//! waits capped at `RETRY_MAX_BACKOFF`, so a persistent failure delays the
+//! logging thread by about two seconds at most before the error is reported.
+//! A write that failed halfway may be repeated in full.
+
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use crate::core::config::LogLevel;
+use crate::error::LoggerResult;
+use crate::format::LogInfo;
+use super::LogWriter;
+
+/// Highest accepted `LoggerConfig::max_open_retries`
+pub const MAX_OPEN_RETRIES: u32 = 10;
+
+/// Wait before the first retry (doubled for every further retry)
+pub const RETRY_INITIAL_BACKOFF: Duration = Duration::from_millis(1);
+
+/// Longest wait between two attempts
+pub const RETRY_MAX_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Writer retrying failed writes of the wrapped writer
+pub struct RetryWriter {
+    /// Writer doing the actual output
+    inner: Box<dyn LogWriter>,
+    /// Retries after the first attempt (doubled for ERROR entries)
+    retries: u32,
+}
+
+impl RetryWriter {
+    /// Wrap `inner`, retrying a failed write up to `retries` times
+    pub fn new(inner: Box<dyn LogWriter>, retries: u32) -> Self {
+        Self { inner, retries }
+    }
+
+    /// Retries allowed for an entry at `level`
+    fn retries_for(&self, level: LogLevel) -> u32 {
+        match level {
+            LogLevel::Error => self.retries.saturating_mul(2),
+            _ => self.retries,
+        }
+    }
+
+    /// Run `attempt` until it succeeds, fails permanently or runs out of retries
+    fn retry(&self, retries: u32, mut attempt: impl FnMut() -> LoggerResult<()>) -> LoggerResult<()> {
+        let mut backoff = RETRY_INITIAL_BACKOFF;
+        let mut retried = 0;
+        loop {
+            match attempt() {
+                Err(error) if retried < retries && error.is_write_failure() => {
+                    thread::sleep(backoff);
+                    backoff = (backoff * 2).min(RETRY_MAX_BACKOFF);
+                    retried += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for RetryWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryWriter").field("retries", &self.retries).finish_non_exhaustive()
+    }
+}
+
+impl LogWriter for RetryWriter {
+    fn write_entry(&self, info: &LogInfo, formatted: &str, path: &Path) -> LoggerResult<()> {
+        self.retry(self.retries_for(info.level), || self.inner.write_entry(info, formatted, path))
+    }
+
+    fn flush(&self, path: &Path) -> LoggerResult<()> {
+        self.retry(self.retries, || self.inner.flush(path))
+    }
+
+    fn buffered_bytes(&self) -> usize {
+        self.inner.buffered_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::LoggerError;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Writer failing a given number of times, then succeeding
+    struct Flaky {
+        failures_left: AtomicU32,
+        attempts: AtomicU32,
+    }
+
+    impl LogWriter for Flaky {
+        fn write_entry(&self, _info: &LogInfo, _formatted: &str, path: &Path) -> LoggerResult<()> {
+            self.attempts.fetch_add(1, Ordering::Relaxed);
+            if self.failures_left.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| left.checked_sub(1)).is_ok() {
+                return Err(LoggerError::FileCreationFailed {
+                    path: path.display().to_string(),
+                    reason: "busy".to_string(),
+                });
+            }
+            Ok(())
+        }
+    }
+
+    fn flaky_writer(failures: u32, retries: u32) -> (std::sync::Arc<Flaky>, RetryWriter) {
+        let flaky = std::sync::Arc::new(Flaky { failures_left: AtomicU32::new(failures), attempts: AtomicU32::new(0) });
+        let writer = RetryWriter::new(Box::new(std::sync::Arc::clone(&flaky)), retries);
+        (flaky, writer)
+    }
+
+    #[test]
+    fn test_retries_are_bounded_and_doubled_for_errors() {
+        let info = LogInfo::new("entry", LogLevel::Info, "2025-09-06 15:30:45");
+        let (flaky, writer) = flaky_writer(5, 2);
+        assert!(writer.write_entry(&info, "entry", Path::new("app.log")).is_err());
+        assert_eq!(flaky.attempts.load(Ordering::Relaxed), 3);
+
+        let error = LogInfo::new("entry", LogLevel::Error, "2025-09-06 15:30:45");
+        let (flaky, writer) = flaky_writer(4, 2);
+        assert!(writer.write_entry(&error, "entry", Path::new("app.log")).is_ok());
+        assert_eq!(flaky.attempts.load(Ordering::Relaxed), 5);
+    }
+}
@@ -1,39 +1,88 @@
-/// Text file writer for FreedomLogger
-///
-/// Handles writing formatted log messages to plain text files (.log extension).
-/// Responsible for:
-/// - Creating directories if they don't exist
-/// - Opening/creating log files in append mode
-/// - Writing formatted strings to files
-/// - Proper error handling and reporting
-///
-/// This writer outputs human-readable text logs suitable for viewing
-/// in text editors or processing with standard Unix tools.
+//! Text file writer for FreedomLogger
+//!
+//! Handles writing formatted log messages to plain text files (.log extension).
+//! Responsible for:
+//! - Creating directories if they don't exist
+//! - Opening/creating log files in append mode
+//! - Writing formatted strings to files
+//! - Proper error handling and reporting
+//!
+//! This writer outputs human-readable text logs suitable for viewing
+//! in text editors or processing with standard Unix tools.
 
-use std::fs::{self, OpenOptions};
-use std::io::{BufWriter, Write};
+use std::fs;
 use std::path::Path;
-use crate::error::{LoggerError, LoggerResult};
+use crate::error::{IoResultExt, LoggerError, LoggerResult};
+use crate::core::config::LineEnding;
+use crate::format::LogInfo;
+use super::{LogWriter, WriteBuffer, append_entry, append_options};
 
 /// Text file writer for plain text log output
 ///
 /// Handles all aspects of writing to text log files including
 /// directory creation, file management, and error handling.
 #[derive(Debug)]
-pub struct TextWriter;
+pub struct TextWriter {
+    /// Unix permissions for newly created log files (None = system default)
+    file_mode: Option<u32>,
+    /// Pending lines when buffering is enabled
+    buffer: Option<WriteBuffer>,
+    /// Terminator appended to every line
+    line_ending: LineEnding,
+    /// Start new files with the UTF-8 byte order mark
+    utf8_bom: bool,
+}
+
+impl Default for TextWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl TextWriter {
     /// Create a new text writer instance
     pub fn new() -> Self {
-        Self
+        Self { file_mode: None, buffer: None, line_ending: LineEnding::Lf, utf8_bom: false }
+    }
+
+    /// Set the Unix permissions of newly created log files (e.g. 0o600)
+    ///
+    /// No effect on other platforms.
+    pub fn with_file_mode(mut self, file_mode: Option<u32>) -> Self {
+        self.file_mode = file_mode;
+        self
+    }
+
+    /// Collect lines in memory and write them in batches (see `WriteBuffer`)
+    ///
+    /// # Arguments
+    /// * `capacity` - Buffer size in bytes, or None to write every line directly
+    pub fn with_buffer(mut self, capacity: Option<usize>) -> Self {
+        self.buffer = capacity.map(WriteBuffer::new);
+        self
+    }
+
+    /// Terminate lines with "\r\n" instead of "\n" (or back)
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Start new files with the UTF-8 byte order mark (`UTF8_BOM`)
+    pub fn with_utf8_bom(mut self, utf8_bom: bool) -> Self {
+        self.utf8_bom = utf8_bom;
+        self
     }
 
     /// Write a formatted log message to the specified file
     ///
+    /// With buffering enabled the line is only added to the buffer, and
+    /// written together with the other pending lines once the buffer is full.
+    ///
     /// This method handles the complete write process:
     /// 1. Ensure directory exists (create if needed)
     /// 2. Open/create log file in append mode
-    /// 3. Write the message with newline
+    /// 3. Write the message with the line terminator
     /// 4. Flush to ensure data is written
     ///
     /// # Arguments
@@ -43,39 +92,45 @@ impl TextWriter {
     /// # Returns
     /// Ok(()) on success, LoggerError on failure
     ///
-  
     pub fn write_message(&self, message: &str, file_path: &Path) -> LoggerResult<()> {
+        let line = format!("{}{}", message, self.line_ending.as_str());
+
+        match &self.buffer {
+            Some(buffer) => {
+                if buffer.push(&line) {
+                    self.write_pending(file_path)?;
+                }
+                Ok(())
+            }
+            None => self.append_to_file(&line, file_path),
+        }
+    }
+
+    /// Write all buffered lines to the file (no-op without buffering)
+    pub fn write_pending(&self, file_path: &Path) -> LoggerResult<()> {
+        let pending = match &self.buffer {
+            Some(buffer) => buffer.take(),
+            None => return Ok(()),
+        };
+
+        if pending.is_empty() {
+            return Ok(());
+        }
+        self.append_to_file(&pending, file_path)
+    }
+
+    /// Append text to the file in one write
+    fn append_to_file(&self, text: &str, file_path: &Path) -> LoggerResult<()> {
         // Step 1: Ensure directory exists
         self.ensure_directory_exists(file_path)?;
 
         // Step 2: Open file in append mode (create if doesn't exist)
-        let file = OpenOptions::new()
-            .create(true)
-            .append(true)
+        let file = append_options(self.file_mode)
             .open(file_path)
-            .map_err(|_| LoggerError::FileCreationFailed {
-                path: file_path.display().to_string(),
-                reason: "Failed to open file for writing".to_string(),
-            })?;
-
-        // Step 3: Use buffered writer for better performance
-        let mut writer = BufWriter::new(file);
-
-        // Step 4: Write message with newline
-        writeln!(writer, "{}", message)
-            .map_err(|_| LoggerError::DiskFull {
-                path: file_path.display().to_string(),
-                bytes_attempted: message.len() + 1, // +1 for newline
-            })?;
-
-        // Step 5: Flush to ensure data is written to disk
-        writer.flush()
-            .map_err(|_| LoggerError::DiskFull {
-                path: file_path.display().to_string(),
-                bytes_attempted: message.len() + 1,
-            })?;
+            .with_write(file_path, text.len())?;
 
-        Ok(())
+        // Step 3: Write text (already newline terminated) as one unit
+        append_entry(&file, text, self.utf8_bom, file_path)
     }
 
     /// Ensure the directory for the log file exists
@@ -112,12 +167,29 @@ impl TextWriter {
     /// # Returns
     /// Ok(()) if writable, LoggerError if not
     pub fn test_write_permissions(&self, file_path: &Path) -> LoggerResult<()> {
-        // Try to write an empty test message
-        self.write_message("", file_path)?;
+        // Create the directory and open the file for appending, without writing
+        self.ensure_directory_exists(file_path)?;
+        append_options(self.file_mode)
+            .open(file_path)
+            .with_path(file_path)?;
         Ok(())
     }
 }
 
+impl LogWriter for TextWriter {
+    fn write_entry(&self, _info: &LogInfo, formatted: &str, path: &Path) -> LoggerResult<()> {
+        self.write_message(formatted, path)
+    }
+
+    /// Writes out buffered lines
+    fn flush(&self, path: &Path) -> LoggerResult<()> {
+        self.write_pending(path)
+    }
+    fn buffered_bytes(&self) -> usize {
+        self.buffer.as_ref().map_or(0, WriteBuffer::len)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,4 +254,51 @@ mod tests {
         let content = fs::read_to_string(&log_path).unwrap();
         assert_eq!(content, "\n"); // Just a newline
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_buffered_lines_written_on_flush() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("buffered.log");
+        let writer = TextWriter::new().with_buffer(Some(1024));
+
+        writer.write_message("First", &log_path).unwrap();
+        writer.write_message("Second", &log_path).unwrap();
+        assert!(!log_path.exists());
+
+        writer.write_pending(&log_path).unwrap();
+        assert_eq!(fs::read_to_string(&log_path).unwrap(), "First\nSecond\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_read_only_file_reports_permission_denied() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("readonly.log");
+        fs::write(&log_path, "").unwrap();
+        fs::set_permissions(&log_path, fs::Permissions::from_mode(0o444)).unwrap();
+
+        // Privileged users (root) can write read-only files - nothing to test then
+        if fs::OpenOptions::new().append(true).open(&log_path).is_ok() {
+            return;
+        }
+
+        let result = TextWriter::new().write_message("Denied", &log_path);
+        assert_eq!(result, Err(LoggerError::WritePermissionDenied {
+            path: log_path.display().to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_crlf_line_ending() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("test.log");
+
+        let writer = TextWriter::new().with_line_ending(LineEnding::Crlf);
+        writer.write_message("first", &log_path).unwrap();
+        writer.write_message("second", &log_path).unwrap();
+
+        assert_eq!(fs::read(&log_path).unwrap(), b"first\r\nsecond\r\n");
+    }
+}
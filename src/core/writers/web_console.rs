@@ -0,0 +1,44 @@
+//! Browser console writer for FreedomLogger (`wasm` feature, wasm32 only)
+//!
+//! There is no file system or stdout in the browser, so on wasm32 the file,
+//! console and stderr destinations are all written here instead. Each entry
+//! goes to the console method matching its level, so the browser's level
+//! filter and colouring apply:
+//! - ERROR: console.error
+//! - WARNING: console.warn
+//! - INFO: console.info
+//! - DEBUG/TRACE: console.debug
+//!
+//! Selected with `Destination::WebConsole`.
+
+use std::path::Path;
+use wasm_bindgen::JsValue;
+use web_sys::console;
+use crate::core::config::LogLevel;
+use crate::error::LoggerResult;
+use crate::format::LogInfo;
+use super::LogWriter;
+
+/// Writer sending formatted lines to the browser console
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WebConsoleWriter;
+
+impl WebConsoleWriter {
+    /// Create a new browser console writer
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl LogWriter for WebConsoleWriter {
+    fn write_entry(&self, info: &LogInfo, formatted: &str, _path: &Path) -> LoggerResult<()> {
+        let line = JsValue::from_str(formatted);
+        match info.level {
+            LogLevel::Off | LogLevel::Error => console::error_1(&line),
+            LogLevel::Warning => console::warn_1(&line),
+            LogLevel::Info => console::info_1(&line),
+            LogLevel::Debug | LogLevel::Trace => console::debug_1(&line),
+        }
+        Ok(())
+    }
+}
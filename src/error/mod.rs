@@ -1,58 +1,215 @@
-/// File: src/error/mod.rs
+//! File: src/error/mod.rs
 
-/// This module contains all error types and error handling logic for internal
-/// logger failures. When the logger itself encounters problems (disk full,
-/// permission denied, etc.), these errors are caught and handled silently.
-///
-/// The user's application never sees these errors - they are logged to a
-/// separate error file for debugging purposes only.
+//! This module contains all error types and error handling logic for internal
+//! logger failures. When the logger itself encounters problems (disk full,
+//! permission denied, etc.), these errors are caught and handled silently.
+//!
+//! The user's application never sees these errors - they are logged to a
+//! separate error file for debugging purposes only.
 
-use std::fs::{File, OpenOptions};
-use std::io::{Write, BufWriter};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write, BufWriter};
 use std::path::Path;
+use crate::core::config::LoggerConfig;
+use crate::format::{current_timestamp, current_rfc3339, escape_json_string};
 
 // Re-export all error types for easy importing
 pub use types::*;
+pub use rate_limit::{ErrorRateLimiter, DEFAULT_ERROR_REPEAT_INTERVAL};
 
 mod types;
+mod rate_limit;
+
+/// Default name of the text error log (see `LoggerConfig::error_log_name`)
+pub const DEFAULT_ERROR_LOG_NAME: &str = "logger_errors.log";
+
+/// Default name of the JSON error log (with `LoggerConfig::internal_error_json`)
+pub const DEFAULT_JSON_ERROR_LOG_NAME: &str = "logger_errors.json";
+
+/// Writes an internal error where the configuration sends it
+///
+/// Text or JSON (`internal_error_json`), to the error log file
+/// (`LoggerConfig::error_log_path`) or to stderr (`error_log_to_stderr`).
+/// A missing `error_log_dir` is created; if that fails the error log is
+/// written to the main log directory instead. A configuration without any
+/// directory (console-only) has no error log file, so the error is only
+/// passed to `on_error` by the logger.
+///
+/// # Arguments
+/// * `error` - The LoggerError to write
+/// * `repeated` - How often the error repeated since it was last written (0 for a plain error)
+/// * `config` - Configuration of the logger reporting the error
+pub fn write_repeated_error(error: &LoggerError, repeated: u64, config: &LoggerConfig) {
+    let error_line = if config.internal_error_json {
+        format_error_json_line(error, repeated, config.use_utc)
+    } else {
+        format_error_line(error, repeated, config.use_utc)
+    };
+
+    if config.error_log_to_stderr {
+        let _ = io::stderr().lock().write_all(error_line.as_bytes());
+        return;
+    }
+
+    let directory = match &config.error_log_dir {
+        Some(error_log_dir) if fs::create_dir_all(error_log_dir).is_ok() => error_log_dir,
+        _ => &config.file_path,
+    };
+    if !directory.as_os_str().is_empty() {
+        append_error_line(&directory.join(config.error_log_file_name()), &error_line);
+    }
+}
+
+/// Writes an internal error where the configuration sends it
+///
+/// Same as `write_repeated_error` with a count of 0: honors `use_utc`,
+/// `internal_error_json`, `error_log_name`, `error_log_dir` and
+/// `error_log_to_stderr`.
+///
+/// # Arguments
+/// * `error` - The LoggerError to write
+/// * `config` - Configuration of the logger reporting the error
+pub fn write_error(error: &LoggerError, config: &LoggerConfig) {
+    write_repeated_error(error, 0, config);
+}
 
 // Writes internal logger errors to the error log file
 ///
 /// This function handles errors that occur within the FreedomLogger itself.
-/// It writes to 'logger_errors.log' in the same directory as the main log file.
+/// It writes to 'logger_errors.log' in the given directory, with a local
+/// time timestamp; use `write_error` to follow a logger's configuration
+/// (UTC, JSON, error log name and location).
 /// If this function itself fails (e.g., can't create an error log), it silently fails.
 ///
 /// # Arguments
 /// * `error` - The LoggerError to write to the error log
 /// * `log_directory` - Directory where the main log file is located
-
 pub fn write_error_to_log(error: &LoggerError, log_directory: &Path) {
-    // Create an error log path in the same directory as the main log
-    let error_log_path = log_directory.join("logger_errors.log");
+    let error_line = format_error_line(error, 0, false);
+    append_error_line(&log_directory.join(DEFAULT_ERROR_LOG_NAME), &error_line);
+}
+
+/// Text error log line, with a "(repeated N times)" suffix for a count above 0
+fn format_error_line(error: &LoggerError, repeated: u64, use_utc: bool) -> String {
+    let timestamp = current_timestamp(use_utc);
+    match repeated {
+        0 => format!("[{}] FreedomLogger Error: {}\n", timestamp, error),
+        count => format!("[{}] FreedomLogger Error: {} (repeated {} times)\n", timestamp, error, count),
+    }
+}
+
+/// JSON error log line, with a "repeated" key for a count above 0
+fn format_error_json_line(error: &LoggerError, repeated: u64, use_utc: bool) -> String {
+    let mut error_line = format_error_json(error, &current_rfc3339(use_utc));
+    if repeated > 0 {
+        error_line.pop();
+        error_line.push_str(&format!(",\"repeated\":{}}}", repeated));
+    }
+    error_line.push('\n');
+    error_line
+}
 
-    /*
-    Try to write error - if this fails, we silently give up
-    We cannot recursively handle error from error handling
-     */
+/// Append one line to an error log file
+///
+/// If this fails (e.g. the file can't be created) we silently give up:
+/// errors from error handling can't be handled again without looping.
+fn append_error_line(error_log_path: &Path, error_line: &str) {
     if let Ok(mut file) = OpenOptions::new()
         .create(true)
         .append(true)
         .open(error_log_path)
     {
-        let timestamp = get_current_timestamp();
-        let error_message = format!("[{}] FreedomLogger Error: {}\n", timestamp, error);
-
         // Use BufWriter for better performance when writing errors
         let mut writer = BufWriter::new(&mut file);
-        let _ = writer.write_all(error_message.as_bytes());
+        let _ = writer.write_all(error_line.as_bytes());
         let _ = writer.flush();
     }
+}
+
+/// Convert a LoggerError to a single-line JSON object
+///
+/// Every variant includes "ts", "error_type" and "detail" (the Display text),
+/// followed by the variant's own fields.
+pub fn format_error_json(error: &LoggerError, timestamp: &str) -> String {
+    let mut json_parts = vec![
+        format!("\"ts\":\"{}\"", escape_json_string(timestamp)),
+        format!("\"error_type\":\"{}\"", error.error_type()),
+        format!("\"detail\":\"{}\"", escape_json_string(&error.to_string())),
+    ];
 
-    // If we can't write to the error log, we silently fail.
-    // This prevents infinite error loops
+    let string_field = |name: &str, value: &str| format!("\"{}\":\"{}\"", name, escape_json_string(value));
+
+    match error {
+        LoggerError::FileCreationFailed { path, reason }
+        | LoggerError::DirectoryCreationFailed { path, reason } => {
+            json_parts.push(string_field("path", path));
+            json_parts.push(string_field("reason", reason));
+        }
+        LoggerError::WritePermissionDenied { path } => {
+            json_parts.push(string_field("path", path));
+        }
+        LoggerError::DiskFull { path, bytes_attempted } => {
+            json_parts.push(string_field("path", path));
+            json_parts.push(format!("\"bytes_attempted\":{}", bytes_attempted));
+        }
+        LoggerError::RotationFailed { current_file, backup_file, reason } => {
+            json_parts.push(string_field("current_file", current_file));
+            json_parts.push(string_field("backup_file", backup_file));
+            json_parts.push(string_field("reason", reason));
+        }
+        LoggerError::InvalidConfig { field, reason } => {
+            json_parts.push(string_field("field", field));
+            json_parts.push(string_field("reason", reason));
+        }
+        LoggerError::SerializationFailed { reason }
+        | LoggerError::ChannelSendFailed { reason }
+        | LoggerError::LockPoisoned { reason } => {
+            json_parts.push(string_field("reason", reason));
+        }
+        LoggerError::LowDiskSpace { path, available_bytes, min_free_bytes } => {
+            json_parts.push(string_field("path", path));
+            json_parts.push(format!("\"available_bytes\":{}", available_bytes));
+            json_parts.push(format!("\"min_free_bytes\":{}", min_free_bytes));
+        }
+        LoggerError::AlreadyInitialized => {}
+    }
+
+    format!("{{{}}}", json_parts.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disk_full_json_includes_bytes_attempted() {
+        let error = LoggerError::DiskFull {
+            path: "/logs/app.log".to_string(),
+            bytes_attempted: 42,
+        };
+
+        let json = format_error_json(&error, "2025-09-06T15:30:45Z");
+        assert!(json.starts_with("{\"ts\":\"2025-09-06T15:30:45Z\",\"error_type\":\"DiskFull\""));
+        assert!(json.contains("\"path\":\"/logs/app.log\""));
+        assert!(json.contains("\"bytes_attempted\":42"));
+    }
+
+    #[test]
+    fn test_write_error_follows_config_and_legacy_writes_to_directory() {
+        use crate::core::config::Pattern;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let error = LoggerError::WritePermissionDenied { path: "/readonly/app.log".to_string() };
+
+        write_error_to_log(&error, temp_dir.path());
+        let mut config = LoggerConfig::basic(Pattern::Basic, temp_dir.path().to_path_buf(), "app".to_string());
+        config.use_utc = true;
+        config.error_log_name = Some("internal.log".to_string());
+        write_error(&error, &config);
+
+        let legacy = fs::read_to_string(temp_dir.path().join(DEFAULT_ERROR_LOG_NAME)).unwrap();
+        let configured = fs::read_to_string(temp_dir.path().join("internal.log")).unwrap();
+        assert!(legacy.contains("FreedomLogger Error: "));
+        assert_eq!(configured.lines().count(), 1);
+        assert!(configured.contains("FreedomLogger Error: "));
+    }
 }
-fn get_current_timestamp() -> String {
-    use chrono::{Local, DateTime};
-    let now: DateTime<Local> = Local::now();
-    now.format("%Y-%m-%d %H:%M:%S").to_string()
-}
\ No newline at end of file
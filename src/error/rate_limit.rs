@@ -0,0 +1,114 @@
+// File: src/error/rate_limit.rs
+
+/*
+Deduplication of internal errors before they reach the error log.
+When the log directory becomes unwritable, every log call fails the same
+way - without this, logger_errors.log gets one line per message.
+Identical consecutive errors are collapsed: the first is written, repeats
+are counted, and a "(repeated N times)" summary is written at most once
+per interval (or as soon as a different error arrives).
+ */
+use std::sync::Mutex;
+use std::time::Duration;
+use crate::core::time::Instant;
+use super::LoggerError;
+
+/// Default minimum time between two lines for the same repeating error
+pub const DEFAULT_ERROR_REPEAT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Collapses identical consecutive errors (see module docs)
+#[derive(Debug)]
+pub struct ErrorRateLimiter {
+    interval: Duration,
+    state: Mutex<RateLimitState>,
+}
+
+/// Last written error and the repeats seen since
+#[derive(Debug, Default)]
+struct RateLimitState {
+    last_error: Option<LoggerError>,
+    repeated: u64,
+    last_written: Option<Instant>,
+}
+
+impl ErrorRateLimiter {
+    /// Create a limiter writing a repeating error at most once per `interval`
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            state: Mutex::new(RateLimitState::default()),
+        }
+    }
+
+    /// Register an error and get the lines to write for it
+    ///
+    /// # Returns
+    /// (error, repeat count) pairs to write in order - a count above 0 means
+    /// the line is a "(repeated N times)" summary. Empty while repeats are
+    /// being collapsed.
+    pub fn record(&self, error: &LoggerError) -> Vec<(LoggerError, u64)> {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+
+        if state.last_error.as_ref() == Some(error) {
+            state.repeated += 1;
+            let due = state.last_written.is_none_or(|written| now.duration_since(written) >= self.interval);
+            if !due {
+                return Vec::new();
+            }
+            let repeated = std::mem::take(&mut state.repeated);
+            state.last_written = Some(now);
+            return vec![(error.clone(), repeated)];
+        }
+
+        // A different error: summarize the pending repeats of the previous one first
+        let mut lines = Vec::new();
+        if let Some(previous) = state.last_error.take() {
+            if state.repeated > 0 {
+                lines.push((previous, state.repeated));
+            }
+        }
+        lines.push((error.clone(), 0));
+
+        state.last_error = Some(error.clone());
+        state.repeated = 0;
+        state.last_written = Some(now);
+        lines
+    }
+}
+
+impl Default for ErrorRateLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_ERROR_REPEAT_INTERVAL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn disk_full() -> LoggerError {
+        LoggerError::DiskFull { path: "app.log".to_string(), bytes_attempted: 1 }
+    }
+
+    #[test]
+    fn test_repeats_collapsed_until_different_error() {
+        let limiter = ErrorRateLimiter::default();
+
+        assert_eq!(limiter.record(&disk_full()), vec![(disk_full(), 0)]);
+        for _ in 0..5 {
+            assert!(limiter.record(&disk_full()).is_empty());
+        }
+
+        let other = LoggerError::SerializationFailed { reason: "panic".to_string() };
+        assert_eq!(limiter.record(&other), vec![(disk_full(), 5), (other.clone(), 0)]);
+    }
+
+    #[test]
+    fn test_summary_written_once_per_interval() {
+        let limiter = ErrorRateLimiter::new(Duration::ZERO);
+
+        assert_eq!(limiter.record(&disk_full()), vec![(disk_full(), 0)]);
+        assert_eq!(limiter.record(&disk_full()), vec![(disk_full(), 1)]);
+    }
+}
@@ -7,6 +7,8 @@ When these errors occur, they are silently handled and optionally.
 Written to a separate error log fie for debugging.
  */
 use std::fmt;
+use std::io::{self, ErrorKind};
+use std::path::Path;
 
 /// Represents all possible internal failures of the FreedomLogger.
 #[derive(Debug, Clone, PartialEq)]
@@ -55,6 +57,55 @@ pub enum LoggerError {
         backup_file: String,
         reason: String,
     },
+
+    /*
+    Logger configuration is invalid
+    Occurs when: validating the config at init (empty file name, etc.)
+     */
+    InvalidConfig {
+        field: String,
+        reason: String,
+    },
+
+    /*
+    Formatting or writing a log entry panicked
+    Occurs when: a pattern, formatter or custom writer panics (the panic is caught)
+     */
+    SerializationFailed {
+        reason: String,
+    },
+
+    /*
+    Handing an entry to the async writer thread failed
+    Occurs when: logging in async mode after the worker stopped (e.g. after shutdown)
+     */
+    ChannelSendFailed {
+        reason: String,
+    },
+
+    /*
+    Free space of the log directory fell below the configured minimum
+    Occurs when: writing with `min_free_bytes` set (reported once per low period)
+     */
+    LowDiskSpace {
+        path: String,
+        available_bytes: u64,
+        min_free_bytes: u64,
+    },
+
+    /*
+    A lock of the logger was poisoned by a thread that panicked while holding it
+    Occurs when: writing after such a panic (recovered, reported once)
+     */
+    LockPoisoned {
+        reason: String,
+    },
+
+    /*
+    A global logger is already installed
+    Occurs when: calling `install` after the global logger was initialized
+     */
+    AlreadyInitialized,
 }
 
 impl fmt::Display for LoggerError {
@@ -82,14 +133,168 @@ impl fmt::Display for LoggerError {
             LoggerError::RotationFailed {current_file, backup_file, reason} => {
                 write!(f, "Log rotation failed: '{}' -> '{}': {}", current_file, backup_file, reason)
             }
+
+            LoggerError::InvalidConfig {field, reason} => {
+                write!(f, "Invalid logger configuration '{}': {}", field, reason)
+            }
+
+            LoggerError::SerializationFailed {reason} => {
+                write!(f, "Failed to format log entry: {}", reason)
+            }
+
+            LoggerError::ChannelSendFailed {reason} => {
+                write!(f, "Failed to queue log entry: {}", reason)
+            }
+
+            LoggerError::LowDiskSpace {path, available_bytes, min_free_bytes} => {
+                write!(f, "Low disk space at '{}': {} bytes free, minimum is {}", path, available_bytes, min_free_bytes)
+            }
+
+            LoggerError::LockPoisoned {reason} => {
+                write!(f, "Lock poisoned: {}", reason)
+            }
+
+            LoggerError::AlreadyInitialized => {
+                write!(f, "Global logger is already initialized")
+            }
         }
     }
 }
 
+impl LoggerError {
+    /// Get the variant name, used as "error_type" in the JSON error log
+    pub fn error_type(&self) -> &'static str {
+        match self {
+            LoggerError::FileCreationFailed { .. } => "FileCreationFailed",
+            LoggerError::DirectoryCreationFailed { .. } => "DirectoryCreationFailed",
+            LoggerError::WritePermissionDenied { .. } => "WritePermissionDenied",
+            LoggerError::DiskFull { .. } => "DiskFull",
+            LoggerError::RotationFailed { .. } => "RotationFailed",
+            LoggerError::InvalidConfig { .. } => "InvalidConfig",
+            LoggerError::SerializationFailed { .. } => "SerializationFailed",
+            LoggerError::ChannelSendFailed { .. } => "ChannelSendFailed",
+            LoggerError::LowDiskSpace { .. } => "LowDiskSpace",
+            LoggerError::LockPoisoned { .. } => "LockPoisoned",
+            LoggerError::AlreadyInitialized => "AlreadyInitialized",
+        }
+    }
+
+    /// Check if the error means a log file can't be created or written
+    ///
+    /// Used by strict mode and to decide whether a write is retried.
+    pub fn is_write_failure(&self) -> bool {
+        matches!(
+            self,
+            LoggerError::FileCreationFailed { .. }
+                | LoggerError::DirectoryCreationFailed { .. }
+                | LoggerError::WritePermissionDenied { .. }
+                | LoggerError::DiskFull { .. }
+        )
+    }
+
+    /// Convert an I/O error on a log file to the matching variant
+    ///
+    /// - PermissionDenied -> WritePermissionDenied
+    /// - StorageFull / WriteZero -> DiskFull (0 bytes attempted)
+    /// - anything else -> FileCreationFailed (with the OS error as reason)
+    ///
+    /// # Arguments
+    /// * `error` - The I/O error
+    /// * `path` - Path of the file being opened or written
+    pub fn from_io(error: &io::Error, path: &Path) -> Self {
+        let path = path.display().to_string();
+        match error.kind() {
+            ErrorKind::PermissionDenied => LoggerError::WritePermissionDenied { path },
+            ErrorKind::StorageFull | ErrorKind::WriteZero => LoggerError::DiskFull { path, bytes_attempted: 0 },
+            _ => LoggerError::FileCreationFailed {
+                path,
+                reason: error.to_string(),
+            },
+        }
+    }
+
+    /// Set the number of bytes attempted on a DiskFull error (other variants are unchanged)
+    pub fn with_bytes_attempted(self, bytes: usize) -> Self {
+        match self {
+            LoggerError::DiskFull { path, .. } => LoggerError::DiskFull { path, bytes_attempted: bytes },
+            other => other,
+        }
+    }
+}
+
+/*
+Conversion without a known path (the path is left empty)
+Prefer `LoggerError::from_io` or `IoResultExt` when the path is available.
+ */
+impl From<io::Error> for LoggerError {
+    fn from(error: io::Error) -> Self {
+        LoggerError::from_io(&error, Path::new(""))
+    }
+}
+
+/// Attach the file path (and write size) when converting I/O results
+///
+/// Lets writers use `?` instead of mapping each error by hand:
+/// `append_options(mode).open(path).with_path(path)?`
+pub trait IoResultExt<T> {
+    /// Convert the error with `LoggerError::from_io`
+    fn with_path(self, path: &Path) -> LoggerResult<T>;
+
+    /// Convert the error and record the size of the failed write
+    fn with_write(self, path: &Path, bytes_attempted: usize) -> LoggerResult<T>;
+}
+
+impl<T> IoResultExt<T> for io::Result<T> {
+    fn with_path(self, path: &Path) -> LoggerResult<T> {
+        self.map_err(|error| LoggerError::from_io(&error, path))
+    }
+
+    fn with_write(self, path: &Path, bytes_attempted: usize) -> LoggerResult<T> {
+        self.map_err(|error| LoggerError::from_io(&error, path).with_bytes_attempted(bytes_attempted))
+    }
+}
+
 impl std::error::Error for LoggerError {}
 
 /*
 Result type for logging operations
 Used internally - user never sees this result
  */
-pub type LoggerResult<T> = Result<T, LoggerError>;
\ No newline at end of file
+pub type LoggerResult<T> = Result<T, LoggerError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_io_error_selects_variant() {
+        let convert = |kind: ErrorKind| LoggerError::from(io::Error::from(kind));
+
+        assert_eq!(convert(ErrorKind::PermissionDenied), LoggerError::WritePermissionDenied {
+            path: String::new(),
+        });
+        assert_eq!(convert(ErrorKind::StorageFull), LoggerError::DiskFull {
+            path: String::new(),
+            bytes_attempted: 0,
+        });
+        assert!(matches!(convert(ErrorKind::WriteZero), LoggerError::DiskFull { .. }));
+        assert!(matches!(convert(ErrorKind::NotFound), LoggerError::FileCreationFailed { .. }));
+        assert!(matches!(convert(ErrorKind::Other), LoggerError::FileCreationFailed { .. }));
+    }
+
+    #[test]
+    fn test_io_result_with_path_and_write_size() {
+        let path = Path::new("logs/app.log");
+        let full: io::Result<()> = Err(io::Error::from(ErrorKind::StorageFull));
+        assert_eq!(full.with_write(path, 10), Err(LoggerError::DiskFull {
+            path: "logs/app.log".to_string(),
+            bytes_attempted: 10,
+        }));
+
+        let missing: io::Result<()> = Err(io::Error::new(ErrorKind::NotFound, "no such file"));
+        assert_eq!(missing.with_path(path), Err(LoggerError::FileCreationFailed {
+            path: "logs/app.log".to_string(),
+            reason: "no such file".to_string(),
+        }));
+    }
+}
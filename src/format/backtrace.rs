@@ -0,0 +1,105 @@
+//! Compact backtraces for FreedomLogger
+//!
+//! With `LoggerConfig::backtrace_on_error` every ERROR entry gets the stack
+//! of the logging call appended. The full `std::backtrace::Backtrace` output
+//! is long (two lines per frame, runtime frames, the logger's own frames),
+//! so only the caller's frames are kept, one line each:
+//!
+//! "  at my_app::db::connect (./src/db.rs:42:9)"
+//!
+//! Frames of the logger itself and of the standard library's backtrace
+//! capture are skipped, the list stops at the runtime's entry point and is
+//! limited to `MAX_BACKTRACE_FRAMES` lines.
+
+use std::backtrace::Backtrace;
+
+/// Maximum number of frames kept in a compact backtrace
+pub const MAX_BACKTRACE_FRAMES: usize = 16;
+
+/// Frames of the capture itself and of the logger's call chain
+const SKIPPED_FRAME_PREFIXES: &[&str] = &[
+    "std::backtrace",
+    "FreedomLogger::core::logger::Logger::",
+    "FreedomLogger::log_",
+];
+
+/// Frames where the application's own stack ends
+const STOP_FRAME_MARKERS: &[&str] = &[
+    "__rust_begin_short_backtrace",
+    "std::rt::lang_start",
+];
+
+/// Format a captured backtrace as a compact frame list
+///
+/// # Returns
+/// One "  at function (file:line:column)" line per frame, joined with '\n'
+/// (empty if the backtrace was not captured or has no symbols)
+pub fn compact_backtrace(backtrace: &Backtrace) -> String {
+    compact_frames(&backtrace.to_string())
+}
+
+/// Compact the `Display` output of a backtrace
+///
+/// That output has a "N: function" line per frame, optionally followed by
+/// an "at file:line:column" line.
+fn compact_frames(rendered: &str) -> String {
+    let mut frames: Vec<String> = Vec::new();
+    let mut lines = rendered.lines().map(str::trim).peekable();
+
+    while let Some(line) = lines.next() {
+        let Some(function) = frame_function(line) else {
+            continue;
+        };
+        let location = lines.next_if(|next| next.starts_with("at ")).map(|next| &next[3..]);
+
+        if STOP_FRAME_MARKERS.iter().any(|marker| function.contains(marker)) {
+            break;
+        }
+        if SKIPPED_FRAME_PREFIXES.iter().any(|prefix| function.starts_with(prefix)) {
+            continue;
+        }
+
+        frames.push(match location {
+            Some(location) => format!("  at {} ({})", function, location),
+            None => format!("  at {}", function),
+        });
+        if frames.len() == MAX_BACKTRACE_FRAMES {
+            break;
+        }
+    }
+    frames.join("\n")
+}
+
+/// Function name of a "N: function" frame line
+fn frame_function(line: &str) -> Option<&str> {
+    let (index, function) = line.split_once(": ")?;
+    if index.is_empty() || !index.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    Some(function)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_frames_keeps_caller_frames_only() {
+        let rendered = "   0: std::backtrace::Backtrace::force_capture
+             at /rustc/library/std/src/backtrace.rs:312:13
+   1: FreedomLogger::core::logger::Logger::log
+             at ./src/core/logger.rs:470:25
+   2: my_app::db::connect
+             at ./src/db.rs:42:9
+   3: my_app::main
+   4: std::sys::backtrace::__rust_begin_short_backtrace
+             at /rustc/library/std/src/sys/backtrace.rs:166:18
+   5: std::rt::lang_start::{{closure}}";
+
+        assert_eq!(
+            compact_frames(rendered),
+            "  at my_app::db::connect (./src/db.rs:42:9)\n  at my_app::main"
+        );
+        assert_eq!(compact_frames("disabled backtrace"), "");
+    }
+}
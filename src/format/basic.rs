@@ -7,13 +7,12 @@
 /// or when users want clean, simple logging output.
 ///
 /// Example output: [2025-09-06 15:30:45] INFO: User logged in
-
 use crate::core::config::LogLevel;
 
 /// Information needed to format any log message
 /// This struct contains all posible data that formatters might need
-
-#[derive(Debug)]
+/// (see `OwnedLogInfo` for a copy that can outlive the call)
+#[derive(Debug, PartialEq)]
 pub struct LogInfo<'a> {
     // The log messsage
     pub message: &'a str,
@@ -27,12 +26,23 @@ pub struct LogInfo<'a> {
     pub line: Option<u32>,
     // Thread
     pub thread: Option<&'a str>,
+    // Numeric thread ID (distinguishes unnamed threads)
+    pub thread_id: Option<u64>,
+    // Module path of the call site (set by the logging macros)
+    pub module: Option<&'a str>,
+    // Process ID (with `include_pid`)
+    pub pid: Option<u32>,
+    // Host name (with `include_hostname`)
+    pub hostname: Option<&'a str>,
+    // Sequence number of the entry (with `include_sequence`)
+    pub seq: Option<u64>,
+    // Extra key/value fields (thread context)
+    pub fields: &'a [(String, String)],
 }
 
 impl<'a> LogInfo<'a> {
     /// Create new LogInfo with required fields
     /// Optional fields (file, line, thread) can be set separately
-
     pub fn new(message: &'a str, level: LogLevel, timestamp: &'a str) -> Self {
         Self {
             message,
@@ -41,12 +51,17 @@ impl<'a> LogInfo<'a> {
             file: None,
             line: None,
             thread: None,
+            thread_id: None,
+            module: None,
+            pid: None,
+            hostname: None,
+            seq: None,
+            fields: &[],
         }
     }
 
 
     /// Add file and line information (used by detailled patterns)
-
     pub fn with_location(mut self, file: &'a str, line: u32) -> Self {
         self.file = Some(file);
         self.line = Some(line);
@@ -59,6 +74,42 @@ impl<'a> LogInfo<'a> {
         self.thread = Some(thread);
         self
     }
+
+    /// Add the numeric thread ID (used by Extended, JSON and custom patterns)
+    pub fn with_thread_id(mut self, thread_id: u64) -> Self {
+        self.thread_id = Some(thread_id);
+        self
+    }
+
+    /// Add the module path of the call site (used by JSON and custom patterns)
+    pub fn with_module(mut self, module: &'a str) -> Self {
+        self.module = Some(module);
+        self
+    }
+
+    /// Add the process ID (used by JSON and custom patterns)
+    pub fn with_pid(mut self, pid: u32) -> Self {
+        self.pid = Some(pid);
+        self
+    }
+
+    /// Add the host name (used by JSON and custom patterns)
+    pub fn with_hostname(mut self, hostname: &'a str) -> Self {
+        self.hostname = Some(hostname);
+        self
+    }
+
+    /// Add the sequence number (used by JSON and custom patterns)
+    pub fn with_seq(mut self, seq: u64) -> Self {
+        self.seq = Some(seq);
+        self
+    }
+
+    /// Add extra key/value fields (used by all patterns)
+    pub fn with_fields(mut self, fields: &'a [(String, String)]) -> Self {
+        self.fields = fields;
+        self
+    }
 }
 
 
@@ -75,13 +126,25 @@ impl<'a> LogInfo<'a> {
 ///
 pub fn format_basic(info: &LogInfo) -> String {
     format!(
-        "[{}] {}: {}",
+        "[{}] {}: {}{}",
         info.timestamp,
         info.level.as_str(),
-        info.message
+        info.message,
+        format_fields(info)
     )
 }
 
+/// Format extra fields as a suffix for text patterns
+///
+/// # Returns
+/// " key=value key2=value2", or an empty string when there are no fields
+pub fn format_fields(info: &LogInfo) -> String {
+    info.fields
+        .iter()
+        .map(|(key, value)| format!(" {}={}", key, value))
+        .collect()
+}
+
 
 /*
 TESTS FOR THE BASIC LOGGER
@@ -125,4 +188,17 @@ mod tests {
         let result = format_basic(&info);
         assert_eq!(result, "[2025-09-06 15:30:45] WARNING: Test");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_basic_formatting_with_fields() {
+        let fields = vec![
+            ("request_id".to_string(), "42".to_string()),
+            ("user".to_string(), "alice".to_string()),
+        ];
+        let info = LogInfo::new("Test", LogLevel::Info, "2025-09-06 15:30:45")
+            .with_fields(&fields);
+
+        let result = format_basic(&info);
+        assert_eq!(result, "[2025-09-06 15:30:45] INFO: Test request_id=42 user=alice");
+    }
+}
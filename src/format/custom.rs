@@ -0,0 +1,92 @@
+//! Custom pattern formatter for FreedomLogger
+//!
+//! Replaces placeholders in a user-defined pattern with the log information.
+//! Supported placeholders:
+//! - {timestamp}, {level}, {message}
+//! - {file}, {line}, {thread}, {thread_id}, {module}, {pid}, {hostname}, {seq} (empty when not available)
+//!
+//! - {key} for a context or static field, e.g. {request_id} (the built-in names win)
+//!
+//! Fields without a placeholder are appended after the pattern, like the
+//! built-in patterns.
+//!
+//! Example: "{level} {module} - {message}" gives "INFO my_app::db - Connected"
+
+use super::basic::LogInfo;
+
+/// Format a log message using a custom pattern
+///
+/// # Arguments
+/// * `pattern` - Pattern string with placeholders (must contain {message})
+/// * `info` - All log information
+///
+/// # Returns
+/// Formatted string ready to write to log file
+pub fn format_custom(pattern: &str, info: &LogInfo) -> String {
+    let line = info.line.map(|line| line.to_string()).unwrap_or_default();
+    let thread_id = info.thread_id.map(|id| id.to_string()).unwrap_or_default();
+    let pid = info.pid.map(|pid| pid.to_string()).unwrap_or_default();
+    let seq = info.seq.map(|seq| seq.to_string()).unwrap_or_default();
+
+    // {message} is replaced last so placeholders inside the message stay untouched
+    let formatted = pattern
+        .replace("{timestamp}", info.timestamp)
+        .replace("{level}", info.level.as_str())
+        .replace("{file}", info.file.unwrap_or(""))
+        .replace("{line}", &line)
+        .replace("{thread_id}", &thread_id)
+        .replace("{thread}", info.thread.unwrap_or(""))
+        .replace("{module}", info.module.unwrap_or(""))
+        .replace("{pid}", &pid)
+        .replace("{hostname}", info.hostname.unwrap_or(""))
+        .replace("{seq}", &seq);
+
+    let mut suffix = String::new();
+    let formatted = info.fields.iter().fold(formatted, |formatted, (key, value)| {
+        let placeholder = format!("{{{}}}", key);
+        if formatted.contains(&placeholder) {
+            formatted.replace(&placeholder, value)
+        } else {
+            suffix.push_str(&format!(" {}={}", key, value));
+            formatted
+        }
+    });
+
+    format!("{}{}", formatted.replace("{message}", info.message), suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::LogLevel;
+
+    #[test]
+    fn test_custom_placeholders() {
+        let info = LogInfo::new("Connected", LogLevel::Info, "2025-09-06 15:30:45")
+            .with_location("db.rs", 7)
+            .with_module("my_app::db");
+
+        let result = format_custom("{timestamp} {level} {module} [{file}:{line}] {message}", &info);
+        assert_eq!(result, "2025-09-06 15:30:45 INFO my_app::db [db.rs:7] Connected");
+    }
+
+    #[test]
+    fn test_field_placeholders_and_appended_fields() {
+        let fields = vec![
+            ("service".to_string(), "api".to_string()),
+            ("request_id".to_string(), "42".to_string()),
+        ];
+        let info = LogInfo::new("Hello {request_id}", LogLevel::Info, "ts").with_fields(&fields);
+
+        let result = format_custom("[{service}] {level}: {message}", &info);
+        assert_eq!(result, "[api] INFO: Hello {request_id} request_id=42");
+    }
+
+    #[test]
+    fn test_missing_values_are_empty() {
+        let info = LogInfo::new("Hello {module}", LogLevel::Warning, "ts");
+
+        let result = format_custom("{level}<{module}> {message}", &info);
+        assert_eq!(result, "WARNING<> Hello {module}");
+    }
+}
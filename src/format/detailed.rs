@@ -1,13 +1,12 @@
-/// Detailed pattern formatter for FreedomLogger
-///
-/// Implements detailed log format: [TIMESTAMP] [FILE:LINE] LEVEL: MESSAGE
-/// This adds source file and line number information to help with debugging.
-/// Useful for development and detailed production logging.
-///
-/// Example output: [2025-09-06 15:30:45] [main.rs:42] INFO: User logged in
+//! Detailed pattern formatter for FreedomLogger
+//!
+//! Implements detailed log format: [TIMESTAMP] [FILE:LINE] LEVEL: MESSAGE
+//! This adds source file and line number information to help with debugging.
+//! Useful for development and detailed production logging.
+//!
+//! Example output: [2025-09-06 15:30:45] [main.rs:42] INFO: User logged in
 
-use super::basic::LogInfo;
-use crate::core::config::LogLevel;
+use super::basic::{LogInfo, format_fields};
 
 /// Format a log message using the Detailed pattern
 ///
@@ -20,39 +19,41 @@ use crate::core::config::LogLevel;
 /// # Returns
 /// Formatted string ready to write to log file
 ///
-
 pub fn format_detailed(info: &LogInfo) -> String {
     match (info.file, info.line) {
         // Both file and line available - full detailed format
         (Some(file), Some(line)) => {
             format!(
-                "[{}] [{}:{}] {}: {}",
+                "[{}] [{}:{}] {}: {}{}",
                 info.timestamp,
                 file,
                 line,
                 info.level.as_str(),
-                info.message
+                info.message,
+                format_fields(info)
             )
         }
 
         // Only file available - show file without line
         (Some(file), None) => {
             format!(
-                "[{}] [{}] {}: {}",
+                "[{}] [{}] {}: {}{}",
                 info.timestamp,
                 file,
                 info.level.as_str(),
-                info.message
+                info.message,
+                format_fields(info)
             )
         }
 
         // No file/line info available - fallback to basic format
         (None, _) => {
             format!(
-                "[{}] {}: {}",
+                "[{}] {}: {}{}",
                 info.timestamp,
                 info.level.as_str(),
-                info.message
+                info.message,
+                format_fields(info)
             )
         }
     }
@@ -61,6 +62,7 @@ pub fn format_detailed(info: &LogInfo) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::config::LogLevel;
 
     #[test]
     fn test_detailed_formatting_with_full_location() {
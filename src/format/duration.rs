@@ -0,0 +1,98 @@
+//! Human-readable durations for FreedomLogger
+//!
+//! Used for the "took ..." part of span exit lines, and available for
+//! logging manual timers.
+//!
+//! Rounding rules:
+//! - below 1µs: whole nanoseconds ("850ns"); zero is "0s"
+//! - below 1 minute: the largest fitting unit (µs, ms, s) with at most two
+//!   decimals, rounded half up, trailing zeros dropped ("1.23ms", "4.5s")
+//! - from 1 minute: whole seconds, rounded half up ("2m10s", "1h0m5s")
+//!
+//! A value that rounds up to 1000 of a unit moves to the next unit
+//! (999.996µs is "1ms"), and 59.995s or more is "1m0s".
+//!
+//! Example output: "exited load_config (took 1.23ms)"
+
+use std::time::Duration;
+
+const NANOS_PER_MICRO: u128 = 1_000;
+const NANOS_PER_MILLI: u128 = 1_000_000;
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// Format a duration with human units
+///
+/// # Arguments
+/// * `duration` - The duration to format
+///
+/// # Returns
+/// The duration as e.g. "850ns", "12.3µs", "1.23ms", "4.5s" or "2m10s"
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos == 0 {
+        return "0s".to_string();
+    }
+    if nanos < NANOS_PER_MICRO {
+        return format!("{}ns", nanos);
+    }
+
+    for (unit, suffix, limit) in [
+        (NANOS_PER_MICRO, "µs", 1000),
+        (NANOS_PER_MILLI, "ms", 1000),
+        (NANOS_PER_SECOND, "s", 60),
+    ] {
+        let hundredths = (nanos * 100 + unit / 2) / unit;
+        if hundredths < limit * 100 {
+            return format!("{}{}", format_hundredths(hundredths), suffix);
+        }
+    }
+
+    let seconds = (nanos + NANOS_PER_SECOND / 2) / NANOS_PER_SECOND;
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}h{}m{}s", hours, minutes, seconds)
+    } else {
+        format!("{}m{}s", minutes, seconds)
+    }
+}
+
+/// Write a number of hundredths with up to two decimals, without trailing zeros
+fn format_hundredths(hundredths: u128) -> String {
+    let (whole, fraction) = (hundredths / 100, hundredths % 100);
+    match fraction {
+        0 => whole.to_string(),
+        _ if fraction % 10 == 0 => format!("{}.{}", whole, fraction / 10),
+        _ => format!("{}.{:02}", whole, fraction),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_and_sub_microsecond() {
+        assert_eq!(format_duration(Duration::ZERO), "0s");
+        assert_eq!(format_duration(Duration::from_nanos(1)), "1ns");
+        assert_eq!(format_duration(Duration::from_nanos(850)), "850ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.35µs");
+        assert_eq!(format_duration(Duration::from_nanos(999_996)), "1ms");
+    }
+
+    #[test]
+    fn test_milliseconds_and_seconds() {
+        assert_eq!(format_duration(Duration::from_micros(1_234)), "1.23ms");
+        assert_eq!(format_duration(Duration::from_micros(1_500)), "1.5ms");
+        assert_eq!(format_duration(Duration::from_millis(250)), "250ms");
+        assert_eq!(format_duration(Duration::from_millis(4_500)), "4.5s");
+        assert_eq!(format_duration(Duration::from_millis(59_990)), "59.99s");
+    }
+
+    #[test]
+    fn test_minutes_and_hours() {
+        assert_eq!(format_duration(Duration::from_millis(59_995)), "1m0s");
+        assert_eq!(format_duration(Duration::from_millis(130_400)), "2m10s");
+        assert_eq!(format_duration(Duration::from_millis(130_500)), "2m11s");
+        assert_eq!(format_duration(Duration::from_secs(3_605)), "1h0m5s");
+    }
+}
@@ -0,0 +1,67 @@
+//! Extended pattern formatter for FreedomLogger
+//!
+//! Implements extended log format: [TIMESTAMP] [FILE:LINE] [THREAD#ID] LEVEL: MESSAGE
+//! Adds the thread name and numeric thread ID to the Detailed pattern, so
+//! lines from different (possibly unnamed) threads can be told apart.
+//!
+//! Example output: [2025-09-06 15:30:45] [main.rs:42] [worker#3] INFO: Job done
+
+use super::basic::{LogInfo, format_fields};
+
+/// Format a log message using the Extended pattern
+///
+/// Extended pattern format: [TIMESTAMP] [FILE:LINE] [THREAD#ID] LEVEL: MESSAGE
+/// Location and thread parts are left out when not available.
+///
+/// # Arguments
+/// * `info` - All log information (uses message, level, timestamp, file, line, thread, thread_id)
+///
+/// # Returns
+/// Formatted string ready to write to log file
+pub fn format_extended(info: &LogInfo) -> String {
+    let location = match (info.file, info.line) {
+        (Some(file), Some(line)) => format!(" [{}:{}]", file, line),
+        (Some(file), None) => format!(" [{}]", file),
+        (None, _) => String::new(),
+    };
+
+    let thread = match (info.thread, info.thread_id) {
+        (Some(name), Some(id)) => format!(" [{}#{}]", name, id),
+        (Some(name), None) => format!(" [{}]", name),
+        (None, Some(id)) => format!(" [#{}]", id),
+        (None, None) => String::new(),
+    };
+
+    format!(
+        "[{}]{}{} {}: {}{}",
+        info.timestamp,
+        location,
+        thread,
+        info.level.as_str(),
+        info.message,
+        format_fields(info)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::LogLevel;
+
+    #[test]
+    fn test_extended_formatting() {
+        let info = LogInfo::new("Job done", LogLevel::Info, "2025-09-06 15:30:45")
+            .with_location("main.rs", 42)
+            .with_thread("worker")
+            .with_thread_id(3);
+
+        assert_eq!(format_extended(&info), "[2025-09-06 15:30:45] [main.rs:42] [worker#3] INFO: Job done");
+    }
+
+    #[test]
+    fn test_extended_without_optional_parts() {
+        let info = LogInfo::new("Bare", LogLevel::Warning, "2025-09-06 15:30:45");
+
+        assert_eq!(format_extended(&info), "[2025-09-06 15:30:45] WARNING: Bare");
+    }
+}
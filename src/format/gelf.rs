@@ -0,0 +1,140 @@
+//! GELF pattern formatter for FreedomLogger
+//!
+//! Implements the Gelf pattern: one GELF 1.1 JSON object per line, as
+//! expected by Graylog:
+//! - version: "1.1"
+//! - host: host name of the entry ("unknown" when not available)
+//! - short_message: the log message
+//! - timestamp: seconds since the Unix epoch with milliseconds (number)
+//! - level: syslog severity (ERROR 3, WARNING 4, INFO 6, DEBUG/TRACE 7)
+//! - additional fields, prefixed with `_`: _file, _line, _module, _thread,
+//!   _thread_id, _pid, _seq and every context/static field
+//!
+//! GELF field names may only contain letters, digits, `_`, `.` and `-`;
+//! other characters in field keys become `_`. The reserved `_id` is written
+//! as `__id`.
+//!
+//! Example output:
+//! {"version":"1.1","host":"web-1","short_message":"User logged in","timestamp":1757169045.123,"level":6,"_file":"main.rs","_line":42}
+
+use crate::core::time::{self, Timestamp};
+use super::basic::LogInfo;
+use super::json::escape_json_string;
+
+/// Host sent when the entry carries no host name
+const UNKNOWN_HOST: &str = "unknown";
+
+/// Format a GELF timestamp: Unix seconds with three decimals ("1757169045.123")
+///
+/// The logger puts this into `LogInfo::timestamp` for the Gelf pattern.
+pub fn format_gelf_timestamp(instant: Timestamp) -> String {
+    let millis = time::unix_millis(instant);
+    format!("{}.{:03}", millis.div_euclid(1000), millis.rem_euclid(1000))
+}
+
+/// Format a log message using the Gelf pattern
+///
+/// # Arguments
+/// * `info` - All log information; `timestamp` must be a number such as
+///   `format_gelf_timestamp` produces (anything else is sent as 0)
+///
+/// # Returns
+/// One GELF JSON object, without line ending
+pub fn format_gelf(info: &LogInfo) -> String {
+    let timestamp = if info.timestamp.parse::<f64>().is_ok() { info.timestamp } else { "0" };
+
+    let mut gelf = format!(
+        "{{\"version\":\"1.1\",\"host\":\"{}\",\"short_message\":\"{}\",\"timestamp\":{},\"level\":{}",
+        escape_json_string(info.hostname.unwrap_or(UNKNOWN_HOST)),
+        escape_json_string(info.message),
+        timestamp,
+        info.level.syslog_severity(),
+    );
+
+    let mut push_string = |key: &str, value: &str| {
+        gelf.push_str(&format!(",\"{}\":\"{}\"", field_name(key), escape_json_string(value)));
+    };
+    if let Some(file) = info.file {
+        push_string("file", file);
+    }
+    if let Some(module) = info.module {
+        push_string("module", module);
+    }
+    if let Some(thread) = info.thread {
+        push_string("thread", thread);
+    }
+    for (key, value) in info.fields {
+        push_string(key, value);
+    }
+
+    let numbers = [
+        ("line", info.line.map(u64::from)),
+        ("thread_id", info.thread_id),
+        ("pid", info.pid.map(u64::from)),
+        ("seq", info.seq),
+    ];
+    for (key, value) in numbers {
+        if let Some(value) = value {
+            gelf.push_str(&format!(",\"{}\":{}", field_name(key), value));
+        }
+    }
+
+    gelf.push('}');
+    gelf
+}
+
+/// GELF additional field name for a key: `_` prefix, invalid characters as `_`
+fn field_name(key: &str) -> String {
+    let name: String = key
+        .chars()
+        .map(|character| match character {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '.' | '-' => character,
+            _ => '_',
+        })
+        .collect();
+    if name == "id" {
+        "__id".to_string()
+    } else {
+        format!("_{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::LogLevel;
+    use crate::core::time::timestamp_from_utc;
+
+    #[test]
+    fn test_gelf_fields_and_types() {
+        let instant = timestamp_from_utc(2025, 9, 6, 13, 30, 45).unwrap() + std::time::Duration::from_millis(120);
+        let timestamp = format_gelf_timestamp(instant);
+        assert_eq!(timestamp, "1757165445.120");
+
+        let fields = vec![("request id".to_string(), "abc\"1".to_string()), ("id".to_string(), "7".to_string())];
+        let info = LogInfo::new("Disk almost full", LogLevel::Warning, &timestamp)
+            .with_location("main.rs", 42)
+            .with_hostname("web-1")
+            .with_fields(&fields);
+
+        let gelf: serde_json::Value = serde_json::from_str(&format_gelf(&info)).unwrap();
+        assert_eq!(gelf["version"], "1.1");
+        assert_eq!(gelf["host"], "web-1");
+        assert_eq!(gelf["short_message"], "Disk almost full");
+        assert_eq!(gelf["level"].as_u64(), Some(4));
+        assert_eq!(gelf["timestamp"].as_f64(), Some(1_757_165_445.12));
+        assert_eq!(gelf["_file"], "main.rs");
+        assert_eq!(gelf["_line"].as_u64(), Some(42));
+        assert_eq!(gelf["_request_id"], "abc\"1");
+        assert_eq!(gelf["__id"], "7");
+    }
+
+    #[test]
+    fn test_gelf_without_host_or_numeric_timestamp() {
+        let info = LogInfo::new("plain", LogLevel::Error, "2025-09-06 13:30:45");
+        assert_eq!(
+            format_gelf(&info),
+            "{\"version\":\"1.1\",\"host\":\"unknown\",\"short_message\":\"plain\",\"timestamp\":0,\"level\":3}"
+        );
+    }
+}
@@ -0,0 +1,73 @@
+//! Hexdump formatting for FreedomLogger
+//!
+//! Turns a byte buffer into the classic offset + hex + ASCII layout, so
+//! protocol data stays readable in a log file instead of the `{:?}` output
+//! of a `Vec<u8>`. Used by `log_hex!` with `LoggerConfig::hexdump_width`
+//! bytes per line.
+//!
+//! Example output (width 8):
+//! "00000000  48 65 6c 6c 6f 0a 00 ff  |Hello...|"
+
+use std::fmt::Write;
+
+/// Default number of bytes per hexdump line
+pub const DEFAULT_HEXDUMP_WIDTH: usize = 16;
+
+/// Format bytes as hexdump lines
+///
+/// Each line has the offset (8 hex digits), the bytes in hex and the same
+/// bytes as ASCII between bars, with non-printable bytes shown as '.'.
+/// The hex column of a short last line is padded so the ASCII column lines up.
+///
+/// # Arguments
+/// * `bytes` - The buffer to dump
+/// * `width` - Bytes per line (0 is treated as 1)
+///
+/// # Returns
+/// The lines joined with '\n' (empty for an empty buffer)
+pub fn format_hexdump(bytes: &[u8], width: usize) -> String {
+    let width = width.max(1);
+    let mut dump = String::new();
+
+    for (index, chunk) in bytes.chunks(width).enumerate() {
+        if index > 0 {
+            dump.push('\n');
+        }
+        let _ = write!(dump, "{:08x} ", index * width);
+
+        for byte in chunk {
+            let _ = write!(dump, " {:02x}", byte);
+        }
+        for _ in chunk.len()..width {
+            dump.push_str("   ");
+        }
+
+        dump.push_str("  |");
+        dump.extend(chunk.iter().map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            }
+        }));
+        dump.push('|');
+    }
+
+    dump
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hexdump_columns_and_padding() {
+        let dump = format_hexdump(b"Hello\n\x00\xffWorld", 8);
+        let lines: Vec<&str> = dump.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], "00000000  48 65 6c 6c 6f 0a 00 ff  |Hello...|");
+        assert_eq!(lines[1], "00000008  57 6f 72 6c 64           |World|");
+        assert_eq!(format_hexdump(&[], 16), "");
+    }
+}
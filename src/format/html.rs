@@ -0,0 +1,107 @@
+//! HTML pattern formatter for FreedomLogger
+//!
+//! Implements the Html pattern: each entry is one table row with the cells
+//! time, level, location, thread and message, for embedding logs in a web
+//! report. The level cell is colored with `LogLevel::html_color`.
+//!
+//! The rows go between `HTML_TABLE_START` and `HTML_TABLE_END`, which the
+//! HTML file writer adds once per file.
+//!
+//! Example output:
+//! <tr class="info"><td>2025-09-06 15:30:45</td><td style="color:#388e3c">INFO</td><td>main.rs:42</td><td>main</td><td>User logged in</td></tr>
+
+use std::borrow::Cow;
+use super::basic::{LogInfo, format_fields};
+
+/// Start of the table written at the top of an HTML log file (with header row)
+pub const HTML_TABLE_START: &str = "<table class=\"freedomlogger\">\
+<tr><th>Time</th><th>Level</th><th>Location</th><th>Thread</th><th>Message</th></tr>";
+
+/// End of the table, written when the HTML log file is flushed
+pub const HTML_TABLE_END: &str = "</table>";
+
+/// Escape text for use inside an HTML element or attribute
+///
+/// Replaces `&`, `<`, `>`, `"` and `'` with character references, so
+/// user-controlled text (e.g. "<script>") is shown instead of interpreted.
+/// Borrows the input when nothing needs escaping.
+pub fn escape_html(input: &str) -> Cow<'_, str> {
+    if !input.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::Borrowed(input);
+    }
+
+    let mut escaped = String::with_capacity(input.len() + 16);
+    for character in input.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            character => escaped.push(character),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Format a log message using the Html pattern
+///
+/// Location and thread cells are empty when not available; fields follow
+/// the message like in the text patterns.
+///
+/// # Arguments
+/// * `info` - All log information (uses message, level, timestamp, file, line, thread, fields)
+///
+/// # Returns
+/// One `<tr>` row, without line ending
+pub fn format_html(info: &LogInfo) -> String {
+    let location = match (info.file, info.line) {
+        (Some(file), Some(line)) => format!("{}:{}", file, line),
+        (Some(file), None) => file.to_string(),
+        (None, _) => String::new(),
+    };
+    let message = format!("{}{}", info.message, format_fields(info));
+
+    format!(
+        "<tr class=\"{}\"><td>{}</td><td style=\"color:{}\">{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+        info.level.as_str().to_ascii_lowercase(),
+        escape_html(info.timestamp),
+        info.level.html_color(),
+        info.level.as_str(),
+        escape_html(&location),
+        escape_html(info.thread.unwrap_or_default()),
+        escape_html(&message),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::LogLevel;
+
+    #[test]
+    fn test_html_row() {
+        let info = LogInfo::new("User logged in", LogLevel::Info, "2025-09-06 15:30:45")
+            .with_location("main.rs", 42)
+            .with_thread("main");
+
+        assert_eq!(
+            format_html(&info),
+            "<tr class=\"info\"><td>2025-09-06 15:30:45</td><td style=\"color:#388e3c\">INFO</td>\
+             <td>main.rs:42</td><td>main</td><td>User logged in</td></tr>"
+        );
+    }
+
+    #[test]
+    fn test_html_escapes_message_file_and_thread() {
+        let info = LogInfo::new("<script>alert('x')</script> & \"more\"", LogLevel::Error, "2025-09-06 15:30:45")
+            .with_location("<a>.rs", 1)
+            .with_thread("t<1>");
+
+        let row = format_html(&info);
+        assert!(!row.contains("<script>"));
+        assert!(row.contains("<td>&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt; &amp; &quot;more&quot;</td>"));
+        assert!(row.contains("<td>&lt;a&gt;.rs:1</td><td>t&lt;1&gt;</td>"));
+        assert!(matches!(escape_html("plain"), Cow::Borrowed("plain")));
+    }
+}
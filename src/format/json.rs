@@ -0,0 +1,39 @@
+//! JSON helpers for FreedomLogger
+//!
+//! The crate builds JSON by hand to avoid external dependencies.
+//! These helpers are shared by the JSON writer and the internal error log.
+
+/// Escape special characters in JSON strings
+///
+/// Handles quotes, backslashes, newlines and other characters that need
+/// escaping inside a JSON string value. Every control character is escaped
+/// (\n, \r, \t, or \u00XX for the others), so a value never contains a
+/// literal line break and each JSONL entry stays on one physical line.
+pub fn escape_json_string(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for character in input.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            character if u32::from(character) < 0x20 => {
+                escaped.push_str(&format!("\\u{:04x}", u32::from(character)));
+            }
+            character => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_json_string() {
+        assert_eq!(escape_json_string("C:\\logs \"app\"\n"), "C:\\\\logs \\\"app\\\"\\n");
+        assert_eq!(escape_json_string("tab\tnul\0ff\x0c"), "tab\\tnul\\u0000ff\\u000c");
+    }
+}
@@ -10,23 +10,46 @@
 /// The formatters are organized from simple to complex:
 /// - Basic: Just timestamp, level, message
 /// - Detailed: Adds file and line information
-/// - Extended: Adds thread information (TODO)
+/// - Extended: Adds thread name and ID
 /// - Json: Structured JSON output (TODO)
-/// - Custom: User-defined patterns (TODO)
-
+/// - Gelf: GELF 1.1 JSON lines for Graylog
+/// - Html: One table row per entry, for web reports
+/// - Custom: User-defined patterns with {placeholders}
 // Re-export LogInfo struct for other modules to use
 pub use basic::LogInfo;
+pub use owned::OwnedLogInfo;
 
 // Re-export all formatter functions
-pub use basic::format_basic;
+pub use basic::{format_basic, format_fields};
 pub use detailed::format_detailed;
+pub use extended::format_extended;
+pub use gelf::{format_gelf, format_gelf_timestamp};
+pub use html::{format_html, escape_html, HTML_TABLE_START, HTML_TABLE_END};
+pub use custom::format_custom;
+pub use truncate::truncate_message;
+pub use sanitize::sanitize_text;
+pub use hexdump::{format_hexdump, DEFAULT_HEXDUMP_WIDTH};
+pub use backtrace::{compact_backtrace, MAX_BACKTRACE_FRAMES};
+pub use duration::format_duration;
+#[cfg(feature = "redaction")]
+pub use redact::{redact, Redactor, email_redactor, card_number_redactor, bearer_token_redactor};
+pub use json::escape_json_string;
+pub use timestamp::{current_timestamp, current_rfc3339, format_timestamp, format_rfc3339, TimestampCache};
 
 // Import the formatter functions
+pub mod backtrace;
 pub mod basic;
+pub mod custom;
 pub mod detailed;
-
-
-// TODO: Future formatters to implement
-// pub mod extended;
-// pub mod json;
-// pub mod custom;
\ No newline at end of file
+pub mod duration;
+pub mod extended;
+pub mod gelf;
+pub mod hexdump;
+pub mod html;
+pub mod json;
+pub mod owned;
+#[cfg(feature = "redaction")]
+pub mod redact;
+pub mod sanitize;
+pub mod timestamp;
+pub mod truncate;
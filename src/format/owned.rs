@@ -0,0 +1,111 @@
+//! Owned log information for FreedomLogger
+//!
+//! `LogInfo<'a>` borrows every string, which keeps formatting allocation
+//! free but ties it to the caller's stack frame. `OwnedLogInfo` holds the
+//! same data as owned values, so a custom `LogWriter` can keep an entry or
+//! send it to another thread (e.g. its own batching thread), and later turn
+//! it back into a `LogInfo` for the formatters.
+//!
+//! Example: `info.to_owned()` in `write_entry`, then
+//! `format_basic(&owned.as_log_info())` on the receiving thread
+//!
+//! The logger's own async mode doesn't need it: it queues the entry as
+//! captured on the calling thread and builds the `LogInfo` on the worker.
+
+use crate::core::config::LogLevel;
+use super::LogInfo;
+
+/// `LogInfo` with owned fields (Send + 'static)
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnedLogInfo {
+    pub message: String,
+    pub level: LogLevel,
+    pub timestamp: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub thread: Option<String>,
+    pub thread_id: Option<u64>,
+    pub module: Option<String>,
+    pub pid: Option<u32>,
+    pub hostname: Option<String>,
+    pub seq: Option<u64>,
+    pub fields: Vec<(String, String)>,
+}
+
+impl OwnedLogInfo {
+    /// Borrow the fields as a `LogInfo` for formatting
+    pub fn as_log_info(&self) -> LogInfo<'_> {
+        LogInfo {
+            message: &self.message,
+            level: self.level,
+            timestamp: &self.timestamp,
+            file: self.file.as_deref(),
+            line: self.line,
+            thread: self.thread.as_deref(),
+            thread_id: self.thread_id,
+            module: self.module.as_deref(),
+            pid: self.pid,
+            hostname: self.hostname.as_deref(),
+            seq: self.seq,
+            fields: &self.fields,
+        }
+    }
+}
+
+impl From<&LogInfo<'_>> for OwnedLogInfo {
+    fn from(info: &LogInfo<'_>) -> Self {
+        Self {
+            message: info.message.to_string(),
+            level: info.level,
+            timestamp: info.timestamp.to_string(),
+            file: info.file.map(str::to_string),
+            line: info.line,
+            thread: info.thread.map(str::to_string),
+            thread_id: info.thread_id,
+            module: info.module.map(str::to_string),
+            pid: info.pid,
+            hostname: info.hostname.map(str::to_string),
+            seq: info.seq,
+            fields: info.fields.to_vec(),
+        }
+    }
+}
+
+impl LogInfo<'_> {
+    /// Copy the borrowed fields into an `OwnedLogInfo`
+    pub fn to_owned(&self) -> OwnedLogInfo {
+        OwnedLogInfo::from(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::format_extended;
+
+    #[test]
+    fn test_round_trip_keeps_every_field() {
+        let fields = vec![("request_id".to_string(), "42".to_string())];
+        let info = LogInfo::new("Test message", LogLevel::Warning, "2025-09-06 15:30:45")
+            .with_location("main.rs", 42)
+            .with_thread("worker")
+            .with_thread_id(7)
+            .with_module("app::db")
+            .with_pid(1234)
+            .with_hostname("host-1")
+            .with_seq(3)
+            .with_fields(&fields);
+
+        let owned = std::thread::spawn({
+            let owned = info.to_owned();
+            move || owned
+        })
+        .join()
+        .unwrap();
+        let borrowed = owned.as_log_info();
+
+        assert_eq!(borrowed, info);
+        assert_eq!(OwnedLogInfo::from(&borrowed), owned);
+        assert_eq!(format_extended(&borrowed), format_extended(&info));
+    }
+}
@@ -0,0 +1,80 @@
+//! Redaction of sensitive data for FreedomLogger (`redaction` feature)
+//!
+//! Masks secrets (emails, tokens, card numbers, ...) in the message before it
+//! is formatted, so they never reach the log file - in text or JSON output.
+//! Configured with `LoggerConfig::redactors`: a list of (pattern, replacement)
+//! pairs applied in order.
+//!
+//! Built-in helpers cover common patterns:
+//! `config.redactors = vec![email_redactor("***"), bearer_token_redactor("***")];`
+
+use std::borrow::Cow;
+use regex::Regex;
+
+/// A pattern to mask and the text that replaces each match
+pub type Redactor = (Regex, String);
+
+/// Apply all redactors to a message
+///
+/// # Arguments
+/// * `message` - The raw message
+/// * `redactors` - (pattern, replacement) pairs, applied in order
+///
+/// # Returns
+/// The message with every match replaced (borrowed when nothing matched)
+pub fn redact<'a>(message: &'a str, redactors: &[Redactor]) -> Cow<'a, str> {
+    let mut redacted = Cow::Borrowed(message);
+    for (pattern, replacement) in redactors {
+        if let Cow::Owned(replaced) = pattern.replace_all(&redacted, replacement.as_str()) {
+            redacted = Cow::Owned(replaced);
+        }
+    }
+    redacted
+}
+
+/// Redactor for email addresses
+pub fn email_redactor(replacement: &str) -> Redactor {
+    builtin(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}", replacement)
+}
+
+/// Redactor for card-like numbers: 13 to 19 digits, optionally grouped by spaces or dashes
+pub fn card_number_redactor(replacement: &str) -> Redactor {
+    builtin(r"\b\d(?:[ -]?\d){12,18}\b", replacement)
+}
+
+/// Redactor for bearer tokens in authorization values ("Bearer <token>")
+pub fn bearer_token_redactor(replacement: &str) -> Redactor {
+    builtin(r"(?i)bearer\s+[A-Za-z0-9\-._~+/]+=*", replacement)
+}
+
+/// Build a redactor from a built-in pattern (known to be valid)
+fn builtin(pattern: &str, replacement: &str) -> Redactor {
+    let regex = Regex::new(pattern).expect("built-in redaction pattern is valid");
+    (regex, replacement.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_redactors() {
+        let redactors = vec![
+            email_redactor("***"),
+            card_number_redactor("[card]"),
+            bearer_token_redactor("Bearer ***"),
+        ];
+
+        let message = "user alice@example.com paid with 4111 1111 1111 1111 using Bearer abc.def-123";
+        assert_eq!(
+            redact(message, &redactors),
+            "user *** paid with [card] using Bearer ***"
+        );
+    }
+
+    #[test]
+    fn test_no_match_borrows_message() {
+        let redactors = vec![email_redactor("***")];
+        assert!(matches!(redact("nothing secret", &redactors), Cow::Borrowed(_)));
+    }
+}
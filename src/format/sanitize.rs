@@ -0,0 +1,56 @@
+//! Control character escaping for FreedomLogger text output
+//!
+//! A message containing a newline could fake extra log lines, and ANSI
+//! escape sequences could control the terminal of whoever `cat`s the file.
+//! Text patterns therefore escape control characters in the message (with
+//! `LoggerConfig::sanitize_text`, on by default); JSON escapes them itself.
+//!
+//! - '\n' and '\r' become the visible escapes `\n` and `\r`
+//! - tabs are kept
+//! - other control characters (ESC, NUL, DEL, ...) become `\x1b`-style escapes
+//!
+//! Example: "line one\nline two" -> "line one\\nline two" (one physical line)
+
+use std::borrow::Cow;
+use std::fmt::Write;
+
+/// Escape the control characters of a message for text output
+///
+/// # Returns
+/// The message unchanged (borrowed) when it has nothing to escape
+pub fn sanitize_text(message: &str) -> Cow<'_, str> {
+    if !message.chars().any(needs_escape) {
+        return Cow::Borrowed(message);
+    }
+
+    let mut sanitized = String::with_capacity(message.len() + 8);
+    for character in message.chars() {
+        match character {
+            '\n' => sanitized.push_str("\\n"),
+            '\r' => sanitized.push_str("\\r"),
+            character if needs_escape(character) => {
+                let _ = write!(sanitized, "\\x{:02x}", character as u32);
+            }
+            character => sanitized.push(character),
+        }
+    }
+    Cow::Owned(sanitized)
+}
+
+/// Control characters other than tab
+fn needs_escape(character: char) -> bool {
+    character.is_control() && character != '\t'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_control_characters_are_escaped() {
+        assert!(matches!(sanitize_text("plain\ttext"), Cow::Borrowed("plain\ttext")));
+        assert_eq!(sanitize_text("one\ntwo\r\n"), "one\\ntwo\\r\\n");
+        assert_eq!(sanitize_text("\x1b[31mred\x1b[0m"), "\\x1b[31mred\\x1b[0m");
+        assert_eq!(sanitize_text("nul\0del\x7f"), "nul\\x00del\\x7f");
+    }
+}
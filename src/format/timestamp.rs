@@ -0,0 +1,214 @@
+//! Timestamp helpers for FreedomLogger
+//!
+//! Central place where log timestamps are produced, so the main log and the
+//! internal error log always agree on the time zone.
+//!
+//! Time zone choice:
+//! - Local time (default): easy to read next to the wall clock of the machine
+//! - UTC: lines up logs from machines in different time zones, which is what
+//!   you want when correlating entries across a distributed system
+//!
+//! Example output: 2025-09-06 15:30:45
+//! JSON output always uses RFC 3339 instead: 2025-09-06T15:30:45+02:00
+//!
+//! Both formats have second resolution, so `TimestampCache` lets the logger
+//! format a timestamp once per second instead of once per entry.
+//!
+//! With the `minimal-time` feature the formatting is done by hand from
+//! `std::time::SystemTime`. Times are then always UTC (std has no time zone
+//! data): "2025-09-06 13:30:45" and "2025-09-06T13:30:45Z".
+
+use std::sync::Mutex;
+#[cfg(not(feature = "minimal-time"))]
+use chrono::{SecondsFormat, Utc};
+use crate::core::time::{self, Timestamp};
+
+/// Human-readable timestamp format used by all text patterns
+pub const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Get the current time as a formatted timestamp string
+///
+/// # Arguments
+/// * `use_utc` - Format in UTC instead of the local time zone
+pub fn current_timestamp(use_utc: bool) -> String {
+    format_timestamp(time::now(), use_utc)
+}
+
+/// Format a fixed instant as a timestamp string
+///
+/// # Arguments
+/// * `instant` - The moment to format
+/// * `use_utc` - Format in UTC instead of the local time zone
+///
+/// # Returns
+/// Timestamp string, e.g. "2025-09-06 15:30:45"
+#[cfg(not(feature = "minimal-time"))]
+pub fn format_timestamp(instant: Timestamp, use_utc: bool) -> String {
+    if use_utc {
+        instant.with_timezone(&Utc).format(TIMESTAMP_FORMAT).to_string()
+    } else {
+        instant.format(TIMESTAMP_FORMAT).to_string()
+    }
+}
+
+/// Format a fixed instant as a timestamp string (always UTC)
+///
+/// # Returns
+/// Timestamp string, e.g. "2025-09-06 13:30:45"
+#[cfg(feature = "minimal-time")]
+pub fn format_timestamp(instant: Timestamp, _use_utc: bool) -> String {
+    let (date, hour, minute, second) = time::utc_fields(instant);
+    format!("{} {:02}:{:02}:{:02}", date, hour, minute, second)
+}
+
+/// Get the current time as an RFC 3339 / ISO 8601 timestamp
+///
+/// Used by JSON output, where log processors expect a machine-readable
+/// timestamp with a timezone designator.
+///
+/// # Arguments
+/// * `use_utc` - Format in UTC (`Z` suffix) instead of the local offset
+pub fn current_rfc3339(use_utc: bool) -> String {
+    format_rfc3339(time::now(), use_utc)
+}
+
+/// Format a fixed instant as an RFC 3339 timestamp
+///
+/// # Returns
+/// Timestamp string, e.g. "2025-09-06T15:30:45Z" or "2025-09-06T15:30:45+02:00"
+#[cfg(not(feature = "minimal-time"))]
+pub fn format_rfc3339(instant: Timestamp, use_utc: bool) -> String {
+    if use_utc {
+        instant.with_timezone(&Utc).to_rfc3339_opts(SecondsFormat::Secs, true)
+    } else {
+        instant.to_rfc3339_opts(SecondsFormat::Secs, false)
+    }
+}
+
+/// Format a fixed instant as an RFC 3339 timestamp (always UTC)
+///
+/// # Returns
+/// Timestamp string, e.g. "2025-09-06T13:30:45Z"
+#[cfg(feature = "minimal-time")]
+pub fn format_rfc3339(instant: Timestamp, _use_utc: bool) -> String {
+    let (date, hour, minute, second) = time::utc_fields(instant);
+    format!("{}T{:02}:{:02}:{:02}Z", date, hour, minute, second)
+}
+
+/// Cache of the last formatted timestamp, reused within the same second
+///
+/// Formatting (time zone conversion plus `format()`) is the costly part of a
+/// timestamp; under heavy logging many entries share the same second.
+/// Output is identical to formatting every entry, since both timestamp
+/// formats stop at whole seconds.
+#[derive(Debug, Default)]
+pub struct TimestampCache {
+    /// Unix second of the cached value and its formatted string
+    cached: Mutex<Option<(i64, String)>>,
+}
+
+impl TimestampCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the formatted timestamp for `instant`
+    ///
+    /// # Arguments
+    /// * `instant` - The moment to format
+    /// * `format` - Formatter, only called when the second changed
+    ///
+    /// # Returns
+    /// The cached string for the same second, otherwise the newly formatted one
+    pub fn get(&self, instant: Timestamp, format: impl FnOnce(Timestamp) -> String) -> String {
+        let second = time::unix_seconds(instant);
+        let mut cached = self.cached.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match cached.as_ref() {
+            Some((cached_second, formatted)) if *cached_second == second => formatted.clone(),
+            _ => {
+                let formatted = format(instant);
+                *cached = Some((second, formatted.clone()));
+                formatted
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::time::timestamp_from_utc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_utc_timestamp_has_no_local_offset() {
+        let instant = timestamp_from_utc(2025, 9, 6, 15, 30, 45).unwrap();
+
+        assert_eq!(format_timestamp(instant, true), "2025-09-06 15:30:45");
+    }
+
+    #[cfg(not(feature = "minimal-time"))]
+    #[test]
+    fn test_local_timestamp_uses_local_zone() {
+        use chrono::{Local, TimeZone};
+        let utc_instant = Utc.with_ymd_and_hms(2025, 9, 6, 15, 30, 45).unwrap();
+        let instant = utc_instant.with_timezone(&Local);
+
+        let expected = utc_instant.with_timezone(&Local).format(TIMESTAMP_FORMAT).to_string();
+        assert_eq!(format_timestamp(instant, false), expected);
+    }
+
+    #[test]
+    fn test_rfc3339_utc_uses_z_designator() {
+        let instant = timestamp_from_utc(2025, 9, 6, 15, 30, 45).unwrap();
+
+        assert_eq!(format_rfc3339(instant, true), "2025-09-06T15:30:45Z");
+    }
+
+    #[test]
+    fn test_cache_formats_once_per_second() {
+        let cache = TimestampCache::new();
+        let instant = timestamp_from_utc(2025, 9, 6, 15, 30, 45).unwrap();
+        let mut calls = 0;
+
+        for step in 0..10_000 {
+            let within_second = instant + Duration::from_micros(step * 99);
+            let formatted = cache.get(within_second, |now| {
+                calls += 1;
+                format_timestamp(now, true)
+            });
+            assert_eq!(formatted, "2025-09-06 15:30:45");
+        }
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_cache_advances_across_seconds() {
+        let cache = TimestampCache::new();
+        let instant = timestamp_from_utc(2025, 9, 6, 15, 30, 45).unwrap();
+
+        assert_eq!(cache.get(instant, |now| format_timestamp(now, true)), "2025-09-06 15:30:45");
+        let next = instant + Duration::from_secs(1);
+        assert_eq!(cache.get(next, |now| format_timestamp(now, true)), "2025-09-06 15:30:46");
+        assert_eq!(cache.get(instant, |now| format_timestamp(now, true)), "2025-09-06 15:30:45");
+    }
+
+    #[cfg(feature = "minimal-time")]
+    #[test]
+    fn test_std_time_timestamps_are_parseable() {
+        let instant = timestamp_from_utc(2025, 9, 6, 15, 30, 45).unwrap();
+        assert_eq!(format_timestamp(instant, false), "2025-09-06 15:30:45");
+        assert_eq!(format_rfc3339(instant, false), "2025-09-06T15:30:45Z");
+
+        // The current time splits into valid date and time fields
+        let now = current_rfc3339(false);
+        let (date, clock) = now.strip_suffix('Z').unwrap().split_once('T').unwrap();
+        assert!(time::parse_date(date).is_some(), "bad date in {}", now);
+        let fields: Vec<u32> = clock.split(':').map(|field| field.parse().unwrap()).collect();
+        assert!(fields.len() == 3 && fields[0] < 24 && fields[1] < 60 && fields[2] < 60, "bad time in {}", now);
+
+        #[cfg(feature = "chrono")]
+        assert!(chrono::DateTime::parse_from_rfc3339(&now).is_ok(), "not RFC 3339: {}", now);
+    }
+}
@@ -0,0 +1,64 @@
+//! Message truncation for FreedomLogger
+//!
+//! Keeps a single oversized message (e.g. a serialized blob) from blowing up
+//! the log file or breaking downstream parsers. Used by the logger when
+//! `LoggerConfig::max_message_len` is set.
+//!
+//! Truncation never splits a multibyte UTF-8 character, so the result can be
+//! slightly shorter than the limit.
+//!
+//! Example output: "first 10 b…[truncated 1234 bytes]"
+
+use std::borrow::Cow;
+
+/// Truncate a message to at most `max_len` bytes
+///
+/// # Arguments
+/// * `message` - The message to check
+/// * `max_len` - Maximum length in bytes of the kept part
+///
+/// # Returns
+/// The message unchanged when it fits, otherwise the kept part followed by
+/// a "…[truncated N bytes]" marker with the number of bytes removed
+pub fn truncate_message(message: &str, max_len: usize) -> Cow<'_, str> {
+    if message.len() <= max_len {
+        return Cow::Borrowed(message);
+    }
+
+    // Step back to the start of the character that crosses the limit
+    let mut end = max_len;
+    while !message.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    Cow::Owned(format!(
+        "{}…[truncated {} bytes]",
+        &message[..end],
+        message.len() - end
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_message_unchanged() {
+        assert!(matches!(truncate_message("short", 10), Cow::Borrowed("short")));
+        assert!(matches!(truncate_message("exactly10!", 10), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_oversized_ascii_message() {
+        let message = "x".repeat(1000);
+        assert_eq!(truncate_message(&message, 10), "xxxxxxxxxx…[truncated 990 bytes]");
+    }
+
+    #[test]
+    fn test_oversized_emoji_message_keeps_whole_characters() {
+        // Each emoji is 4 bytes: a limit of 10 keeps two (8 bytes)
+        let message = "🦀🦀🦀🦀🦀";
+        assert_eq!(truncate_message(message, 10), "🦀🦀…[truncated 12 bytes]");
+        assert_eq!(truncate_message(message, 3), "…[truncated 20 bytes]");
+    }
+}
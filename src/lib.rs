@@ -1,28 +1,36 @@
-/// FreedomLogger - A professional logging library for Rust
-///
-/// FreedomLogger provides clean, efficient logging with automatic rotation,
-/// multiple output formats, and error-proof operation. Designed for both
-/// development and production use.
-///
-/// Features:
-/// - Multiple log levels (ERROR, WARNING, INFO, DEBUG, TRACE) with filtering
-/// - Various output patterns (Basic, Detailed, Extended, JSON, Custom)
-/// - Automatic log rotation based on file size
-/// - Thread-safe concurrent logging
-/// - No external dependencies (except chrono for timestamps)
-/// - Error-proof operation (internal errors logged separately)
-/// - Easy single-initialization API
-/// - Flexible logging macros supporting formatted messages
-///
-/// Usage:
-/// 1. Initialize logger once in main(): logger::init(pattern, path, filename)
-/// 2. Log anywhere in your code:
-///    - Simple: logger::info("message")
-///    - Formatted: log_info!("User {} logged in", user_id)
-/// 3. All configuration is done at initialization time
+//! FreedomLogger - A professional logging library for Rust
+//!
+//! FreedomLogger provides clean, efficient logging with automatic rotation,
+//! multiple output formats, and error-proof operation. Designed for both
+//! development and production use.
+//!
+//! Features:
+//! - Multiple log levels (ERROR, WARNING, INFO, DEBUG, TRACE) with filtering
+//! - Various output patterns (Basic, Detailed, Extended, JSON, Custom)
+//! - Automatic log rotation based on file size
+//! - Thread-safe concurrent logging
+//! - No external dependencies (except chrono for timestamps, optional with `minimal-time`)
+//! - Error-proof operation (internal errors logged separately)
+//! - Browser console output on wasm32 with the `wasm` feature
+//! - Easy single-initialization API
+//! - Flexible logging macros supporting formatted messages
+//!
+//! Usage:
+//! 1. Initialize logger once in main(): logger::init(pattern, path, filename)
+//! 2. Log anywhere in your code:
+//!    - Simple: logger::info("message")
+//!    - Formatted: log_info!("User {} logged in", user_id)
+//! 3. All configuration is done at initialization time
+
+#![allow(non_snake_case)]
+// Unsafe code only where a platform API requires it, allowed per item
+#![deny(unsafe_code)]
 
-use std::sync::{Arc, Once};
-use std::path::Path;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::panic;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::{Path, PathBuf};
 
 // Import all our modules
 pub mod error;
@@ -31,12 +39,40 @@ pub mod format;
 pub mod rotation;
 
 // Re-export main types for user convenience
-pub use core::{LogLevel, Pattern, LoggerConfig, Logger};
+pub use core::{LogLevel, LogCounts, Pattern, LoggerConfig, LoggerConfigSnapshot, Logger, Destination, RotationStrategy, LineEnding, Framing, LogWriter, Clock, SystemClock, Timestamp};
 pub use error::LoggerError;
+pub use core::span::LogSpan;
+use core::global::LoggerSlot;
+pub use core::context::{push_context, pop_context, clear_context, scope, scope_many, ContextGuard};
+
+/// Global logger instance - initialized once, replaced only by `reconfigure`
+static GLOBAL_LOGGER: LoggerSlot = LoggerSlot::new();
+
+/// Most verbose level compiled into this build
+///
+/// Set with the `max_level_*` cargo features (e.g. `max_level_info` removes
+/// all DEBUG and TRACE calls from release builds). Messages above this level
+/// are dropped before the runtime level is checked, so the optimizer removes
+/// them entirely. Without a feature every level is compiled in.
+pub const STATIC_MAX_LEVEL: LogLevel = if cfg!(feature = "max_level_off") {
+    LogLevel::Off
+} else if cfg!(feature = "max_level_error") {
+    LogLevel::Error
+} else if cfg!(feature = "max_level_warn") {
+    LogLevel::Warning
+} else if cfg!(feature = "max_level_info") {
+    LogLevel::Info
+} else if cfg!(feature = "max_level_debug") {
+    LogLevel::Debug
+} else {
+    LogLevel::Trace
+};
 
-/// Global logger instance - initialized once, used everywhere
-static mut GLOBAL_LOGGER: Option<Arc<Logger>> = None;
-static INIT_ONCE: Once = Once::new();
+/// Check a level against `STATIC_MAX_LEVEL` (evaluated at compile time)
+#[inline(always)]
+const fn statically_enabled(level: LogLevel) -> bool {
+    level as u8 != LogLevel::Off as u8 && level as u8 <= STATIC_MAX_LEVEL as u8
+}
 
 /// Initialize the global logger with basic configuration
 ///
@@ -114,30 +150,118 @@ pub fn log_init_with_rotation<P: AsRef<Path>>(
     log_init_with_config(config);
 }
 
+/// Initialize the global logger to write only to stdout
+///
+/// For CLI tools that don't want log files: no directory or file name needed.
+///
+/// # Arguments
+/// * `pattern` - Log formatting pattern
+/// * `log_level` - Minimum log level to write
+pub fn log_init_stdout(pattern: Pattern, log_level: LogLevel) {
+    log_init_with_config(LoggerConfig::stdout(pattern, log_level));
+}
+
+/// Initialize the global logger to write only to stderr
+///
+/// Like `log_init_stdout`, but keeps stdout free for the program's output.
+///
+/// # Arguments
+/// * `pattern` - Log formatting pattern
+/// * `log_level` - Minimum log level to write
+pub fn log_init_stderr(pattern: Pattern, log_level: LogLevel) {
+    log_init_with_config(LoggerConfig::stderr(pattern, log_level));
+}
+
 /// Initialize with a complete configuration object
 ///
-/// Internal method used by all public init functions.
-/// Ensures thread-safe single initialization.
-fn log_init_with_config(config: LoggerConfig) {
-    INIT_ONCE.call_once(|| {
-        let logger = Logger::new(config);
-        unsafe {
-            GLOBAL_LOGGER = Some(Arc::new(logger));
-        }
-    });
+/// Used by all other init functions, and available directly for options
+/// that have no dedicated init function (e.g. `use_utc`).
+/// Ensures thread-safe single initialization: if the logger is already
+/// initialized the configuration is ignored (use `reconfigure` to replace it).
+///
+/// # Arguments
+/// * `config` - Complete logger configuration
+pub fn log_init_with_config(config: LoggerConfig) {
+    GLOBAL_LOGGER.init(|| Logger::new(config));
+}
+
+/// Replace the global logger with one built from a new configuration
+///
+/// For settings known only after startup (e.g. a config file loaded late).
+/// The old logger is flushed and new messages go to the new logger; calls
+/// already running when it is swapped finish on the old one. Also works as
+/// the first initialization.
+///
+/// # Arguments
+/// * `config` - Complete logger configuration
+pub fn reconfigure(config: LoggerConfig) {
+    GLOBAL_LOGGER.replace(Logger::new(config));
+}
+
+/// Install an already built logger as the global logger
+///
+/// For loggers set up beyond what `LoggerConfig` offers, e.g. with a custom
+/// `LogWriter` or an injected `Clock` (`Logger::with_clock`). The caller may
+/// keep its own `Arc` to inspect the logger afterwards.
+///
+/// # Arguments
+/// * `logger` - The logger to use for the logging functions
+///
+/// # Returns
+/// Ok(()) on success, LoggerError::AlreadyInitialized if a global logger is already installed
+pub fn install(logger: Arc<Logger>) -> Result<(), LoggerError> {
+    if GLOBAL_LOGGER.install(logger) {
+        Ok(())
+    } else {
+        Err(LoggerError::AlreadyInitialized)
+    }
+}
+
+/// Validate a configuration and initialize the global logger with it
+///
+/// Unlike the other init functions, an invalid configuration is reported
+/// instead of silently producing broken output.
+/// If the logger is already initialized, the configuration is ignored.
+///
+/// # Arguments
+/// * `config` - Complete logger configuration
+///
+/// # Returns
+/// Ok(()) on success, LoggerError::InvalidConfig if validation fails
+pub fn try_log_init(config: LoggerConfig) -> Result<(), LoggerError> {
+    config.validate()?;
+    log_init_with_config(config);
+    Ok(())
+}
+
+/// Load a TOML config file and initialize the global logger with it
+///
+/// See `core::config_file` for the file layout. Like `try_log_init`, the
+/// configuration is ignored if the logger is already initialized.
+///
+/// # Arguments
+/// * `path` - Path to the config file (e.g. "logger.toml")
+///
+/// # Returns
+/// Ok(()) on success, LoggerError::InvalidConfig if the file can't be read or is invalid
+#[cfg(feature = "config-file")]
+pub fn init_from_file<P: AsRef<Path>>(path: P) -> Result<(), LoggerError> {
+    let path = path.as_ref();
+    let text = std::fs::read_to_string(path).map_err(|error| LoggerError::InvalidConfig {
+        field: "config_file".to_string(),
+        reason: format!("Failed to read '{}': {}", path.display(), error),
+    })?;
+    try_log_init(LoggerConfig::from_toml_str(&text)?)
 }
 
 /// Get reference to the global logger instance
 ///
 /// Returns the initialized logger or panics if not initialized.
 /// This is used internally by the logging functions.
-#[allow(static_mut_refs)]
-fn get_logger() -> &'static Arc<Logger> {
-    unsafe {
-        GLOBAL_LOGGER
-            .as_ref()
-            .expect("Logger not initialized - call logger::init() first")
-    }
+fn get_logger() -> Arc<Logger> {
+    GLOBAL_LOGGER
+        .get()
+        .expect("Logger not initialized - call logger::init() first")
 }
 
 /// Log an ERROR level message
@@ -147,8 +271,11 @@ fn get_logger() -> &'static Arc<Logger> {
 ///
 /// # Arguments
 /// * `message` - The error message to log
+#[track_caller]
 pub fn log_error(message: &str) {
-    get_logger().error(message);
+    if statically_enabled(LogLevel::Error) {
+        get_logger().error(message);
+    }
 }
 
 /// Log a WARNING level message
@@ -158,8 +285,11 @@ pub fn log_error(message: &str) {
 ///
 /// # Arguments
 /// * `message` - The warning message to log
+#[track_caller]
 pub fn log_warning(message: &str) {
-    get_logger().warning(message);
+    if statically_enabled(LogLevel::Warning) {
+        get_logger().warning(message);
+    }
 }
 
 /// Log an INFO level message
@@ -169,8 +299,11 @@ pub fn log_warning(message: &str) {
 ///
 /// # Arguments
 /// * `message` - The info message to log
+#[track_caller]
 pub fn log_info(message: &str) {
-    get_logger().info(message);
+    if statically_enabled(LogLevel::Info) {
+        get_logger().info(message);
+    }
 }
 
 /// Log a DEBUG level message
@@ -180,8 +313,11 @@ pub fn log_info(message: &str) {
 ///
 /// # Arguments
 /// * `message` - The debug message to log
+#[track_caller]
 pub fn log_debug(message: &str) {
-    get_logger().debug(message);
+    if statically_enabled(LogLevel::Debug) {
+        get_logger().debug(message);
+    }
 }
 
 /// Log a TRACE level message
@@ -191,64 +327,281 @@ pub fn log_debug(message: &str) {
 ///
 /// # Arguments
 /// * `message` - The trace message to log
+#[track_caller]
 pub fn log_trace(message: &str) {
-    get_logger().trace(message);
+    if statically_enabled(LogLevel::Trace) {
+        get_logger().trace(message);
+    }
+}
+
+/// Get the lines captured by the memory destination
+///
+/// Lets tests assert on emitted logs without touching disk.
+/// Requires `Destination::Memory(capacity)` in the configured destinations.
+///
+/// # Returns
+/// Captured lines, oldest first (empty without a memory destination)
+pub fn captured_lines() -> Vec<String> {
+    get_logger().captured_lines()
+}
+
+/// Finish pending output of the global logger (e.g. close a JSON array)
+///
+/// Logging can continue afterwards.
+pub fn log_flush() {
+    get_logger().flush();
+}
+
+/// Flush the global logger before the application exits
+///
+/// The global logger is never dropped, so call this at the end of main()
+/// when using `LoggerConfig::json_array`. In async mode it also stops the
+/// background writer; messages logged afterwards are discarded.
+pub fn log_shutdown() {
+    get_logger().shutdown();
+}
+
+/// Log panics of the application as ERROR entries
+///
+/// Installs a panic hook that writes the panic message and location (plus a
+/// backtrace when enabled with `RUST_BACKTRACE`) to the global logger, then
+/// runs the previously installed hook, so the usual stderr output stays.
+/// Call it once after init; later calls do nothing.
+pub fn capture_panics() {
+    static INSTALLED: AtomicBool = AtomicBool::new(false);
+    if INSTALLED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        // A panic inside a write can't log: this thread holds the write lock
+        if let Some(logger) = GLOBAL_LOGGER.get().filter(|_| !core::logger::is_writing()) {
+            let (file, line, column) = info
+                .location()
+                .map_or(("<unknown>", 0, 0), |location| (location.file(), location.line(), location.column()));
+            let mut message = format!("{} at {}:{}:{}", core::logger::panic_message(info.payload()), file, line, column);
+
+            let backtrace = Backtrace::capture();
+            if backtrace.status() == BacktraceStatus::Captured {
+                message.push_str(&format!("\nstack backtrace:\n{}", backtrace));
+            }
+            logger.log_located(LogLevel::Error, &message, file, line);
+            logger.flush();
+        }
+        previous(info);
+    }));
+}
+
+/// Number of messages dropped because the async queue was full
+///
+/// # Returns
+/// Total since init (always 0 without `LoggerConfig::async_queue_capacity`)
+pub fn dropped_count() -> u64 {
+    get_logger().dropped_count()
+}
+
+/// Get the per-level counts of the global logger
+///
+/// Filtered messages include the macros' `log_enabled` checks that returned
+/// false (levels removed at compile time are not counted).
+///
+/// # Returns
+/// Counts since init
+pub fn log_counts() -> LogCounts {
+    get_logger().log_counts()
+}
+
+/// Get the bytes of file output the global logger holds in memory
+///
+/// # Returns
+/// Buffered bytes not yet written (0 without `LoggerConfig::buffered`)
+pub fn buffer_used() -> usize {
+    get_logger().buffer_used()
+}
+
+/// Get the path of the file the global logger is writing to now
+///
+/// Includes the extension and, with `daily_files`, today's date. Follows
+/// `reconfigure`: the path of the logger currently installed is returned.
+pub fn current_log_path() -> PathBuf {
+    get_logger().current_log_path()
+}
+
+/// Format a message with the global logger's pattern, without writing it
+///
+/// Useful to test patterns or to send the line somewhere else. Uses the
+/// current timestamp, thread and context and the caller's location.
+///
+/// # Arguments
+/// * `level` - Level of the message (not checked against the configured level)
+/// * `message` - The message to format
+///
+/// # Returns
+/// The formatted entry (a JSON line for `Pattern::Json`), without a line ending
+#[track_caller]
+pub fn format_entry(level: LogLevel, message: &str) -> String {
+    get_logger().format_entry(level, message)
+}
+
+/// Log a message at the given level
+///
+/// Used by macros that take the level as a parameter.
+///
+/// # Arguments
+/// * `level` - Level of the message
+/// * `message` - The message to log
+#[track_caller]
+pub fn log_at(level: LogLevel, message: &str) {
+    if statically_enabled(level) {
+        get_logger().log_at(level, message);
+    }
+}
+
+/// Get the minimum level of the global logger
+///
+/// # Returns
+/// The most verbose level that is written, or None when every level is logged
+pub fn current_level() -> Option<LogLevel> {
+    get_logger().current_level()
+}
+
+/// Get a read-only snapshot of the global logger's configuration
+///
+/// Reflects runtime level changes made with `set_level`.
+pub fn current_config() -> LoggerConfigSnapshot {
+    get_logger().current_config()
+}
+
+/// Check if a message at this level would be written by the global logger
+///
+/// The logging macros call this before formatting, so arguments of a
+/// filtered message are never evaluated. Levels above `STATIC_MAX_LEVEL`
+/// are rejected at compile time, without touching the logger.
+/// A runtime rejection counts as filtered in `log_counts()`.
+#[inline]
+pub fn log_enabled(level: LogLevel) -> bool {
+    // Borrows the logger: filtered calls cost a read lock and a level compare
+    statically_enabled(level)
+        && GLOBAL_LOGGER
+            .with(|logger| logger.filter(level))
+            .expect("Logger not initialized - call logger::init() first")
+}
+
+/// Change the minimum level of the global logger at runtime
+///
+/// # Arguments
+/// * `level` - New minimum level (`LogLevel::Off` mutes all logging)
+pub fn set_level(level: LogLevel) {
+    get_logger().set_level(level);
+}
+
+/// Log a message with its call site (module path, file and line)
+///
+/// Used by the logging macros; prefer the macros over calling this directly.
+///
+/// # Arguments
+/// * `level` - Level of the message
+/// * `message` - The message to log
+/// * `module` - Module path of the call site (`module_path!()`)
+/// * `file` - Source file of the call site (`file!()`)
+/// * `line` - Line of the call site (`line!()`)
+#[doc(hidden)]
+pub fn log_from(level: LogLevel, message: &str, module: &str, file: &str, line: u32) {
+    if statically_enabled(level) {
+        get_logger().log_from(level, message, module, file, line);
+    }
+}
+
+/// Log a hexdump with the global logger and the call site of `log_hex!`
+///
+/// Used by `log_hex!`; prefer the macro over calling this directly.
+#[doc(hidden)]
+pub fn log_hex_from(level: LogLevel, bytes: &[u8], module: &str, file: &str, line: u32) {
+    if statically_enabled(level) {
+        get_logger().log_hex_from(level, bytes, module, file, line);
+    }
+}
+
+/// Open a timing span on the global logger
+///
+/// Used by `log_span!`; prefer the macro over calling this directly.
+#[doc(hidden)]
+pub fn log_span_from(name: &str, module: &'static str, file: &'static str, line: u32) -> LogSpan<'static> {
+    if statically_enabled(LogLevel::Trace) {
+        LogSpan::enter_shared(get_logger(), name, module, file, line)
+    } else {
+        LogSpan::disabled(name, module, file, line)
+    }
 }
 
 // ============================================================================
 // MACROS VOOR FORMATTED LOGGING
 // ============================================================================
+//
+// All macros check `log_enabled()` first: when the level is filtered out the
+// message is not formatted and its arguments are not evaluated at all.
+// Levels above `STATIC_MAX_LEVEL` fold to `if false`, so they compile to nothing.
 
 /// Macro for logging ERROR messages with formatting support
 ///
 /// Supports both simple messages and formatted strings with arguments.
 /// Uses Rust's built-in format! macro for automatic type handling.
 ///
-
 #[macro_export]
 macro_rules! log_error {
     // Simple message zonder formatting
-    ($msg:expr) => {
-        $crate::log_error($msg);
-    };
+    ($msg:expr) => {{
+        if $crate::log_enabled($crate::LogLevel::Error) {
+            $crate::log_from($crate::LogLevel::Error, $msg, module_path!(), file!(), line!());
+        }
+    }};
 
     // Formatted message met argumenten
-    ($fmt:expr, $($arg:expr),+ $(,)?) => {
-        $crate::log_error(&format!($fmt, $($arg),+));
-    };
+    ($fmt:expr, $($arg:expr),+ $(,)?) => {{
+        if $crate::log_enabled($crate::LogLevel::Error) {
+            $crate::log_from($crate::LogLevel::Error, &format!($fmt, $($arg),+), module_path!(), file!(), line!());
+        }
+    }};
 }
 
 /// Macro for logging WARNING messages with formatting support
 ///
 /// Supports both simple messages and formatted strings with arguments.
 ///
-
 /// ```
 #[macro_export]
 macro_rules! log_warning {
-    ($msg:expr) => {
-        $crate::log_warning($msg);
-    };
+    ($msg:expr) => {{
+        if $crate::log_enabled($crate::LogLevel::Warning) {
+            $crate::log_from($crate::LogLevel::Warning, $msg, module_path!(), file!(), line!());
+        }
+    }};
 
-    ($fmt:expr, $($arg:expr),+ $(,)?) => {
-        $crate::log_warning(&format!($fmt, $($arg),+));
-    };
+    ($fmt:expr, $($arg:expr),+ $(,)?) => {{
+        if $crate::log_enabled($crate::LogLevel::Warning) {
+            $crate::log_from($crate::LogLevel::Warning, &format!($fmt, $($arg),+), module_path!(), file!(), line!());
+        }
+    }};
 }
 
 /// Macro for logging INFO messages with formatting support
 ///
 /// Supports both simple messages and formatted strings with arguments.
 ///
-
 #[macro_export]
 macro_rules! log_info {
-    ($msg:expr) => {
-        $crate::log_info($msg);
-    };
+    ($msg:expr) => {{
+        if $crate::log_enabled($crate::LogLevel::Info) {
+            $crate::log_from($crate::LogLevel::Info, $msg, module_path!(), file!(), line!());
+        }
+    }};
 
-    ($fmt:expr, $($arg:expr),+ $(,)?) => {
-        $crate::log_info(&format!($fmt, $($arg),+));
-    };
+    ($fmt:expr, $($arg:expr),+ $(,)?) => {{
+        if $crate::log_enabled($crate::LogLevel::Info) {
+            $crate::log_from($crate::LogLevel::Info, &format!($fmt, $($arg),+), module_path!(), file!(), line!());
+        }
+    }};
 }
 
 /// Macro for logging DEBUG messages with formatting support
@@ -257,47 +610,306 @@ macro_rules! log_info {
 /// and formatted strings with arguments, automatically handling any type
 /// that implements Display or Debug.
 ///
-
 #[macro_export]
 macro_rules! log_debug {
-    ($msg:expr) => {
-        $crate::log_debug($msg);
-    };
+    ($msg:expr) => {{
+        if $crate::log_enabled($crate::LogLevel::Debug) {
+            $crate::log_from($crate::LogLevel::Debug, $msg, module_path!(), file!(), line!());
+        }
+    }};
 
-    ($fmt:expr, $($arg:expr),+ $(,)?) => {
-        $crate::log_debug(&format!($fmt, $($arg),+));
-    };
+    ($fmt:expr, $($arg:expr),+ $(,)?) => {{
+        if $crate::log_enabled($crate::LogLevel::Debug) {
+            $crate::log_from($crate::LogLevel::Debug, &format!($fmt, $($arg),+), module_path!(), file!(), line!());
+        }
+    }};
 }
 
 /// Macro for logging TRACE messages with formatting support
 ///
 /// Supports both simple messages and formatted strings with arguments.
 ///
-
 #[macro_export]
 macro_rules! log_trace {
-    ($msg:expr) => {
-        $crate::log_trace($msg);
+    ($msg:expr) => {{
+        if $crate::log_enabled($crate::LogLevel::Trace) {
+            $crate::log_from($crate::LogLevel::Trace, $msg, module_path!(), file!(), line!());
+        }
+    }};
+
+    ($fmt:expr, $($arg:expr),+ $(,)?) => {{
+        if $crate::log_enabled($crate::LogLevel::Trace) {
+            $crate::log_from($crate::LogLevel::Trace, &format!($fmt, $($arg),+), module_path!(), file!(), line!());
+        }
+    }};
+}
+
+/// Macro for logging a byte buffer as a hexdump
+///
+/// Each line shows the offset, the bytes in hex and as ASCII, with
+/// `LoggerConfig::hexdump_width` bytes per line (16 by default).
+///
+/// Usage: `log_hex!(LogLevel::Debug, &packet);`
+#[macro_export]
+macro_rules! log_hex {
+    ($level:expr, $bytes:expr $(,)?) => {{
+        let level = $level;
+        if $crate::log_enabled(level) {
+            $crate::log_hex_from(level, $bytes, module_path!(), file!(), line!());
+        }
+    }};
+}
+
+// ============================================================================
+// SAMPLING MACROS
+// ============================================================================
+
+/// Macro for logging only every Nth invocation of a call site
+///
+/// The 1st, (N+1)th, (2N+1)th, ... invocation is logged; the rest are dropped
+/// without formatting. State is kept per call site.
+///
+/// Usage: `log_every!(LogLevel::Info, 10, "Processed item {}", id);`
+#[macro_export]
+macro_rules! log_every {
+    ($level:expr, $n:expr, $msg:expr) => {{
+        static SAMPLER: $crate::core::sampling::EveryN = $crate::core::sampling::EveryN::new();
+        let level = $level;
+        if $crate::log_enabled(level) && SAMPLER.should_log($n as u64) {
+            $crate::log_from(level, $msg, module_path!(), file!(), line!());
+        }
+    }};
+
+    ($level:expr, $n:expr, $fmt:expr, $($arg:expr),+ $(,)?) => {{
+        static SAMPLER: $crate::core::sampling::EveryN = $crate::core::sampling::EveryN::new();
+        let level = $level;
+        if $crate::log_enabled(level) && SAMPLER.should_log($n as u64) {
+            $crate::log_from(level, &format!($fmt, $($arg),+), module_path!(), file!(), line!());
+        }
+    }};
+}
+
+/// Macro for logging a call site at most once per time interval
+///
+/// Invocations within the interval are dropped without formatting. The next
+/// emitted line gets a "(suppressed N)" note when messages were dropped.
+///
+/// Usage: `log_throttle!(LogLevel::Warning, Duration::from_secs(1), "Queue full");`
+#[macro_export]
+macro_rules! log_throttle {
+    ($level:expr, $interval:expr, $msg:expr) => {{
+        static THROTTLE: $crate::core::sampling::Throttle = $crate::core::sampling::Throttle::new();
+        let level = $level;
+        if $crate::log_enabled(level) {
+            if let Some(suppressed) = THROTTLE.should_log($interval) {
+                $crate::log_from(
+                    level,
+                    &$crate::core::sampling::with_suppressed_note(($msg).to_string(), suppressed),
+                    module_path!(),
+                    file!(),
+                    line!(),
+                );
+            }
+        }
+    }};
+
+    ($level:expr, $interval:expr, $fmt:expr, $($arg:expr),+ $(,)?) => {{
+        static THROTTLE: $crate::core::sampling::Throttle = $crate::core::sampling::Throttle::new();
+        let level = $level;
+        if $crate::log_enabled(level) {
+            if let Some(suppressed) = THROTTLE.should_log($interval) {
+                $crate::log_from(
+                    level,
+                    &$crate::core::sampling::with_suppressed_note(format!($fmt, $($arg),+), suppressed),
+                    module_path!(),
+                    file!(),
+                    line!(),
+                );
+            }
+        }
+    }};
+}
+
+/// Macro for logging only the first invocation of a call site
+///
+/// Later invocations of the same call site are dropped without formatting,
+/// e.g. for a startup warning inside a loop. An invocation whose level is
+/// filtered out does not count.
+///
+/// Usage: `log_once!(LogLevel::Warning, "Deprecated config key '{}' used", key);`
+#[macro_export]
+macro_rules! log_once {
+    ($level:expr, $msg:expr) => {{
+        static ONCE: $crate::core::sampling::LogOnce = $crate::core::sampling::LogOnce::new();
+        let level = $level;
+        if $crate::log_enabled(level) && ONCE.should_log() {
+            $crate::log_from(level, $msg, module_path!(), file!(), line!());
+        }
+    }};
+
+    ($level:expr, $fmt:expr, $($arg:expr),+ $(,)?) => {{
+        static ONCE: $crate::core::sampling::LogOnce = $crate::core::sampling::LogOnce::new();
+        let level = $level;
+        if $crate::log_enabled(level) && ONCE.should_log() {
+            $crate::log_from(level, &format!($fmt, $($arg),+), module_path!(), file!(), line!());
+        }
+    }};
+}
+
+/// Log an ERROR message every Nth invocation - see `log_every!`
+#[macro_export]
+macro_rules! log_error_every {
+    ($n:expr, $($rest:tt)+) => { $crate::log_every!($crate::LogLevel::Error, $n, $($rest)+) };
+}
+
+/// Log a WARNING message every Nth invocation - see `log_every!`
+#[macro_export]
+macro_rules! log_warning_every {
+    ($n:expr, $($rest:tt)+) => { $crate::log_every!($crate::LogLevel::Warning, $n, $($rest)+) };
+}
+
+/// Log an INFO message every Nth invocation - see `log_every!`
+#[macro_export]
+macro_rules! log_info_every {
+    ($n:expr, $($rest:tt)+) => { $crate::log_every!($crate::LogLevel::Info, $n, $($rest)+) };
+}
+
+/// Log a DEBUG message every Nth invocation - see `log_every!`
+#[macro_export]
+macro_rules! log_debug_every {
+    ($n:expr, $($rest:tt)+) => { $crate::log_every!($crate::LogLevel::Debug, $n, $($rest)+) };
+}
+
+/// Log a TRACE message every Nth invocation - see `log_every!`
+#[macro_export]
+macro_rules! log_trace_every {
+    ($n:expr, $($rest:tt)+) => { $crate::log_every!($crate::LogLevel::Trace, $n, $($rest)+) };
+}
+
+/// Log an ERROR message at most once per interval - see `log_throttle!`
+#[macro_export]
+macro_rules! log_error_throttle {
+    ($interval:expr, $($rest:tt)+) => { $crate::log_throttle!($crate::LogLevel::Error, $interval, $($rest)+) };
+}
+
+/// Log a WARNING message at most once per interval - see `log_throttle!`
+#[macro_export]
+macro_rules! log_warning_throttle {
+    ($interval:expr, $($rest:tt)+) => { $crate::log_throttle!($crate::LogLevel::Warning, $interval, $($rest)+) };
+}
+
+/// Log an INFO message at most once per interval - see `log_throttle!`
+#[macro_export]
+macro_rules! log_info_throttle {
+    ($interval:expr, $($rest:tt)+) => { $crate::log_throttle!($crate::LogLevel::Info, $interval, $($rest)+) };
+}
+
+/// Log a DEBUG message at most once per interval - see `log_throttle!`
+#[macro_export]
+macro_rules! log_debug_throttle {
+    ($interval:expr, $($rest:tt)+) => { $crate::log_throttle!($crate::LogLevel::Debug, $interval, $($rest)+) };
+}
+
+/// Log a TRACE message at most once per interval - see `log_throttle!`
+#[macro_export]
+macro_rules! log_trace_throttle {
+    ($interval:expr, $($rest:tt)+) => { $crate::log_throttle!($crate::LogLevel::Trace, $interval, $($rest)+) };
+}
+
+/// Log an ERROR message only once per call site - see `log_once!`
+#[macro_export]
+macro_rules! log_error_once {
+    ($($rest:tt)+) => { $crate::log_once!($crate::LogLevel::Error, $($rest)+) };
+}
+
+/// Log a WARNING message only once per call site - see `log_once!`
+#[macro_export]
+macro_rules! log_warning_once {
+    ($($rest:tt)+) => { $crate::log_once!($crate::LogLevel::Warning, $($rest)+) };
+}
+
+/// Log an INFO message only once per call site - see `log_once!`
+#[macro_export]
+macro_rules! log_info_once {
+    ($($rest:tt)+) => { $crate::log_once!($crate::LogLevel::Info, $($rest)+) };
+}
+
+/// Log a DEBUG message only once per call site - see `log_once!`
+#[macro_export]
+macro_rules! log_debug_once {
+    ($($rest:tt)+) => { $crate::log_once!($crate::LogLevel::Debug, $($rest)+) };
+}
+
+/// Log a TRACE message only once per call site - see `log_once!`
+#[macro_export]
+macro_rules! log_trace_once {
+    ($($rest:tt)+) => { $crate::log_once!($crate::LogLevel::Trace, $($rest)+) };
+}
+
+/// Time a scope: logs "entered NAME" at TRACE now and
+/// "exited NAME (took ...)" when the returned `LogSpan` is dropped
+///
+/// Nested spans on the same thread are indented.
+///
+/// Usage:
+/// - `let _span = log_span!("load_config");`
+/// - `let _span = log_span!("request {}", request_id);`
+#[macro_export]
+macro_rules! log_span {
+    ($name:expr) => {
+        $crate::log_span_from($name, module_path!(), file!(), line!())
     };
 
     ($fmt:expr, $($arg:expr),+ $(,)?) => {
-        $crate::log_trace(&format!($fmt, $($arg),+));
+        $crate::log_span_from(&format!($fmt, $($arg),+), module_path!(), file!(), line!())
     };
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tempfile::tempdir;
+    use tempfile::{tempdir, TempDir};
     use std::fs;
     use std::path::PathBuf;
+    use std::sync::OnceLock;
+
+    /// Shared log directory for tests using the global logger
+    ///
+    /// The global logger can only be initialized once per process, so all
+    /// tests in this module log to the same file and use unique messages.
+    /// The custom pattern shows the module path captured by the macros,
+    /// and TRACE is filtered out to test the macros' level check.
+    static TEST_LOG_DIR: OnceLock<TempDir> = OnceLock::new();
+
+    fn init_test_logger() -> &'static TempDir {
+        let temp_dir = TEST_LOG_DIR.get_or_init(|| tempdir().unwrap());
+        let pattern = Pattern::Custom("[{timestamp}] {level}: {message} <{module}>".to_string());
+        log_init_with_level(pattern, temp_dir.path(), "test", LogLevel::Debug);
+        temp_dir
+    }
+
+    /// Count lines in the shared test log containing the marker
+    fn count_lines_containing(temp_dir: &TempDir, marker: &str) -> usize {
+        let content = fs::read_to_string(temp_dir.path().join("test.log")).unwrap_or_default();
+        content.lines().filter(|line| line.contains(marker)).count()
+    }
+
+    /// Run one test of this module again in a child process
+    ///
+    /// For tests that need a fresh global logger: the child sees `env_key`
+    /// set and runs the test's child body; the parent asserts on the output.
+    fn run_in_child(test_name: &str, env_key: &str) -> std::process::Output {
+        std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", &format!("tests::{}", test_name), "--nocapture"])
+            .env(env_key, "1")
+            .output()
+            .unwrap()
+    }
 
     #[test]
     fn test_basic_logging_integration() {
-        let temp_dir = tempdir().unwrap();
-
         // Initialize logger
-        log_init(Pattern::Basic, temp_dir.path(), "test");
+        let temp_dir = init_test_logger();
 
         // Test basic function logging
         log_info("Test info message");
@@ -319,23 +931,364 @@ mod tests {
         log_debug!("Simple debug message");
         log_info!("Simple info message");
 
-        // Check that log file was created
+        // Check that log file was created (max_level_off compiles out every call)
         let log_file = temp_dir.path().join("test.log");
-        assert!(log_file.exists());
+        assert_eq!(log_file.exists(), statically_enabled(LogLevel::Error));
 
         // Check log content
-        let content = fs::read_to_string(&log_file).unwrap();
+        let content = fs::read_to_string(&log_file).unwrap_or_default();
+
+        // Each message is written only if its level survives the max_level_* features
+        let expected = [
+            // Basic function messages
+            (LogLevel::Info, "INFO: Test info message"),
+            (LogLevel::Warning, "WARNING: Test warning message"),
+            (LogLevel::Error, "ERROR: Test error message"),
+            // Macro messages
+            (LogLevel::Debug, "Database path:"),
+            (LogLevel::Info, "User 12345 has status: active"),
+            (LogLevel::Warning, "Processing 42 items"),
+            (LogLevel::Error, "Failed to connect to localhost:5432"),
+            (LogLevel::Debug, "Simple debug message"),
+        ];
+        for (level, text) in expected {
+            assert_eq!(content.contains(text), statically_enabled(level), "{}", text);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(
+        any(feature = "max_level_off", feature = "max_level_error", feature = "max_level_warn"),
+        ignore = "INFO calls are compiled out"
+    )]
+    fn test_macros_capture_module_path() {
+        let temp_dir = init_test_logger();
+
+        log_info!("Module path macro marker");
+        log_info("Module path function marker");
+
+        let content = fs::read_to_string(temp_dir.path().join("test.log")).unwrap();
+        assert!(content.contains("Module path macro marker <FreedomLogger::tests>"));
+        assert!(content.contains("Module path function marker <>"));
+    }
+
+    #[test]
+    fn test_filtered_macro_does_not_evaluate_arguments() {
+        let temp_dir = init_test_logger();
+        let expensive = || -> String { panic!("argument of a filtered message was evaluated") };
+
+        assert_eq!(current_level(), Some(LogLevel::Debug));
+        assert!(!log_enabled(LogLevel::Trace));
+
+        log_trace!("Filtered lazy marker {}", expensive());
+        log_trace_every!(1, "Filtered lazy marker {}", expensive());
+        log_trace_throttle!(std::time::Duration::from_secs(1), "Filtered lazy marker {}", expensive());
+        log_debug!("Enabled lazy marker {}", 1);
+
+        assert_eq!(count_lines_containing(temp_dir, "Filtered lazy marker"), 0);
+        // Under max_level_info and below the DEBUG call is compiled out too
+        let expected = usize::from(statically_enabled(LogLevel::Debug));
+        assert_eq!(count_lines_containing(temp_dir, "Enabled lazy marker"), expected);
+    }
+
+    #[test]
+    #[cfg_attr(
+        any(feature = "max_level_off", feature = "max_level_error", feature = "max_level_warn"),
+        ignore = "INFO calls are compiled out"
+    )]
+    fn test_log_info_every_emits_one_in_n() {
+        let temp_dir = init_test_logger();
+
+        for i in 0..100 {
+            log_info_every!(10, "Sampled every-n marker {}", i);
+        }
+
+        assert_eq!(count_lines_containing(temp_dir, "Sampled every-n marker"), 10);
+    }
+
+    #[test]
+    #[cfg_attr(
+        any(feature = "max_level_off", feature = "max_level_error", feature = "max_level_warn"),
+        ignore = "INFO calls are compiled out"
+    )]
+    fn test_log_info_throttle_suppresses_within_interval() {
+        use std::time::Duration;
+        let temp_dir = init_test_logger();
+
+        for _ in 0..100 {
+            log_info_throttle!(Duration::from_secs(60), "Throttled marker");
+        }
+
+        assert_eq!(count_lines_containing(temp_dir, "Throttled marker"), 1);
+    }
+
+    #[test]
+    #[cfg_attr(any(feature = "max_level_off", feature = "max_level_error"), ignore = "WARNING calls are compiled out")]
+    fn test_log_warning_once_logs_single_line() {
+        let temp_dir = init_test_logger();
+
+        for i in 0..50 {
+            log_warning_once!("Logged once marker {}", i);
+        }
 
-        // Test basic function messages
-        assert!(content.contains("INFO: Test info message"));
-        assert!(content.contains("WARNING: Test warning message"));
-        assert!(content.contains("ERROR: Test error message"));
+        assert_eq!(count_lines_containing(temp_dir, "Logged once marker"), 1);
+    }
+
+    #[test]
+    fn test_sampling_macros_evaluate_level_once() {
+        init_test_logger();
+        let mut evaluated = 0;
 
-        // Test macro messages
-        assert!(content.contains("Database path:"));
-        assert!(content.contains("User 12345 has status: active"));
-        assert!(content.contains("Processing 42 items"));
-        assert!(content.contains("Failed to connect to localhost:5432"));
-        assert!(content.contains("Simple debug message"));
+        log_every!({ evaluated += 1; LogLevel::Info }, 1, "Level once every marker");
+        log_throttle!({ evaluated += 1; LogLevel::Info }, std::time::Duration::from_secs(60), "Level once throttle marker {}", 1);
+
+        assert_eq!(evaluated, 2);
+    }
+
+    #[test]
+    fn test_log_once_evaluates_level_once() {
+        init_test_logger();
+        let mut evaluated = 0;
+
+        log_once!({ evaluated += 1; LogLevel::Info }, "Level once marker");
+        log_once!({ evaluated += 1; LogLevel::Info }, "Level once marker {}", 2);
+
+        assert_eq!(evaluated, 2);
+    }
+
+    #[test]
+    fn test_try_log_init_rejects_invalid_config() {
+        let config = LoggerConfig::basic(Pattern::Basic, PathBuf::from("logs"), String::new());
+
+        match try_log_init(config) {
+            Err(LoggerError::InvalidConfig { field, .. }) => assert_eq!(field, "file_name"),
+            other => panic!("expected InvalidConfig, got {:?}", other),
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    #[cfg(not(any(
+        feature = "max_level_off",
+        feature = "max_level_error",
+        feature = "max_level_warn",
+        feature = "max_level_info",
+        feature = "max_level_debug"
+    )))]
+    fn test_static_max_level_defaults_to_trace() {
+        assert_eq!(STATIC_MAX_LEVEL, LogLevel::Trace);
+        assert!(statically_enabled(LogLevel::Trace));
+        assert!(!statically_enabled(LogLevel::Off));
+    }
+
+    #[test]
+    #[cfg(all(feature = "max_level_info", not(any(
+        feature = "max_level_off",
+        feature = "max_level_error",
+        feature = "max_level_warn"
+    ))))]
+    fn test_max_level_info_removes_debug_calls() {
+        let temp_dir = init_test_logger();
+        let marker = "STATIC_MAX_LEVEL_MARKER";
+        let mut evaluated = false;
+
+        log_debug!("{} {}", marker, { evaluated = true; "debug" });
+        log_debug(marker);
+        log_info!("{} info", marker);
+
+        assert_eq!(STATIC_MAX_LEVEL, LogLevel::Info);
+        assert!(!evaluated);
+        assert!(!log_enabled(LogLevel::Debug));
+        assert_eq!(count_lines_containing(temp_dir, marker), 1);
+    }
+
+    #[test]
+    fn test_log_span_respects_global_level() {
+        let temp_dir = init_test_logger();
+
+        {
+            let span = log_span!("Filtered span marker {}", 1);
+            assert_eq!(span.depth(), 0);
+        }
+
+        // The test logger filters TRACE, so the span writes nothing
+        assert_eq!(count_lines_containing(temp_dir, "Filtered span marker"), 0);
+    }
+
+    #[test]
+    #[cfg_attr(
+        any(feature = "max_level_off", feature = "max_level_error", feature = "max_level_warn"),
+        ignore = "INFO calls are compiled out"
+    )]
+    fn test_log_hex_writes_hexdump_columns() {
+        let temp_dir = init_test_logger();
+
+        log_hex!(LogLevel::Info, b"HEXTEST marker\x01\x02tail");
+        log_hex!(LogLevel::Trace, b"HEXTEST filtered");
+
+        assert_eq!(count_lines_containing(temp_dir, "hexdump (20 bytes)"), 1);
+        assert_eq!(count_lines_containing(
+            temp_dir,
+            "00000000  48 45 58 54 45 53 54 20 6d 61 72 6b 65 72 01 02  |HEXTEST marker..|",
+        ), 1);
+        assert_eq!(count_lines_containing(temp_dir, "00000010  74 61 69 6c"), 1);
+        assert_eq!(count_lines_containing(temp_dir, "|HEXTEST filtered|"), 0);
+    }
+
+    #[test]
+    #[cfg_attr(
+        any(feature = "max_level_off", feature = "max_level_error", feature = "max_level_warn"),
+        ignore = "INFO calls are compiled out"
+    )]
+    fn test_init_then_log_uses_installed_logger() {
+        let temp_dir = init_test_logger();
+        // A second init is ignored: the first logger stays installed
+        log_init(Pattern::Basic, temp_dir.path(), "second_init");
+
+        log_info("Global slot marker");
+
+        assert!(GLOBAL_LOGGER.get().is_some());
+        assert_eq!(count_lines_containing(temp_dir, "Global slot marker"), 1);
+        assert!(!temp_dir.path().join("second_init.log").exists());
+    }
+
+    #[test]
+    fn test_capture_panics_logs_error_line() {
+        let temp_dir = init_test_logger();
+        capture_panics();
+
+        let result = std::panic::catch_unwind(|| panic!("Panic hook marker {}", 7));
+
+        assert!(result.is_err());
+        let content = fs::read_to_string(temp_dir.path().join("test.log")).unwrap();
+        let line = content.lines().find(|line| line.contains("Panic hook marker 7")).unwrap();
+        assert!(line.contains("ERROR: panic: Panic hook marker 7 at src/lib.rs:"));
+    }
+
+    #[test]
+    fn test_format_entry_uses_pattern_without_writing() {
+        let temp_dir = init_test_logger();
+
+        let formatted = format_entry(LogLevel::Trace, "Format only marker");
+
+        assert!(formatted.starts_with('['));
+        assert!(formatted.ends_with("] TRACE: Format only marker <>"));
+        assert_eq!(count_lines_containing(temp_dir, "Format only marker"), 0);
+    }
+
+    #[test]
+    #[cfg_attr(
+        any(feature = "max_level_off", feature = "max_level_error", feature = "max_level_warn"),
+        ignore = "INFO calls are compiled out"
+    )]
+    fn test_free_functions_report_caller_location() {
+        // Child process (see run_in_child)
+        if std::env::var_os("FREEDOMLOGGER_CALLER_CHILD").is_some() {
+            let mut config = LoggerConfig::stdout(Pattern::Custom("{file}:{line} {message}".to_string()), LogLevel::Trace);
+            config.destinations = vec![Destination::Memory(10)];
+            log_init_with_config(config);
+
+            let info_line = line!() + 1;
+            log_info("Caller marker");
+            let at_line = line!() + 1;
+            log_at(LogLevel::Warning, "Log at marker");
+
+            assert_eq!(captured_lines(), [
+                format!("{}:{} Caller marker", file!(), info_line),
+                format!("{}:{} Log at marker", file!(), at_line),
+            ]);
+            return;
+        }
+
+        let output = run_in_child("test_free_functions_report_caller_location", "FREEDOMLOGGER_CALLER_CHILD");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    #[test]
+    #[cfg_attr(any(feature = "max_level_off", feature = "max_level_error"), ignore = "WARNING calls are compiled out")]
+    fn test_log_init_stderr_writes_pattern_to_stderr_only() {
+        // Child process (see run_in_child)
+        if std::env::var_os("FREEDOMLOGGER_STDERR_CHILD").is_some() {
+            log_init_stderr(Pattern::Custom("{level}|{message}".to_string()), LogLevel::Warning);
+            log_info!("filtered out");
+            log_warning!("disk almost full");
+            return;
+        }
+
+        let output = run_in_child("test_log_init_stderr_writes_pattern_to_stderr_only", "FREEDOMLOGGER_STDERR_CHILD");
+        assert!(output.status.success());
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stderr.lines().any(|line| line == "WARNING|disk almost full"), "stderr: {}", stderr);
+        assert!(!stderr.contains("filtered out"));
+        assert!(!stdout.contains("disk almost full"));
+    }
+
+    #[test]
+    #[cfg_attr(
+        any(feature = "max_level_off", feature = "max_level_error", feature = "max_level_warn"),
+        ignore = "INFO calls are compiled out"
+    )]
+    fn test_install_prebuilt_logger_with_custom_writer() {
+        use crate::error::LoggerResult;
+        use std::sync::Mutex;
+
+        /// Keeps every formatted line in memory
+        struct MemoryWriter {
+            lines: Mutex<Vec<String>>,
+        }
+
+        impl LogWriter for MemoryWriter {
+            fn write_entry(&self, _info: &crate::format::LogInfo, formatted: &str, _path: &Path) -> LoggerResult<()> {
+                self.lines.lock().unwrap().push(formatted.to_string());
+                Ok(())
+            }
+        }
+
+        // Child process (see run_in_child)
+        if std::env::var_os("FREEDOMLOGGER_INSTALL_CHILD").is_some() {
+            let temp_dir = tempdir().unwrap();
+            let memory = Arc::new(MemoryWriter { lines: Mutex::new(Vec::new()) });
+            let mut config = LoggerConfig::basic(Pattern::Custom("{level}|{message}".to_string()), temp_dir.path().to_path_buf(), "app".to_string());
+            config.destinations = Vec::new();
+            config.custom_writer = Some(memory.clone());
+
+            install(Arc::new(Logger::new(config.clone()))).unwrap();
+            log_info("Installed logger");
+            assert_eq!(*memory.lines.lock().unwrap(), ["INFO|Installed logger"]);
+
+            assert_eq!(install(Arc::new(Logger::new(config))), Err(LoggerError::AlreadyInitialized));
+            return;
+        }
+
+        let output = run_in_child("test_install_prebuilt_logger_with_custom_writer", "FREEDOMLOGGER_INSTALL_CHILD");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    #[test]
+    #[cfg_attr(
+        any(feature = "max_level_off", feature = "max_level_error", feature = "max_level_warn"),
+        ignore = "INFO calls are compiled out"
+    )]
+    fn test_current_log_path_follows_reconfigure() {
+        // Child process (see run_in_child)
+        if std::env::var_os("FREEDOMLOGGER_PATH_CHILD").is_some() {
+            let temp_dir = tempdir().unwrap();
+            log_init(Pattern::Basic, temp_dir.path(), "first");
+            log_info("Into the first file");
+            log_flush();
+            assert_eq!(current_log_path(), temp_dir.path().join("first.log"));
+            assert!(fs::read_to_string(current_log_path()).unwrap().contains("Into the first file"));
+
+            reconfigure(LoggerConfig::basic(Pattern::Json, temp_dir.path().to_path_buf(), "second".to_string()));
+            log_info("Into the second file");
+            log_flush();
+            assert_eq!(current_log_path(), temp_dir.path().join("second.json"));
+            assert!(fs::read_to_string(current_log_path()).unwrap().contains("Into the second file"));
+            return;
+        }
+
+        let output = run_in_child("test_current_log_path_follows_reconfigure", "FREEDOMLOGGER_PATH_CHILD");
+        assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    }
+}
@@ -0,0 +1,192 @@
+//! Combined size-and-time rotation for FreedomLogger
+//!
+//! "Rotate at midnight OR when the file hits 100MB, whichever comes first":
+//! wraps a `SizeBasedRotation` (for the size limit) and a daily
+//! `TimeBasedRotation`, and rotates when either one is due.
+//!
+//! Backups are named after the day their content belongs to, with a suffix
+//! counting the rotations of that day:
+//! - app.2025-09-06.1.log (first rotation on that day, e.g. size limit)
+//! - app.2025-09-06.2.log (second rotation on that day, e.g. at midnight)
+//! - app.2025-09-07.1.log
+//!
+//! Backups are never renamed afterwards. The oldest backups (by date, then
+//! suffix) are deleted beyond `max_backup_files`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::core::time::{self, Date};
+use crate::error::{LoggerError, LoggerResult};
+use super::{RotationResult, SizeBasedRotation, TimeBasedRotation};
+
+/// Size-or-daily rotation manager
+#[derive(Debug)]
+pub struct CombinedRotation {
+    /// Size limit (and backup extension)
+    size: SizeBasedRotation,
+    /// Day boundary
+    time: TimeBasedRotation,
+    /// Maximum number of dated backups to keep
+    max_backup_files: u32,
+}
+
+impl CombinedRotation {
+    /// Create a rotation manager for "size limit or new day"
+    ///
+    /// # Arguments
+    /// * `max_file_size` - Maximum size in bytes before rotation
+    /// * `max_backup_files` - Number of dated backup files to keep
+    pub fn new(max_file_size: u64, max_backup_files: u32) -> Self {
+        Self {
+            size: SizeBasedRotation::new(max_file_size, max_backup_files),
+            time: TimeBasedRotation::daily(),
+            max_backup_files,
+        }
+    }
+
+    /// Read file dates in UTC (match `LoggerConfig::use_utc`)
+    pub fn with_utc(mut self, use_utc: bool) -> Self {
+        self.time = self.time.with_utc(use_utc);
+        self
+    }
+
+    /// Check if either the size limit is reached or a new day started
+    ///
+    /// # Arguments
+    /// * `log_file_path` - Path to the current log file
+    /// * `today` - Date of the entry about to be written
+    pub fn needs_rotation(&self, log_file_path: &Path, today: Date) -> LoggerResult<bool> {
+        Ok(self.size.needs_rotation(log_file_path)? || self.time_due(log_file_path, today))
+    }
+
+    /// Check only the day boundary (for callers that track the size themselves)
+    pub fn time_due(&self, log_file_path: &Path, today: Date) -> bool {
+        self.time.needs_rotation(log_file_path, today)
+    }
+
+    /// Check if rotation is needed and perform it if necessary
+    pub fn check_and_rotate(&self, log_file_path: &Path, today: Date) -> RotationResult {
+        match self.needs_rotation(log_file_path, today) {
+            Ok(true) => self.rotate(log_file_path, today),
+            Ok(false) => RotationResult::NotNeeded,
+            Err(error) => RotationResult::Failed(error),
+        }
+    }
+
+    /// Move the current file to the next dated backup and start a new period
+    ///
+    /// The backup is dated with the day the file was started (today for a
+    /// size rotation, yesterday or earlier at a day boundary).
+    pub fn rotate(&self, log_file_path: &Path, today: Date) -> RotationResult {
+        let backup = self.next_backup_path(log_file_path, today);
+
+        if fs::rename(log_file_path, &backup).is_err() {
+            return RotationResult::Failed(LoggerError::RotationFailed {
+                current_file: log_file_path.display().to_string(),
+                backup_file: backup.display().to_string(),
+                reason: "Failed to move current log to dated backup".to_string(),
+            });
+        }
+        self.time.start_period(today);
+
+        match self.prune_backups(log_file_path) {
+            Ok(()) => RotationResult::Completed,
+            Err(error) => RotationResult::Failed(error),
+        }
+    }
+
+    /// Path the current log is moved to by `rotate(log_file_path, today)`
+    ///
+    /// The first unused app.<date>.<n>.<ext>, dated with the day of the file.
+    pub fn next_backup_path(&self, log_file_path: &Path, today: Date) -> PathBuf {
+        let date = self.time.period().unwrap_or(today);
+        let directory = log_file_path.parent().unwrap_or(Path::new("."));
+        let base_name = stem(log_file_path);
+        let extension = self.size.backup_extension(log_file_path);
+
+        (1..)
+            .map(|index| directory.join(format!("{}.{}.{}.{}", base_name, time::format_date(date), index, extension)))
+            .find(|backup| !backup.exists())
+            .expect("unbounded backup index")
+    }
+
+    /// Delete the oldest dated backups beyond `max_backup_files`
+    fn prune_backups(&self, log_file_path: &Path) -> LoggerResult<()> {
+        let directory = log_file_path.parent().unwrap_or(Path::new("."));
+        let prefix = format!("{}.", stem(log_file_path));
+        let suffix = format!(".{}", self.size.backup_extension(log_file_path));
+
+        let mut backups: Vec<(Date, u32, PathBuf)> = fs::read_dir(directory)
+            .map(|entries| entries.flatten().map(|entry| entry.path()).collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|path| {
+                let name = path.file_name()?.to_str()?;
+                let (date, index) = name.strip_prefix(&prefix)?.strip_suffix(&suffix)?.split_once('.')?;
+                let date = time::parse_date(date)?;
+                Some((date, index.parse().ok()?, path))
+            })
+            .collect();
+
+        backups.sort();
+        let excess = backups.len().saturating_sub(self.max_backup_files as usize);
+        for (_, _, backup) in backups.into_iter().take(excess) {
+            fs::remove_file(&backup).map_err(|_| LoggerError::RotationFailed {
+                current_file: log_file_path.display().to_string(),
+                backup_file: backup.display().to_string(),
+                reason: "Failed to delete oldest dated backup".to_string(),
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// File name without extension ("app" for app.log)
+fn stem(log_file_path: &Path) -> String {
+    log_file_path
+        .file_stem()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_size_limit_rotates_mid_day_with_dated_backups() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("app.log");
+        let day = Date::from_ymd_opt(2025, 9, 6).unwrap();
+        let rotation = CombinedRotation::new(10, 2);
+
+        // Same day, so only the size limit can trigger these rotations
+        for content in ["first file", "second file", "third file"] {
+            assert_eq!(rotation.check_and_rotate(&log_path, day), RotationResult::NotNeeded);
+            fs::write(&log_path, content).unwrap();
+            assert_eq!(rotation.check_and_rotate(&log_path, day), RotationResult::Completed);
+        }
+
+        // Oldest backup pruned beyond max_backup_files
+        assert!(!temp_dir.path().join("app.2025-09-06.1.log").exists());
+        assert_eq!(fs::read_to_string(temp_dir.path().join("app.2025-09-06.2.log")).unwrap(), "second file");
+        assert_eq!(fs::read_to_string(temp_dir.path().join("app.2025-09-06.3.log")).unwrap(), "third file");
+    }
+
+    #[test]
+    fn test_new_day_rotates_small_file() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("app.log");
+        let day = Date::from_ymd_opt(2025, 9, 6).unwrap();
+        let rotation = CombinedRotation::new(1024, 5);
+
+        assert_eq!(rotation.check_and_rotate(&log_path, day), RotationResult::NotNeeded);
+        fs::write(&log_path, "small").unwrap();
+        let next_day = day.succ_opt().unwrap();
+        assert_eq!(rotation.check_and_rotate(&log_path, next_day), RotationResult::Completed);
+
+        assert_eq!(fs::read_to_string(temp_dir.path().join("app.2025-09-06.1.log")).unwrap(), "small");
+        assert_eq!(rotation.check_and_rotate(&log_path, next_day), RotationResult::NotNeeded);
+    }
+}
@@ -0,0 +1,172 @@
+//! Rotation index for FreedomLogger
+//!
+//! With `LoggerConfig::rotation_index` every rotation is recorded in a
+//! manifest next to the log, "<file_name>.index.json": one JSON object per
+//! line, oldest backup first:
+//! {"backup_file":"app.1.log","start_time":"2025-09-06T10:00:00Z","end_time":"2025-09-06T11:59:59Z","size":10485760}
+//! - backup_file: file name of the backup, in the log directory
+//! - start_time / end_time: first and last entry the logger wrote to it (RFC 3339)
+//! - size: bytes of the backup right after the rotation
+//!
+//! A tool finds the backup covering a timestamp by looking for the record
+//! with start_time <= t <= end_time.
+//!
+//! Numbered backups (size and line rotation) move up one number on every
+//! rotation; the index renames its records along with them and drops the
+//! backups the rotation deleted (max_backup_files, max_total_size). The
+//! index is rewritten through a temporary file, so readers never see it half
+//! written.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use crate::core::time::Timestamp;
+use crate::error::{IoResultExt, LoggerResult};
+use crate::format::{escape_json_string, format_rfc3339};
+
+/// Start of every record line, followed by the escaped backup file name
+const RECORD_START: &str = "{\"backup_file\":\"";
+
+/// Manifest of rotated backups and the time span each one covers
+#[derive(Debug)]
+pub struct RotationIndex {
+    /// Path of the index file
+    path: PathBuf,
+    /// Write the times in UTC instead of local time
+    use_utc: bool,
+    /// First and last entry time of the current log file
+    span: Mutex<Option<(Timestamp, Timestamp)>>,
+}
+
+impl RotationIndex {
+    /// Create an index written to `path` (created with the first rotation)
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, use_utc: false, span: Mutex::new(None) }
+    }
+
+    /// Write the entry times in UTC instead of local time
+    pub fn with_utc(mut self, use_utc: bool) -> Self {
+        self.use_utc = use_utc;
+        self
+    }
+
+    /// Path of the index file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Extend the time span of the current log file with an entry written at `now`
+    pub fn record_entry(&self, now: Timestamp) {
+        let mut span = self.span.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match span.as_mut() {
+            Some((_, end)) => *end = now,
+            None => *span = Some((now, now)),
+        }
+    }
+
+    /// Record a completed rotation and start the span of the new file
+    ///
+    /// # Arguments
+    /// * `backup` - Where the rotated file went (None: deleted, nothing to record)
+    /// * `shifted` - Existing numbered backups moved up one number
+    /// * `now` - Span of a file that got no entries from this logger
+    pub fn record_rotation(&self, backup: Option<&Path>, shifted: bool, now: Timestamp) -> LoggerResult<()> {
+        let span = self.span.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).take();
+        let backup = match backup {
+            Some(backup) => backup,
+            None => return Ok(()),
+        };
+        let (start, end) = span.unwrap_or((now, now));
+        let directory = backup.parent().unwrap_or(Path::new("."));
+
+        let existing = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(error).with_path(&self.path),
+        };
+
+        let mut lines: Vec<String> = Vec::new();
+        for line in existing.lines().filter(|line| !line.trim().is_empty()) {
+            let Some((file_name, rest)) = parse_record(line) else {
+                lines.push(line.to_string()); // Not ours: kept as is
+                continue;
+            };
+            let file_name = if shifted { next_backup_name(&file_name) } else { file_name };
+            if directory.join(&file_name).exists() {
+                lines.push(format!("{}{}{}", RECORD_START, escape_json_string(&file_name), rest));
+            }
+        }
+
+        let file_name = backup.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+        let size = fs::metadata(backup).map_or(0, |metadata| metadata.len());
+        lines.push(format!(
+            "{}{}\",\"start_time\":\"{}\",\"end_time\":\"{}\",\"size\":{}}}",
+            RECORD_START,
+            escape_json_string(&file_name),
+            format_rfc3339(start, self.use_utc),
+            format_rfc3339(end, self.use_utc),
+            size,
+        ));
+
+        let mut content = lines.join("\n");
+        content.push('\n');
+        let temp_path = self.path.with_extension("json.tmp");
+        fs::write(&temp_path, &content).with_write(&temp_path, content.len())?;
+        fs::rename(&temp_path, &self.path).with_path(&self.path)
+    }
+}
+
+/// Split a record line into the unescaped backup file name and the rest
+/// of the line after the name (starting with its closing quote)
+fn parse_record(line: &str) -> Option<(String, &str)> {
+    let encoded = line.strip_prefix(RECORD_START)?;
+    let mut name = String::new();
+    let mut chars = encoded.char_indices();
+    while let Some((index, character)) = chars.next() {
+        match character {
+            '"' => return Some((name, &encoded[index..])),
+            '\\' => match chars.next()?.1 {
+                'n' => name.push('\n'),
+                'r' => name.push('\r'),
+                't' => name.push('\t'),
+                'u' => {
+                    let hex: String = (0..4).filter_map(|_| chars.next().map(|(_, digit)| digit)).collect();
+                    name.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                }
+                escaped => name.push(escaped),
+            },
+            character => name.push(character),
+        }
+    }
+    None
+}
+
+/// Name of a numbered backup after shifting: "app.2.log" becomes "app.3.log"
+///
+/// Names without a number before the extension (e.g. dated backups) are kept.
+fn next_backup_name(file_name: &str) -> String {
+    let mut parts = file_name.rsplitn(3, '.');
+    let (Some(extension), Some(number), Some(stem)) = (parts.next(), parts.next(), parts.next()) else {
+        return file_name.to_string();
+    };
+    match number.parse::<u32>() {
+        Ok(number) => format!("{}.{}.{}", stem, number + 1, extension),
+        Err(_) => file_name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_line_round_trip_and_renumbering() {
+        let line = format!("{}{}\",\"size\":3}}", RECORD_START, escape_json_string("we\"ird.1.log"));
+        assert_eq!(parse_record(&line), Some(("we\"ird.1.log".to_string(), "\",\"size\":3}")));
+        assert_eq!(parse_record("{\"other\":1}"), None);
+
+        assert_eq!(next_backup_name("app.1.log"), "app.2.log");
+        assert_eq!(next_backup_name("app.v2.9.json"), "app.v2.10.json");
+        assert_eq!(next_backup_name("app.2025-09-06.log"), "app.2025-09-06.log");
+    }
+}
@@ -0,0 +1,134 @@
+//! Line-count-based log rotation for FreedomLogger
+//!
+//! Rotates the log file after a fixed number of lines ("rotate every 100k
+//! lines") instead of a number of bytes. Backups use the same rolling scheme
+//! as size-based rotation (app.1.log, app.2.log, ...), including the
+//! optional total size cap.
+//!
+//! The line count of the current file is kept in memory: it is seeded by
+//! counting the newlines of the file the first time a path is used (and
+//! again when the path changes, e.g. with daily files), then incremented for
+//! every line written.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use super::{RotationResult, SizeBasedRotation};
+
+/// Line-count-based rotation manager
+#[derive(Debug)]
+pub struct LineBasedRotation {
+    /// Number of lines after which the file rotates
+    max_lines: u64,
+    /// Lines in the current file
+    line_count: AtomicU64,
+    /// Path the line count belongs to (None until seeded)
+    counted_path: Mutex<Option<PathBuf>>,
+    /// Backup shifting (its size limit is not used)
+    backups: SizeBasedRotation,
+}
+
+impl LineBasedRotation {
+    /// Create a new line-based rotation manager
+    ///
+    /// # Arguments
+    /// * `max_lines` - Lines per file before rotation (e.g., 100_000)
+    /// * `max_backup_files` - Number of backup files to keep
+    pub fn new(max_lines: u64, max_backup_files: u32) -> Self {
+        Self {
+            max_lines,
+            line_count: AtomicU64::new(0),
+            counted_path: Mutex::new(None),
+            backups: SizeBasedRotation::new(u64::MAX, max_backup_files),
+        }
+    }
+
+    /// Cap the combined size of the current file and its backups
+    ///
+    /// # Arguments
+    /// * `max_total_size` - Size in bytes, or None for no cap
+    pub fn with_max_total_size(mut self, max_total_size: Option<u64>) -> Self {
+        self.backups = self.backups.with_max_total_size(max_total_size);
+        self
+    }
+
+    /// Count lines written to the current file
+    ///
+    /// # Arguments
+    /// * `lines` - Number of lines just written
+    pub fn record_lines(&self, lines: u64) {
+        self.line_count.fetch_add(lines, Ordering::Relaxed);
+    }
+
+    /// Number of lines counted for the current file
+    pub fn line_count(&self) -> u64 {
+        self.line_count.load(Ordering::Relaxed)
+    }
+
+    /// Check if the file at `log_file_path` reached `max_lines`
+    ///
+    /// Seeds the count from the file on its first check.
+    pub fn needs_rotation(&self, log_file_path: &Path) -> bool {
+        let mut counted_path = self.counted_path.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if counted_path.as_deref() != Some(log_file_path) {
+            self.line_count.store(count_lines(log_file_path), Ordering::Relaxed);
+            *counted_path = Some(log_file_path.to_path_buf());
+        }
+        self.line_count() >= self.max_lines
+    }
+
+    /// Check if rotation is needed and perform it if necessary
+    ///
+    /// # Arguments
+    /// * `log_file_path` - Path to the current log file
+    pub fn check_and_rotate(&self, log_file_path: &Path) -> RotationResult {
+        if self.needs_rotation(log_file_path) {
+            self.rotate(log_file_path)
+        } else {
+            RotationResult::NotNeeded
+        }
+    }
+
+    /// Path the current log is moved to by `rotate` (app.1.log)
+    pub fn first_backup_path(&self, log_file_path: &Path) -> Option<PathBuf> {
+        self.backups.first_backup_path(log_file_path)
+    }
+
+    /// Shift the backups and start counting a new file
+    pub fn rotate(&self, log_file_path: &Path) -> RotationResult {
+        let result = self.backups.rotate(log_file_path);
+        if result == RotationResult::Completed {
+            self.line_count.store(0, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
+/// Count the newlines in a file (0 if it can't be read)
+fn count_lines(path: &Path) -> u64 {
+    fs::read(path).map_or(0, |content| content.iter().filter(|&&byte| byte == b'\n').count() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_count_is_seeded_from_existing_file() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("app.log");
+        fs::write(&log_path, "one\ntwo\nthree\n").unwrap();
+
+        let rotation = LineBasedRotation::new(4, 2);
+        assert!(!rotation.needs_rotation(&log_path));
+        assert_eq!(rotation.line_count(), 3);
+
+        rotation.record_lines(1);
+        assert_eq!(rotation.check_and_rotate(&log_path), RotationResult::Completed);
+        assert_eq!(rotation.line_count(), 0);
+        assert!(temp_dir.path().join("app.1.log").exists());
+        assert!(!log_path.exists());
+    }
+}
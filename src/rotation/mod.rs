@@ -1,18 +1,26 @@
-/// Log rotation module for FreedomLogger
-///
-/// This module handles automatic log file rotation to prevent files from
-/// growing too large. Currently supports size-based rotation, with future
-/// plans for time-based rotation (daily, weekly, monthly).
-///
-/// Rotation strategies:
-/// - Size-based: Rotate when file exceeds configured size limit
-/// - Time-based: Rotate at specific time intervals (TODO: future feature)
+//! Log rotation module for FreedomLogger
+//!
+//! This module handles automatic log file rotation to prevent files from
+//! growing too large or spanning several days.
+//!
+//! Rotation strategies:
+//! - Size-based: Rotate when file exceeds configured size limit
+//! - Line-based: Rotate after a fixed number of lines
+//! - Time-based: Detect a new day (weekly, monthly: TODO)
+//! - Combined: Size limit or new day, whichever comes first, with dated backups
+//!
+//! The optional rotation index records the time span of every backup.
 
 // Re-export all rotation types and functions
 pub use size_based::{SizeBasedRotation, RotationResult};
+pub use line_based::LineBasedRotation;
+pub use time_based::TimeBasedRotation;
+pub use combined::CombinedRotation;
+pub use index::RotationIndex;
 
 // Import rotation implementations  
 pub mod size_based;
-
-// TODO: Future rotation strategies
-// pub mod time_based;  // Daily, weekly, monthly rotation
\ No newline at end of file
+pub mod line_based;
+pub mod time_based;
+pub mod combined;
+pub mod index;
@@ -1,12 +1,20 @@
-/// Size-based log rotation for FreedomLogger
-///
-/// Handles automatic log file rotation when files exceed the configured size limit.
-/// Maintains a configurable number of backup files in rolling fashion:
-/// - app.log (current)
-/// - app.1.log (most recent backup)
-/// - app.2.log (older backup)
-/// - ...
-/// - app.N.log (oldest backup, gets deleted when limit reached)
+//! Size-based log rotation for FreedomLogger
+//!
+//! Handles automatic log file rotation when files exceed the configured size limit.
+//! Maintains a configurable number of backup files in rolling fashion:
+//! - app.log (current)
+//! - app.1.log (most recent backup)
+//! - app.2.log (older backup)
+//! - ...
+//! - app.N.log (oldest backup, gets deleted when limit reached)
+//!
+//! Backups keep the extension of the file being rotated (app.json gives
+//! app.1.json, ...). `with_extension` forces a specific extension instead.
+//!
+//! An optional total size cap (`with_max_total_size`) bounds the disk used by
+//! the whole set: after each rotation the oldest backups are deleted until the
+//! current file plus remaining backups fit. Only `app.N.<ext>` files of this
+//! log are considered, never other files in the directory.
 
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -33,6 +41,11 @@ pub struct SizeBasedRotation {
     max_file_size: u64,
     /// Maximum number of backup files to keep
     max_backup_files: u32,
+    /// Forced extension for backups (without dot)
+    /// None uses the extension of the rotated file itself
+    extension: Option<String>,
+    /// Maximum combined size of the current file and all backups
+    max_total_size: Option<u64>,
 }
 
 impl SizeBasedRotation {
@@ -45,9 +58,29 @@ impl SizeBasedRotation {
         Self {
             max_file_size,
             max_backup_files,
+            extension: None,
+            max_total_size: None,
         }
     }
 
+    /// Use a custom extension for backup file names
+    ///
+    /// # Arguments
+    /// * `extension` - Extension without dot (e.g., "txt" gives app.1.txt)
+    pub fn with_extension(mut self, extension: &str) -> Self {
+        self.extension = Some(extension.trim_start_matches('.').to_string());
+        self
+    }
+
+    /// Cap the combined size of the current file and its backups
+    ///
+    /// # Arguments
+    /// * `max_total_size` - Size in bytes, or None for no cap (only the backup count applies)
+    pub fn with_max_total_size(mut self, max_total_size: Option<u64>) -> Self {
+        self.max_total_size = max_total_size;
+        self
+    }
+
     /// Check if rotation is needed and perform it if necessary
     ///
     /// # Arguments
@@ -57,7 +90,7 @@ impl SizeBasedRotation {
     /// RotationResult indicating what happened
     pub fn check_and_rotate(&self, log_file_path: &Path) -> RotationResult {
         match self.needs_rotation(log_file_path) {
-            Ok(true) => self.perform_rotation(log_file_path),
+            Ok(true) => self.rotate(log_file_path),
             Ok(false) => RotationResult::NotNeeded,
             Err(error) => RotationResult::Failed(error),
         }
@@ -70,7 +103,7 @@ impl SizeBasedRotation {
     ///
     /// # Returns
     /// Ok(true) if rotation needed, Ok(false) if not, Err if can't check
-    fn needs_rotation(&self, log_file_path: &Path) -> LoggerResult<bool> {
+    pub fn needs_rotation(&self, log_file_path: &Path) -> LoggerResult<bool> {
         match fs::metadata(log_file_path) {
             Ok(metadata) => Ok(metadata.len() >= self.max_file_size),
             Err(_) => {
@@ -80,6 +113,20 @@ impl SizeBasedRotation {
         }
     }
 
+    /// Get the extension to use for backup files
+    ///
+    /// Forced extension if set, otherwise the extension of the current file
+    /// (falling back to "log" for files without one).
+    pub(crate) fn backup_extension(&self, log_file_path: &Path) -> String {
+        match &self.extension {
+            Some(extension) => extension.clone(),
+            None => log_file_path
+                .extension()
+                .map(|extension| extension.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "log".to_string()),
+        }
+    }
+
     /// Perform the actual rotation process
     ///
     /// Steps:
@@ -87,7 +134,10 @@ impl SizeBasedRotation {
     /// 2. Shift all backup files up one number (app.1.log → app.2.log)
     /// 3. Move current file to .1 backup (app.log → app.1.log)
     /// 4. Current log file slot is now empty for new logs
-    fn perform_rotation(&self, log_file_path: &Path) -> RotationResult {
+    ///
+    /// Does not check the file size: for callers that already decided
+    /// rotation is due (see `check_and_rotate` otherwise).
+    pub fn rotate(&self, log_file_path: &Path) -> RotationResult {
         let base_name = match log_file_path.file_stem() {
             Some(name) => name.to_string_lossy(),
             None => return RotationResult::Failed(LoggerError::RotationFailed {
@@ -98,41 +148,37 @@ impl SizeBasedRotation {
         };
 
         let directory = log_file_path.parent().unwrap_or(Path::new("."));
+        let extension = self.backup_extension(log_file_path);
 
         // Step 1: Delete oldest backup if it exists
         if self.max_backup_files > 0 {
-            let oldest_backup = directory.join(format!("{}.{}.log", base_name, self.max_backup_files));
-            if oldest_backup.exists() {
-                if let Err(_) = fs::remove_file(&oldest_backup) {
-                    return RotationResult::Failed(LoggerError::RotationFailed {
-                        current_file: log_file_path.display().to_string(),
-                        backup_file: oldest_backup.display().to_string(),
-                        reason: "Failed to delete oldest backup".to_string(),
-                    });
-                }
+            let oldest_backup = directory.join(format!("{}.{}.{}", base_name, self.max_backup_files, extension));
+            if oldest_backup.exists() && fs::remove_file(&oldest_backup).is_err() {
+                return RotationResult::Failed(LoggerError::RotationFailed {
+                    current_file: log_file_path.display().to_string(),
+                    backup_file: oldest_backup.display().to_string(),
+                    reason: "Failed to delete oldest backup".to_string(),
+                });
             }
         }
 
         // Step 2: Shift existing backups up one number (reverse order to avoid conflicts)
         for i in (1..self.max_backup_files).rev() {
-            let current_backup = directory.join(format!("{}.{}.log", base_name, i));
-            let next_backup = directory.join(format!("{}.{}.log", base_name, i + 1));
-
-            if current_backup.exists() {
-                if let Err(_) = fs::rename(&current_backup, &next_backup) {
-                    return RotationResult::Failed(LoggerError::RotationFailed {
-                        current_file: current_backup.display().to_string(),
-                        backup_file: next_backup.display().to_string(),
-                        reason: "Failed to shift backup file".to_string(),
-                    });
-                }
+            let current_backup = directory.join(format!("{}.{}.{}", base_name, i, extension));
+            let next_backup = directory.join(format!("{}.{}.{}", base_name, i + 1, extension));
+
+            if current_backup.exists() && fs::rename(&current_backup, &next_backup).is_err() {
+                return RotationResult::Failed(LoggerError::RotationFailed {
+                    current_file: current_backup.display().to_string(),
+                    backup_file: next_backup.display().to_string(),
+                    reason: "Failed to shift backup file".to_string(),
+                });
             }
         }
 
         // Step 3: Move current log to first backup position
-        if self.max_backup_files > 0 {
-            let first_backup = directory.join(format!("{}.1.log", base_name));
-            if let Err(_) = fs::rename(log_file_path, &first_backup) {
+        if let Some(first_backup) = self.first_backup_path(log_file_path) {
+            if fs::rename(log_file_path, &first_backup).is_err() {
                 return RotationResult::Failed(LoggerError::RotationFailed {
                     current_file: log_file_path.display().to_string(),
                     backup_file: first_backup.display().to_string(),
@@ -141,7 +187,7 @@ impl SizeBasedRotation {
             }
         } else {
             // No backups configured - just delete current file
-            if let Err(_) = fs::remove_file(log_file_path) {
+            if fs::remove_file(log_file_path).is_err() {
                 return RotationResult::Failed(LoggerError::RotationFailed {
                     current_file: log_file_path.display().to_string(),
                     backup_file: "none".to_string(),
@@ -150,8 +196,70 @@ impl SizeBasedRotation {
             }
         }
 
+        // Step 4: Prune oldest backups beyond the total size cap
+        if let Err(error) = self.enforce_total_size(log_file_path, directory, &base_name, &extension) {
+            return RotationResult::Failed(error);
+        }
+
         RotationResult::Completed
     }
+
+    /// Path the current log is moved to by `rotate` (app.1.log)
+    ///
+    /// # Returns
+    /// None without backups (`max_backup_files` = 0: the log is deleted)
+    pub fn first_backup_path(&self, log_file_path: &Path) -> Option<PathBuf> {
+        if self.max_backup_files == 0 {
+            return None;
+        }
+        let base_name = log_file_path.file_stem()?.to_string_lossy();
+        let directory = log_file_path.parent().unwrap_or(Path::new("."));
+        Some(directory.join(format!("{}.1.{}", base_name, self.backup_extension(log_file_path))))
+    }
+
+    /// Delete the oldest backups until the log set fits in `max_total_size`
+    ///
+    /// Sums the current file plus app.1 ... app.N backups of this log only,
+    /// then removes backups from the highest number down. The current file
+    /// itself is never deleted.
+    fn enforce_total_size(
+        &self,
+        log_file_path: &Path,
+        directory: &Path,
+        base_name: &str,
+        extension: &str,
+    ) -> LoggerResult<()> {
+        let max_total_size = match self.max_total_size {
+            Some(max_total_size) => max_total_size,
+            None => return Ok(()),
+        };
+
+        let file_size = |path: &Path| fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(0);
+
+        let backups: Vec<_> = (1..=self.max_backup_files)
+            .map(|i| directory.join(format!("{}.{}.{}", base_name, i, extension)))
+            .filter(|backup| backup.exists())
+            .collect();
+
+        let mut total_size = file_size(log_file_path)
+            + backups.iter().map(|backup| file_size(backup)).sum::<u64>();
+
+        for backup in backups.iter().rev() {
+            if total_size <= max_total_size {
+                break;
+            }
+
+            let backup_size = file_size(backup);
+            fs::remove_file(backup).map_err(|_| LoggerError::RotationFailed {
+                current_file: log_file_path.display().to_string(),
+                backup_file: backup.display().to_string(),
+                reason: "Failed to delete backup over the total size cap".to_string(),
+            })?;
+            total_size -= backup_size;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -168,7 +276,7 @@ mod tests {
 
         // Create small file (100 bytes)
         let mut file = File::create(&log_path).unwrap();
-        file.write_all(&vec![b'x'; 100]).unwrap();
+        file.write_all(&[b'x'; 100]).unwrap();
 
         let rotation = SizeBasedRotation::new(1000, 3); // 1KB limit
         let result = rotation.check_and_rotate(&log_path);
@@ -183,7 +291,7 @@ mod tests {
 
         // Create large file (2KB)
         let mut file = File::create(&log_path).unwrap();
-        file.write_all(&vec![b'x'; 2048]).unwrap();
+        file.write_all(&[b'x'; 2048]).unwrap();
         drop(file); // Close file
 
         let rotation = SizeBasedRotation::new(1000, 2); // 1KB limit, 2 backups
@@ -209,4 +317,55 @@ mod tests {
 
         assert_eq!(result, RotationResult::NotNeeded);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_rotation_with_custom_extension_shifts_backups() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("app.txt");
+        let rotation = SizeBasedRotation::new(10, 3).with_extension("txt");
+
+        // Rotate twice so the first backup gets shifted to .2
+        for content in ["first rotation", "second rotation"] {
+            fs::write(&log_path, content).unwrap();
+            assert_eq!(rotation.check_and_rotate(&log_path), RotationResult::Completed);
+        }
+
+        assert!(!log_path.exists());
+        assert_eq!(fs::read_to_string(temp_dir.path().join("app.1.txt")).unwrap(), "second rotation");
+        assert_eq!(fs::read_to_string(temp_dir.path().join("app.2.txt")).unwrap(), "first rotation");
+        assert!(!temp_dir.path().join("app.1.log").exists());
+    }
+
+    #[test]
+    fn test_json_rotation_keeps_json_extension() {
+        let temp_dir = tempdir().unwrap();
+        let json_path = temp_dir.path().join("app.json");
+        fs::write(&json_path, vec![b'x'; 2048]).unwrap();
+
+        let rotation = SizeBasedRotation::new(1000, 2);
+        assert_eq!(rotation.check_and_rotate(&json_path), RotationResult::Completed);
+
+        assert!(!json_path.exists());
+        assert!(temp_dir.path().join("app.1.json").exists());
+        assert!(!temp_dir.path().join("app.1.log").exists());
+    }
+
+    #[test]
+    fn test_total_size_cap_prunes_oldest_backups() {
+        let temp_dir = tempdir().unwrap();
+        let dir = temp_dir.path();
+        for name in ["app.log", "app.1.log", "app.2.log", "app.3.log", "other.1.log"] {
+            fs::write(dir.join(name), [b'x'; 100]).unwrap();
+        }
+
+        let rotation = SizeBasedRotation::new(50, 5).with_max_total_size(Some(250));
+        assert_eq!(rotation.check_and_rotate(&dir.join("app.log")), RotationResult::Completed);
+
+        // After shifting there were 4 backups (400 bytes) - the two oldest go
+        assert!(dir.join("app.1.log").exists());
+        assert!(dir.join("app.2.log").exists());
+        assert!(!dir.join("app.3.log").exists());
+        assert!(!dir.join("app.4.log").exists());
+        assert!(dir.join("other.1.log").exists());
+    }
+}
@@ -0,0 +1,85 @@
+//! Time-based rotation decision for FreedomLogger
+//!
+//! Tracks the day the current log file belongs to and reports when a new
+//! day has started. The first check takes the day from the file's last
+//! modification time, so a file left over from yesterday rotates on the
+//! first entry of today.
+//!
+//! Only decides *when* to rotate; `CombinedRotation` does the renaming.
+//! Currently daily periods only (weekly / monthly: TODO).
+
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use crate::core::time::{self, Date};
+
+/// Daily rotation trigger
+#[derive(Debug, Default)]
+pub struct TimeBasedRotation {
+    /// Day of the current file (None until the first check)
+    period: Mutex<Option<Date>>,
+    /// Use the UTC date of the file's modification time instead of the local date
+    use_utc: bool,
+}
+
+impl TimeBasedRotation {
+    /// Rotate whenever the date changes
+    pub fn daily() -> Self {
+        Self::default()
+    }
+
+    /// Read file dates in UTC (match `LoggerConfig::use_utc`)
+    pub fn with_utc(mut self, use_utc: bool) -> Self {
+        self.use_utc = use_utc;
+        self
+    }
+
+    /// Check if the current file belongs to an earlier day than `today`
+    ///
+    /// # Arguments
+    /// * `log_file_path` - Path to the current log file (read on the first check)
+    /// * `today` - Date of the entry about to be written
+    pub fn needs_rotation(&self, log_file_path: &Path, today: Date) -> bool {
+        let mut period = self.period.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let current = *period.get_or_insert_with(|| self.file_date(log_file_path).unwrap_or(today));
+        current < today
+    }
+
+    /// Day of the current file, if known
+    pub fn period(&self) -> Option<Date> {
+        *self.period.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Start a new period after the file was rotated
+    pub fn start_period(&self, today: Date) {
+        *self.period.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(today);
+    }
+
+    /// Date of the file's last modification (None if it doesn't exist)
+    fn file_date(&self, log_file_path: &Path) -> Option<Date> {
+        let modified = fs::metadata(log_file_path).and_then(|metadata| metadata.modified()).ok()?;
+        Some(time::date_of_system_time(modified, self.use_utc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_rotation_due_when_date_changes() {
+        let temp_dir = tempdir().unwrap();
+        let log_path = temp_dir.path().join("app.log");
+        let day = Date::from_ymd_opt(2025, 9, 6).unwrap();
+
+        // Without a file the period starts today
+        let rotation = TimeBasedRotation::daily();
+        assert!(!rotation.needs_rotation(&log_path, day));
+        assert_eq!(rotation.period(), Some(day));
+        assert!(rotation.needs_rotation(&log_path, day.succ_opt().unwrap()));
+
+        rotation.start_period(day.succ_opt().unwrap());
+        assert!(!rotation.needs_rotation(&log_path, day.succ_opt().unwrap()));
+    }
+}